@@ -0,0 +1,660 @@
+//! This module contains the access control lists: the users, their passwords and what they can run.
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The user connections are authenticated as on connecting.
+pub const DEFAULT_USER: &str = "default";
+
+/// The command categories, as listed by ACL CAT.
+pub const CATEGORIES: [&str; 8] = [
+    "admin",
+    "connection",
+    "dangerous",
+    "fast",
+    "pubsub",
+    "read",
+    "slow",
+    "write",
+];
+
+/// Gets the categories of a command from its flags.
+/// Admin commands are dangerous, as are the commands flagged `dangerous`, such as FLUSHALL.
+pub fn categories(flags: &[&str]) -> Vec<&'static str> {
+    let mut categories = vec![];
+    for flag in flags {
+        match *flag {
+            "admin" => categories.extend(["admin", "dangerous"]),
+            "dangerous" => categories.push("dangerous"),
+            "no_auth" => categories.push("connection"),
+            "pubsub" => categories.push("pubsub"),
+            "readonly" => categories.push("read"),
+            "write" => categories.push("write"),
+            _ => {}
+        }
+    }
+    categories.push(if flags.contains(&"fast") {
+        "fast"
+    } else {
+        "slow"
+    });
+    categories.sort_unstable();
+    categories.dedup();
+    categories
+}
+
+/// The error for credentials that do not match an enabled user.
+#[derive(Debug, thiserror::Error)]
+#[error("WRONGPASS invalid username-password pair or user is disabled.")]
+pub struct WrongPass;
+
+/// The error for a command the user is not allowed to run.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum Denied {
+    #[error("NOPERM User {0} has no permissions to run the '{1}' command")]
+    Command(String, String),
+    #[error("NOPERM No permissions to access a key")]
+    Key,
+    #[error("NOPERM No permissions to access a channel")]
+    Channel,
+}
+
+/// Hashes a password into the lowercase hexadecimal SHA-256 digest stored for it.
+pub fn hash_password(password: &str) -> String {
+    use sha2::Digest;
+    sha2::Sha256::digest(password.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// A rule allowing or denying a lowercase command name or a category, such as `+get` or `-@write`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandRule {
+    pub allow: bool,
+    /// The command name, or the category prefixed by `@`.
+    pub target: String,
+}
+
+impl std::fmt::Display for CommandRule {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "{}{}", if self.allow { '+' } else { '-' }, self.target)
+    }
+}
+
+/// A user, with its passwords and the commands, keys and channels it can access.
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
+    pub name: String,
+    pub enabled: bool,
+    /// Whether any password authenticates the user.
+    pub nopass: bool,
+    /// The SHA-256 digests of the passwords.
+    pub passwords: Vec<String>,
+    /// The rules allowing or denying commands, where later rules override earlier ones and
+    /// commands no rule allows are denied.
+    pub command_rules: Vec<CommandRule>,
+    /// The glob patterns of the keys the user can access.
+    pub key_patterns: Vec<String>,
+    /// The glob patterns of the channels the user can access.
+    pub channel_patterns: Vec<String>,
+}
+
+impl User {
+    /// Creates a disabled user that cannot run any command.
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        Self {
+            name: name.into(),
+            enabled: false,
+            nopass: false,
+            passwords: vec![],
+            command_rules: vec![],
+            key_patterns: vec![],
+            channel_patterns: vec![],
+        }
+    }
+
+    /// Applies an ACL SETUSER rule, such as `on`, `>password`, `~key*` or `+@read`.
+    ///
+    /// Commands are not checked to exist, so that users can be defined before the commands are
+    /// registered.
+    pub fn apply(&mut self, rule: &str) -> Result<()> {
+        match rule.to_lowercase().as_str() {
+            "on" => self.enabled = true,
+            "off" => self.enabled = false,
+            "nopass" => {
+                self.nopass = true;
+                self.passwords.clear();
+            }
+            "resetpass" => {
+                self.nopass = false;
+                self.passwords.clear();
+            }
+            "allkeys" => self.key_patterns = vec!["*".into()],
+            "resetkeys" => self.key_patterns.clear(),
+            "allchannels" => self.channel_patterns = vec!["*".into()],
+            "resetchannels" => self.channel_patterns.clear(),
+            "allcommands" => self.apply_command_rule(true, "@all"),
+            "nocommands" => self.apply_command_rule(false, "@all"),
+            "reset" => {
+                *self = Self::new(std::mem::take(&mut self.name));
+            }
+            _ => return self.apply_prefixed(rule),
+        }
+        Ok(())
+    }
+
+    /// Applies a rule made of a prefix and a value, such as `>password` or `+get`.
+    fn apply_prefixed(&mut self, rule: &str) -> Result<()> {
+        let mut chars = rule.chars();
+        let prefix = chars.next();
+        let value = chars.as_str();
+        match prefix {
+            Some('>') => self.add_password(hash_password(value)),
+            Some('<') => self.remove_password(&hash_password(value))?,
+            Some('#') => self.add_password(parse_hash(value)?),
+            Some('!') => self.remove_password(&parse_hash(value)?)?,
+            Some('~') => self.key_patterns.push(value.into()),
+            Some('&') => self.channel_patterns.push(value.into()),
+            Some(sign @ ('+' | '-')) if !value.is_empty() => {
+                let target = value.to_lowercase();
+                if let Some(category) = target.strip_prefix('@') {
+                    if category != "all" && !CATEGORIES.contains(&category) {
+                        return Err(anyhow::anyhow!("Unknown command or category name in ACL"));
+                    }
+                }
+                self.apply_command_rule(sign == '+', &target);
+            }
+            _ => return Err(anyhow::anyhow!("Syntax error")),
+        }
+        Ok(())
+    }
+
+    /// Adds a password by its digest, which stops the user from accepting any password.
+    fn add_password(&mut self, digest: String) {
+        self.nopass = false;
+        if !self.passwords.contains(&digest) {
+            self.passwords.push(digest);
+        }
+    }
+
+    /// Removes a password by its digest.
+    fn remove_password(&mut self, digest: &str) -> Result<()> {
+        let length = self.passwords.len();
+        self.passwords.retain(|password| password != digest);
+        if self.passwords.len() == length {
+            return Err(anyhow::anyhow!(
+                "The password you are trying to remove from the user does not exist"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Adds a command rule. A rule for every command overrides the previous rules, so they are
+    /// dropped.
+    fn apply_command_rule(&mut self, allow: bool, target: &str) {
+        if target == "@all" {
+            self.command_rules.clear();
+            if !allow {
+                return;
+            }
+        }
+        self.command_rules.push(CommandRule {
+            allow,
+            target: target.into(),
+        });
+    }
+
+    /// Whether the password authenticates the user.
+    pub fn check_password(&self, password: &str) -> bool {
+        self.nopass || self.passwords.contains(&hash_password(password))
+    }
+
+    /// Whether the user can run the command, given its lowercase name and its categories.
+    pub fn can_run(&self, name: &str, categories: &[&str]) -> bool {
+        let mut allowed = false;
+        for rule in &self.command_rules {
+            let matches = match rule.target.strip_prefix('@') {
+                Some("all") => true,
+                Some(category) => categories.contains(&category),
+                None => rule.target == name,
+            };
+            if matches {
+                allowed = rule.allow;
+            }
+        }
+        allowed
+    }
+
+    /// Whether the user can access the key.
+    pub fn can_access_key(&self, key: &str) -> bool {
+        self.key_patterns
+            .iter()
+            .any(|pattern| crate::pattern::glob_match(pattern, key))
+    }
+
+    /// Whether the user can access the channel.
+    pub fn can_access_channel(&self, channel: &str) -> bool {
+        self.channel_patterns
+            .iter()
+            .any(|pattern| crate::pattern::glob_match(pattern, channel))
+    }
+
+    /// Gets the user's flags, as listed by ACL GETUSER.
+    pub fn flags(&self) -> Vec<&'static str> {
+        let mut flags = vec![if self.enabled { "on" } else { "off" }];
+        if self.nopass {
+            flags.push("nopass");
+        }
+        flags
+    }
+
+    /// Gets the user's command rules as a single string, such as `-@all +get`.
+    pub fn commands(&self) -> String {
+        let mut rules = vec!["-@all".to_string()];
+        if self
+            .command_rules
+            .first()
+            .is_some_and(|rule| rule.target == "@all")
+        {
+            rules.clear();
+        }
+        rules.extend(self.command_rules.iter().map(|rule| rule.to_string()));
+        rules.join(" ")
+    }
+}
+
+impl std::fmt::Display for User {
+    /// Formats the user as the rules recreating it, as listed by ACL LIST.
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rules = vec![format!("user {}", self.name)];
+        rules.extend(self.flags().iter().map(|flag| flag.to_string()));
+        rules.extend(self.passwords.iter().map(|digest| format!("#{digest}")));
+        rules.extend(
+            self.key_patterns
+                .iter()
+                .map(|pattern| format!("~{pattern}")),
+        );
+        if self.channel_patterns.is_empty() {
+            rules.push("resetchannels".into());
+        }
+        rules.extend(
+            self.channel_patterns
+                .iter()
+                .map(|pattern| format!("&{pattern}")),
+        );
+        rules.push(self.commands());
+        write!(fmt, "{}", rules.join(" "))
+    }
+}
+
+/// Parses the hexadecimal SHA-256 digest of a password.
+fn parse_hash(hash: &str) -> Result<String> {
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow::anyhow!(
+            "The password hash must be exactly 64 characters and contain only lowercase hexadecimal characters"
+        ));
+    }
+    Ok(hash.to_lowercase())
+}
+
+/// Gets the channels the command subscribes or publishes to from its arguments.
+fn channels(name: &str, args: &[crate::resp::RespType]) -> Vec<String> {
+    let channels = match name {
+        "subscribe" => args,
+        "publish" => &args[..args.len().min(1)],
+        _ => &[],
+    };
+    channels
+        .iter()
+        .filter_map(|channel| crate::resp::extract_string(channel).ok())
+        .collect()
+}
+
+/// The users, by name.
+#[derive(Debug)]
+pub struct Acl {
+    users: std::sync::RwLock<BTreeMap<String, User>>,
+}
+
+impl Acl {
+    /// Checks the credentials, returning the user they authenticate.
+    pub fn authenticate(&self, username: &str, password: &str) -> Result<User, WrongPass> {
+        match self.users.read().unwrap().get(username) {
+            Some(user) if user.enabled && user.check_password(password) => Ok(user.clone()),
+            _ => Err(WrongPass),
+        }
+    }
+
+    /// Whether new connections must authenticate, as the default user needs a password or is
+    /// disabled.
+    pub fn requires_authentication(&self) -> bool {
+        self.get_user(DEFAULT_USER)
+            .is_none_or(|user| !user.enabled || !user.nopass)
+    }
+
+    /// Gets a user by name.
+    pub fn get_user(&self, name: &str) -> Option<User> {
+        self.users.read().unwrap().get(name).cloned()
+    }
+
+    /// Gets every user, sorted by name.
+    pub fn users(&self) -> Vec<User> {
+        self.users.read().unwrap().values().cloned().collect()
+    }
+
+    /// Creates or updates a user with the rules, applied in order.
+    /// The user is left unchanged if any rule is invalid, with the error naming the rule.
+    pub fn set_user<T: AsRef<str>>(&self, name: &str, rules: &[T]) -> Result<()> {
+        let mut users = self.users.write().unwrap();
+        let mut user = users.get(name).cloned().unwrap_or_else(|| User::new(name));
+        for rule in rules {
+            let rule = rule.as_ref();
+            if let Err(err) = user.apply(rule) {
+                return Err(anyhow::anyhow!(
+                    "Error in ACL SETUSER modifier '{rule}': {err}"
+                ));
+            }
+        }
+        users.insert(name.into(), user);
+        Ok(())
+    }
+
+    /// Sets the only password of the default user, where an empty password lets any password
+    /// authenticate it, as done by `requirepass`.
+    pub fn set_default_password(&self, password: &str) {
+        let rules = if password.is_empty() {
+            vec!["nopass".to_string()]
+        } else {
+            vec!["resetpass".to_string(), format!(">{password}")]
+        };
+        self.set_user(DEFAULT_USER, &rules)
+            .expect("Password rules are always valid.");
+    }
+
+    /// Checks that the user can run the command with the arguments, accessing its keys and
+    /// channels. Commands that can authenticate are always allowed, so that a connection can
+    /// switch to another user.
+    pub fn check(
+        &self,
+        username: &str,
+        command: &dyn crate::commands::Command,
+        args: &[crate::resp::RespType],
+    ) -> Result<(), Denied> {
+        let flags = command.flags();
+        if flags.contains(&"no_auth") {
+            return Ok(());
+        }
+        let name = command.name().to_lowercase();
+        let users = self.users.read().unwrap();
+        let Some(user) = users.get(username) else {
+            return Err(Denied::Command(username.into(), name));
+        };
+        if !user.can_run(&name, &categories(&flags)) {
+            return Err(Denied::Command(username.into(), name));
+        }
+        if !command
+            .keys(args)
+            .iter()
+            .all(|key| user.can_access_key(key))
+        {
+            return Err(Denied::Key);
+        }
+        if !channels(&name, args)
+            .iter()
+            .all(|channel| user.can_access_channel(channel))
+        {
+            return Err(Denied::Channel);
+        }
+        Ok(())
+    }
+}
+
+pub type SharedAcl = Arc<Acl>;
+
+/// Creates the access control lists with only the default user, which can run every command and
+/// needs no password.
+pub fn new() -> SharedAcl {
+    let acl = Acl {
+        users: std::sync::RwLock::new(BTreeMap::new()),
+    };
+    acl.set_user(
+        DEFAULT_USER,
+        &["on", "nopass", "allkeys", "allchannels", "allcommands"],
+    )
+    .expect("The default user's rules are valid.");
+    Arc::new(acl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn acl() -> SharedAcl {
+        new()
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    #[case::read(&["readonly", "fast"], &["fast", "read"])]
+    #[case::write(&["write", "denyoom"], &["slow", "write"])]
+    #[case::admin(&["admin", "noscript"], &["admin", "dangerous", "slow"])]
+    #[case::dangerous(&["write", "dangerous"], &["dangerous", "slow", "write"])]
+    #[case::admin_dangerous(&["admin", "dangerous"], &["admin", "dangerous", "slow"])]
+    #[case::connection(&["fast", "no_auth"], &["connection", "fast"])]
+    #[case::pubsub(&["pubsub", "noscript"], &["pubsub", "slow"])]
+    fn test_categories(#[case] flags: &[&str], #[case] expected: &[&str]) {
+        assert_eq!(expected, categories(flags));
+    }
+
+    #[rstest]
+    fn test_hash_password() {
+        assert_eq!(
+            "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b",
+            hash_password("secret")
+        );
+    }
+
+    #[rstest]
+    fn test_new(acl: SharedAcl) {
+        let user = acl.get_user(DEFAULT_USER).unwrap();
+        assert_eq!("user default on nopass ~* &* +@all", user.to_string());
+        assert!(!acl.requires_authentication());
+    }
+
+    #[rstest]
+    fn test_user_new() {
+        let user = User::new("alice");
+        assert_eq!("user alice off resetchannels -@all", user.to_string());
+        assert!(!user.can_run("get", &["fast", "read"]));
+    }
+
+    #[rstest]
+    #[case::on(&["on"], "user alice on resetchannels -@all")]
+    #[case::off(&["on", "off"], "user alice off resetchannels -@all")]
+    #[case::password(
+        &[">secret"],
+        "user alice off #2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b resetchannels -@all"
+    )]
+    #[case::hash(
+        &["#2BB80D537B1DA3E38BD30361AA855686BDE0EACD7162FEF6A25FE97BF527A25B"],
+        "user alice off #2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b resetchannels -@all"
+    )]
+    #[case::remove_password(&[">secret", "<secret"], "user alice off resetchannels -@all")]
+    #[case::nopass(&[">secret", "nopass"], "user alice off nopass resetchannels -@all")]
+    #[case::resetpass(&["nopass", "resetpass"], "user alice off resetchannels -@all")]
+    #[case::keys(&["~a*", "~b"], "user alice off ~a* ~b resetchannels -@all")]
+    #[case::allkeys(&["~a*", "allkeys"], "user alice off ~* resetchannels -@all")]
+    #[case::resetkeys(&["allkeys", "resetkeys"], "user alice off resetchannels -@all")]
+    #[case::channels(&["&news.*"], "user alice off &news.* -@all")]
+    #[case::allchannels(&["allchannels"], "user alice off &* -@all")]
+    #[case::commands(&["+GET", "+@write", "-set"], "user alice off resetchannels -@all +get +@write -set")]
+    #[case::allcommands(&["+get", "allcommands", "-@admin"], "user alice off resetchannels +@all -@admin")]
+    #[case::nocommands(&["+@all", "nocommands", "+get"], "user alice off resetchannels -@all +get")]
+    #[case::reset(&["on", "nopass", "allkeys", "+@all", "reset"], "user alice off resetchannels -@all")]
+    fn test_user_apply(#[case] rules: &[&str], #[case] expected: &str) {
+        let mut user = User::new("alice");
+        for rule in rules {
+            user.apply(rule).unwrap();
+        }
+        assert_eq!(expected, user.to_string());
+    }
+
+    #[rstest]
+    #[case::syntax("invalid", "Syntax error")]
+    #[case::empty_command("+", "Syntax error")]
+    #[case::unknown_category("+@unknown", "Unknown command or category name in ACL")]
+    #[case::missing_password(
+        "<secret",
+        "The password you are trying to remove from the user does not exist"
+    )]
+    #[case::invalid_hash(
+        "#abc",
+        "The password hash must be exactly 64 characters and contain only lowercase hexadecimal characters"
+    )]
+    fn test_user_apply_errors(#[case] rule: &str, #[case] expected: &str) {
+        let mut user = User::new("alice");
+        assert_eq!(expected, user.apply(rule).unwrap_err().to_string());
+    }
+
+    #[rstest]
+    #[case::command("get", &["fast", "read"], true)]
+    #[case::category("set", &["slow", "write"], true)]
+    #[case::denied_after_category("del", &["slow", "write"], false)]
+    #[case::not_allowed("ping", &["fast"], false)]
+    fn test_user_can_run(#[case] name: &str, #[case] categories: &[&str], #[case] expected: bool) {
+        let mut user = User::new("alice");
+        for rule in ["+get", "+@write", "-del"] {
+            user.apply(rule).unwrap();
+        }
+        assert_eq!(expected, user.can_run(name, categories));
+    }
+
+    #[rstest]
+    #[case::matching("user:1", true)]
+    #[case::other("session:1", false)]
+    fn test_user_can_access_key(#[case] key: &str, #[case] expected: bool) {
+        let mut user = User::new("alice");
+        user.apply("~user:*").unwrap();
+        assert_eq!(expected, user.can_access_key(key));
+        assert!(!user.can_access_channel(key));
+    }
+
+    #[rstest]
+    #[case::password("alice", "secret", true)]
+    #[case::wrong_password("alice", "password", false)]
+    #[case::unknown_user("bob", "secret", false)]
+    #[case::default_user("default", "anything", true)]
+    fn test_authenticate(
+        acl: SharedAcl,
+        #[case] username: &str,
+        #[case] password: &str,
+        #[case] expected: bool,
+    ) {
+        acl.set_user("alice", &["on", ">secret"]).unwrap();
+        assert_eq!(expected, acl.authenticate(username, password).is_ok());
+    }
+
+    #[rstest]
+    fn test_authenticate_disabled(acl: SharedAcl) {
+        acl.set_user("alice", &["off", "nopass"]).unwrap();
+        assert!(acl.authenticate("alice", "").is_err());
+    }
+
+    #[rstest]
+    fn test_set_user_invalid_rule(acl: SharedAcl) {
+        acl.set_user("alice", &["on"]).unwrap();
+        let err = acl.set_user("alice", &["off", "invalid"]).unwrap_err();
+        assert_eq!(
+            "Error in ACL SETUSER modifier 'invalid': Syntax error",
+            err.to_string()
+        );
+        assert!(acl.get_user("alice").unwrap().enabled);
+    }
+
+    #[rstest]
+    fn test_users(acl: SharedAcl) {
+        acl.set_user("bob", &["on"]).unwrap();
+        acl.set_user("alice", &["on"]).unwrap();
+        assert_eq!(
+            vec!["alice", "bob", "default"],
+            acl.users()
+                .iter()
+                .map(|user| user.name.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::password("secret", true)]
+    #[case::no_password("", false)]
+    fn test_set_default_password(acl: SharedAcl, #[case] password: &str, #[case] expected: bool) {
+        acl.set_default_password("old");
+        acl.set_default_password(password);
+        assert_eq!(expected, acl.requires_authentication());
+        // Without a password, any password authenticates the default user.
+        assert_eq!(expected, acl.authenticate(DEFAULT_USER, "old").is_err());
+        assert!(acl.authenticate(DEFAULT_USER, password).is_ok());
+    }
+
+    #[rstest]
+    #[case::allowed("get", &["user:1"], Ok(()))]
+    #[case::command("set", &["user:1", "value"], Err(Denied::Command("alice".into(), "set".into())))]
+    #[case::key("get", &["session:1"], Err(Denied::Key))]
+    #[case::channel("subscribe", &["news", "sports"], Err(Denied::Channel))]
+    #[case::channel_allowed("subscribe", &["news"], Ok(()))]
+    #[case::no_auth("hello", &[], Ok(()))]
+    #[case::lmpop_allowed("lmpop", &["2", "user:1", "user:2", "left"], Ok(()))]
+    #[case::lmpop_key("lmpop", &["2", "user:1", "session:1", "left"], Err(Denied::Key))]
+    #[case::sintercard_allowed("sintercard", &["1", "user:1", "limit", "1"], Ok(()))]
+    #[case::sintercard_key("sintercard", &["2", "session:1", "user:1"], Err(Denied::Key))]
+    fn test_check(
+        acl: SharedAcl,
+        #[case] command: &str,
+        #[case] args: &[&str],
+        #[case] expected: Result<(), Denied>,
+    ) {
+        acl.set_user(
+            "alice",
+            &[
+                "on",
+                "+get",
+                "+subscribe",
+                "+lmpop",
+                "+sintercard",
+                "~user:*",
+                "&news",
+            ],
+        )
+        .unwrap();
+        let command: Box<dyn crate::commands::Command> = match command {
+            "get" => Box::new(crate::commands::get::Get),
+            "set" => Box::new(crate::commands::set::Set),
+            "subscribe" => Box::new(crate::commands::subscribe::Subscribe),
+            "lmpop" => Box::new(crate::commands::lmpop::Lmpop),
+            "sintercard" => Box::new(crate::commands::sintercard::Sintercard),
+            _ => Box::new(crate::commands::hello::Hello::new(acl.clone())),
+        };
+        assert_eq!(
+            expected,
+            acl.check("alice", command.as_ref(), &make_args(args))
+        );
+    }
+
+    #[rstest]
+    fn test_check_unknown_user(acl: SharedAcl) {
+        assert_eq!(
+            Err(Denied::Command("alice".into(), "get".into())),
+            acl.check("alice", &crate::commands::get::Get, &make_args(&["key"]))
+        );
+    }
+}
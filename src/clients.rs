@@ -0,0 +1,241 @@
+//! This module contains the registry of connected clients.
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The details of a connected client, as listed by CLIENT LIST.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientInfo {
+    pub id: u64,
+    /// The address of the client's end of the connection.
+    pub addr: String,
+    pub name: Option<String>,
+    /// The index of the selected database.
+    pub db: usize,
+    /// The number of channels the client is subscribed to.
+    pub subscriptions: usize,
+    /// The lowercase name of the last command the client ran.
+    pub last_command: Option<String>,
+    pub created: tokio::time::Instant,
+    pub last_interaction: tokio::time::Instant,
+}
+
+impl ClientInfo {
+    /// Creates the details of a client that just connected.
+    fn new(id: u64, addr: String) -> Self {
+        let now = tokio::time::Instant::now();
+        Self {
+            id,
+            addr,
+            name: None,
+            db: 0,
+            subscriptions: 0,
+            last_command: None,
+            created: now,
+            last_interaction: now,
+        }
+    }
+}
+
+impl std::fmt::Display for ClientInfo {
+    /// Formats the details as a line of CLIENT LIST, without the trailing newline.
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            fmt,
+            "id={} addr={} name={} age={} idle={} db={} sub={} cmd={}",
+            self.id,
+            self.addr,
+            self.name.as_deref().unwrap_or_default(),
+            self.created.elapsed().as_secs(),
+            self.last_interaction.elapsed().as_secs(),
+            self.db,
+            self.subscriptions,
+            self.last_command.as_deref().unwrap_or("NULL")
+        )
+    }
+}
+
+/// The connected clients, by their id.
+#[derive(Debug, Default)]
+pub struct Clients {
+    clients: std::sync::RwLock<BTreeMap<u64, ClientInfo>>,
+}
+
+impl Clients {
+    /// Adds a client that just connected from the address.
+    /// The client is removed once the returned registration is dropped.
+    pub fn register(self: &Arc<Self>, id: u64, addr: String) -> Registration {
+        self.clients
+            .write()
+            .unwrap()
+            .insert(id, ClientInfo::new(id, addr.clone()));
+        Registration {
+            clients: self.clone(),
+            id,
+            addr,
+        }
+    }
+
+    /// Updates a client's details from its connection state, marking it as active now.
+    /// Clients that are not registered are ignored.
+    pub fn update(&self, state: &crate::state::State) {
+        if let Some(client) = self.clients.write().unwrap().get_mut(&state.client_id) {
+            client.name = state.name.clone();
+            client.db = state.db;
+            client.subscriptions = state.channels.len();
+            client.last_command = state.last_command.clone();
+            client.last_interaction = tokio::time::Instant::now();
+        }
+    }
+
+    /// Gets the details of a client.
+    pub fn get(&self, id: u64) -> Option<ClientInfo> {
+        self.clients.read().unwrap().get(&id).cloned()
+    }
+
+    /// Gets the details of every client, sorted by id.
+    pub fn list(&self) -> Vec<ClientInfo> {
+        self.clients.read().unwrap().values().cloned().collect()
+    }
+}
+
+/// A client's entry in the registry, removed once this is dropped.
+#[derive(Debug)]
+pub struct Registration {
+    clients: SharedClients,
+    id: u64,
+    addr: String,
+}
+
+impl Registration {
+    /// Gets the id of the registered client.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Gets the address of the registered client.
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Updates the client's details from its connection state.
+    pub fn update(&self, state: &crate::state::State) {
+        self.clients.update(state);
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.clients.clients.write().unwrap().remove(&self.id);
+    }
+}
+
+pub type SharedClients = Arc<Clients>;
+
+/// Creates a new registry without clients.
+pub fn new() -> SharedClients {
+    Arc::new(Clients::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn clients() -> SharedClients {
+        new()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_register(clients: SharedClients) {
+        tokio::time::pause();
+        let registration = clients.register(3, "127.0.0.1:5000".into());
+        assert_eq!(3, registration.id());
+        assert_eq!("127.0.0.1:5000", registration.addr());
+        assert_eq!(
+            Some(ClientInfo {
+                id: 3,
+                addr: "127.0.0.1:5000".into(),
+                name: None,
+                db: 0,
+                subscriptions: 0,
+                last_command: None,
+                created: tokio::time::Instant::now(),
+                last_interaction: tokio::time::Instant::now(),
+            }),
+            clients.get(3)
+        );
+
+        drop(registration);
+        assert_eq!(None, clients.get(3));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_update(clients: SharedClients) {
+        tokio::time::pause();
+        let registration = clients.register(1, "127.0.0.1:5000".into());
+        tokio::time::advance(tokio::time::Duration::from_secs(5)).await;
+
+        let mut state = crate::state::State::new(1);
+        state.name = Some("name".into());
+        state.db = 2;
+        state.channels.insert("channel".into());
+        state.last_command = Some("subscribe".into());
+        registration.update(&state);
+
+        let client = clients.get(1).unwrap();
+        assert_eq!(Some("name".into()), client.name);
+        assert_eq!(2, client.db);
+        assert_eq!(1, client.subscriptions);
+        assert_eq!(Some("subscribe".into()), client.last_command);
+        assert_eq!(
+            5,
+            client
+                .last_interaction
+                .duration_since(client.created)
+                .as_secs()
+        );
+    }
+
+    #[rstest]
+    fn test_update_unregistered(clients: SharedClients) {
+        clients.update(&crate::state::State::new(1));
+        assert_eq!(Vec::<ClientInfo>::new(), clients.list());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_list(clients: SharedClients) {
+        let _second = clients.register(2, "127.0.0.1:5002".into());
+        let _first = clients.register(1, "127.0.0.1:5001".into());
+        assert_eq!(
+            vec![1, 2],
+            clients
+                .list()
+                .iter()
+                .map(|client| client.id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_display(clients: SharedClients) {
+        tokio::time::pause();
+        let registration = clients.register(1, "127.0.0.1:5000".into());
+        tokio::time::advance(tokio::time::Duration::from_secs(10)).await;
+        let mut state = crate::state::State::new(1);
+        state.last_command = Some("get".into());
+        registration.update(&state);
+        tokio::time::advance(tokio::time::Duration::from_secs(3)).await;
+
+        assert_eq!(
+            "id=1 addr=127.0.0.1:5000 name= age=13 idle=3 db=0 sub=0 cmd=get",
+            clients.get(1).unwrap().to_string()
+        );
+    }
+}
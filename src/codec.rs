@@ -0,0 +1,170 @@
+//! This module contains the codec framing RESP messages.
+use anyhow::Result;
+use bytes::BytesMut;
+
+/// Decodes RESP messages within the protocol limits and encodes replies for the protocol version.
+#[derive(Debug)]
+pub struct RespCodec {
+    pub protocol_limits: crate::resp::ProtocolLimits,
+    /// The protocol version replies are encoded for.
+    pub protocol_version: crate::state::ProtocolVersion,
+    /// How much of the message being buffered has been framed, across calls to `decode`.
+    frame_state: crate::resp::FrameState,
+}
+
+impl RespCodec {
+    /// Creates a new codec with the default protocol limits, encoding RESP2 replies.
+    pub fn new() -> Self {
+        Self {
+            protocol_limits: crate::resp::ProtocolLimits::default(),
+            protocol_version: crate::state::ProtocolVersion::V2,
+            frame_state: crate::resp::FrameState::default(),
+        }
+    }
+}
+
+impl tokio_util::codec::Decoder for RespCodec {
+    type Item = crate::resp::RespType;
+    type Error = anyhow::Error;
+
+    /// Decodes the first message of the buffer once it is fully buffered.
+    /// Framing resumes where the previous call stopped, so a message arriving in many pieces is
+    /// only scanned once.
+    ///
+    /// Messages that cannot be parsed are returned as a [`crate::resp::ProtocolError`], as are
+    /// incomplete messages that fill the query buffer limit.
+    fn decode(&mut self, buffer: &mut BytesMut) -> Result<Option<Self::Item>> {
+        let message = crate::resp::RespType::try_from_bytes_with_limits(
+            buffer,
+            &self.protocol_limits,
+            &mut self.frame_state,
+        )
+        .map_err(|err| match err.downcast::<crate::resp::ProtocolError>() {
+            Ok(err) => err,
+            Err(err) => crate::resp::ProtocolError::Malformed(err.to_string()),
+        })?;
+        if message.is_none() && buffer.len() >= self.protocol_limits.max_query_buffer_length {
+            return Err(crate::resp::ProtocolError::QueryBufferLimit.into());
+        }
+        Ok(message)
+    }
+
+    /// Decodes the remaining messages once the stream ends, dropping an incomplete message.
+    fn decode_eof(&mut self, buffer: &mut BytesMut) -> Result<Option<Self::Item>> {
+        self.decode(buffer)
+    }
+}
+
+impl tokio_util::codec::Encoder<crate::resp::RespType> for RespCodec {
+    type Error = anyhow::Error;
+
+    fn encode(&mut self, message: crate::resp::RespType, buffer: &mut BytesMut) -> Result<()> {
+        message.serialize_with_version_into(&self.protocol_version, buffer);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+    use tokio_util::codec::{Decoder, Encoder};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn codec() -> RespCodec {
+        RespCodec::new()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_decode(mut codec: RespCodec) {
+        let mut buffer = BytesMut::from("+first\r\n+second\r\n+thi");
+        assert_eq!(
+            Some(crate::resp::RespType::SimpleString("first".into())),
+            codec.decode(&mut buffer).unwrap()
+        );
+        assert_eq!(
+            Some(crate::resp::RespType::SimpleString("second".into())),
+            codec.decode(&mut buffer).unwrap()
+        );
+        assert_eq!(None, codec.decode(&mut buffer).unwrap());
+        assert_eq!(b"+thi".as_slice(), buffer);
+    }
+
+    #[rstest]
+    fn test_decode_in_pieces(mut codec: RespCodec) {
+        let bytes = b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n+next\r\n";
+        let mut buffer = BytesMut::new();
+        let mut messages = vec![];
+        for piece in bytes.chunks(3) {
+            buffer.extend_from_slice(piece);
+            while let Some(message) = codec.decode(&mut buffer).unwrap() {
+                messages.push(message);
+            }
+        }
+        assert_eq!(
+            vec![
+                crate::resp::RespType::Array(vec![
+                    crate::resp::RespType::BulkString(Some("SET".into())),
+                    crate::resp::RespType::BulkString(Some("key".into())),
+                    crate::resp::RespType::BulkString(Some("value".into())),
+                ]),
+                crate::resp::RespType::SimpleString("next".into()),
+            ],
+            messages
+        );
+        assert!(buffer.is_empty());
+        assert_eq!(crate::resp::FrameState::default(), codec.frame_state);
+    }
+
+    #[rstest]
+    fn test_decode_eof(mut codec: RespCodec) {
+        let mut buffer = BytesMut::from("+incomplete");
+        assert_eq!(None, codec.decode_eof(&mut buffer).unwrap());
+    }
+
+    #[rstest]
+    #[case::v2(crate::state::ProtocolVersion::V2, "$-1\r\n")]
+    #[case::v3(crate::state::ProtocolVersion::V3, "_\r\n")]
+    fn test_encode(
+        mut codec: RespCodec,
+        #[case] protocol_version: crate::state::ProtocolVersion,
+        #[case] expected: &str,
+    ) {
+        codec.protocol_version = protocol_version;
+        let mut buffer = BytesMut::from("+previous\r\n");
+        codec
+            .encode(crate::resp::RespType::Null(), &mut buffer)
+            .unwrap();
+        assert_eq!(format!("+previous\r\n{expected}").as_bytes(), buffer);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::bulk_length(b"$5\r\nhel", crate::resp::ProtocolError::InvalidBulkLength)]
+    #[case::malformed(
+        b"*a\r\n",
+        crate::resp::ProtocolError::Malformed("Failed to parse array length.".into())
+    )]
+    #[case::query_buffer_limit(b"$3\r\nab", crate::resp::ProtocolError::QueryBufferLimit)]
+    #[case::nested(b"*1\r\n*1\r\n*1\r\n", crate::resp::ProtocolError::NestingTooDeep)]
+    fn test_decode_errors(
+        mut codec: RespCodec,
+        #[case] bytes: &[u8],
+        #[case] expected: crate::resp::ProtocolError,
+    ) {
+        codec.protocol_limits = crate::resp::ProtocolLimits {
+            max_bulk_length: 4,
+            max_query_buffer_length: 6,
+            ..Default::default()
+        };
+        let result = codec.decode(&mut bytes.into());
+        assert_eq!(
+            Some(&expected),
+            result
+                .unwrap_err()
+                .downcast_ref::<crate::resp::ProtocolError>()
+        );
+    }
+}
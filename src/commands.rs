@@ -4,18 +4,185 @@ use std::sync::Arc;
 
 use tokio::sync::RwLock;
 
+pub mod acl;
+pub mod auth;
+pub mod client;
+pub mod command;
+pub mod config;
+pub mod copy;
+pub mod debug;
+pub mod del;
 pub mod echo;
+pub mod expiretime;
+pub mod flushall;
 pub mod get;
+pub mod getex;
+pub mod hdel;
 pub mod hello;
+pub mod hgetall;
+pub mod hlen;
+pub mod hset;
+pub mod incrbyfloat;
+pub mod info;
+pub mod keys;
+pub mod latency;
+pub mod lindex;
+pub mod linsert;
+pub mod lmpop;
+pub mod lolwut;
+pub mod lpos;
+pub mod lrem;
+pub mod lset;
+pub mod memory;
+pub mod monitor;
+pub mod r#move;
+pub mod object;
 pub mod ping;
+pub mod quit;
+pub mod randomkey;
+pub mod rename;
 pub mod rpush;
+pub mod sadd;
+pub mod scan;
+pub mod scard;
+pub mod select;
 pub mod set;
+pub mod shutdown;
+pub mod sinter;
+pub mod sintercard;
+pub mod sismember;
+pub mod srem;
+pub mod subscribe;
+pub mod swapdb;
+pub mod touch;
+pub mod unlink;
+pub mod wait;
+
+/// The server version reported to clients.
+pub const SERVER_VERSION: &str = "0.0.1";
+
+/// The error message for an operation against a key holding a value of the wrong type.
+pub const WRONG_TYPE_MESSAGE: &str =
+    "WRONGTYPE Operation against a key holding the wrong kind of value";
+
+/// Gets the error for an operation against a key holding a value of the wrong type.
+pub fn wrong_type_error() -> crate::resp::RespType {
+    crate::resp::RespType::SimpleError(WRONG_TYPE_MESSAGE.into())
+}
+
+/// Replies to the HELP subcommand and to unknown subcommands of a container command, such as
+/// OBJECT or CONFIG, so that every container command replies to them consistently.
+///
+/// Each subcommand is given by its usage and a description, with the subcommand's name being the
+/// first word of its usage. Returns `None` if the arguments are for one of the subcommands, leaving
+/// the command to handle them.
+pub fn dispatch_subcommand(
+    command: &str,
+    args: &[crate::resp::RespType],
+    subcommands: &[(&str, &str)],
+) -> Option<crate::resp::RespType> {
+    let subcommand = crate::resp::extract_string(args.first()?).ok()?;
+    if subcommand.eq_ignore_ascii_case("help") {
+        let mut lines = vec![format!(
+            "{command} <subcommand> [<arg> [value] [opt] ...]. Subcommands are:"
+        )];
+        for (usage, description) in subcommands.iter().chain(&[("HELP", "Print this help.")]) {
+            lines.push(usage.to_string());
+            lines.push(format!("    {description}"));
+        }
+        return Some(crate::resp::RespType::Array(
+            lines
+                .into_iter()
+                .map(crate::resp::RespType::SimpleString)
+                .collect(),
+        ));
+    }
+
+    let is_known = subcommands.iter().any(|(usage, _)| {
+        usage
+            .split_whitespace()
+            .next()
+            .is_some_and(|name| name.eq_ignore_ascii_case(&subcommand))
+    });
+    if is_known {
+        None
+    } else {
+        Some(crate::resp::RespType::SimpleError(format!(
+            "ERR Unknown subcommand or wrong number of arguments for '{subcommand}'. Try {command} HELP."
+        )))
+    }
+}
+
+/// Resolves a possibly negative list index into an index from the head of the list.
+/// Negative indices count from the tail, where -1 is the last element.
+pub fn resolve_index(index: i64, length: usize) -> Option<usize> {
+    let index = if index < 0 {
+        length as i64 + index
+    } else {
+        index
+    };
+    if (0..length as i64).contains(&index) {
+        Some(index as usize)
+    } else {
+        None
+    }
+}
+
+/// Gets the name of a command instance.
+/// This is implemented for every command and defaults to the command's static name.
+pub trait CommandName {
+    /// Gets the name of the command.
+    fn name(&self) -> String;
+}
+
+impl<T: Command> CommandName for T {
+    fn name(&self) -> String {
+        T::static_name()
+    }
+}
 
 #[async_trait::async_trait]
 /// The command trait.
-pub trait Command: Send + Sync {
-    /// Gets the name of the comamnd.
-    fn name(&self) -> String;
+pub trait Command: CommandName + Send + Sync {
+    /// Gets the name of the command without needing an instance.
+    fn static_name() -> String
+    where
+        Self: Sized;
+
+    /// Gets the number of arguments the command takes, including the command's name.
+    /// A negative arity is the negated minimum number of arguments.
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    /// Gets the command's flags, such as `readonly` or `write`.
+    /// Commands flagged `no_auth` can run before the connection is authenticated.
+    fn flags(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Gets the positions of the command's first key, last key and the step between keys.
+    /// A negative last key counts from the last argument, and no keys are given by zeroes.
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (0, 0, 0)
+    }
+
+    /// Gets the keys of the command from its arguments, which exclude the command's name.
+    /// This defaults to the arguments at the key positions, so commands with keys at varying
+    /// positions, flagged `movablekeys`, find their own keys.
+    fn keys(&self, args: &[crate::resp::RespType]) -> Vec<String> {
+        let (first, last, step) = self.key_positions();
+        if first <= 0 || step <= 0 {
+            return vec![];
+        }
+        // The positions count the command's name.
+        let count = args.len() as i64 + 1;
+        let last = if last < 0 { count + last } else { last };
+        (first..=last.min(count - 1))
+            .step_by(step as usize)
+            .filter_map(|position| crate::resp::extract_string(&args[position as usize - 1]).ok())
+            .collect()
+    }
 
     /// Runs the command.
     async fn handle(
@@ -26,18 +193,92 @@ pub trait Command: Send + Sync {
     ) -> crate::resp::RespType;
 }
 
+/// Gets the keys of a command whose first argument is the number of keys that follow it, such as
+/// LMPOP or SINTERCARD.
+/// Keys that are not given are left out, as the command rejects its arguments anyway.
+pub fn numkeys_keys(args: &[crate::resp::RespType]) -> Vec<String> {
+    let numkeys = args
+        .first()
+        .and_then(|numkeys| crate::resp::extract_string(numkeys).ok())
+        .and_then(|numkeys| numkeys.parse::<usize>().ok())
+        .unwrap_or(0);
+    args.iter()
+        .skip(1)
+        .take(numkeys)
+        .filter_map(|key| crate::resp::extract_string(key).ok())
+        .collect()
+}
+
 /// A command register.
-pub struct Register(std::collections::HashMap<String, Box<dyn Command>>);
+pub struct Register {
+    commands: std::collections::HashMap<String, Box<dyn Command>>,
+    stats: crate::stats::SharedStats,
+    pause: crate::pause::SharedPause,
+    monitor: crate::monitor::SharedMonitor,
+    latency: crate::latency::SharedLatency,
+    acl: crate::acl::SharedAcl,
+    config: crate::config::SharedConfig,
+    databases: crate::store::SharedDatabases,
+}
+
+/// The error for a write on a read-only replica.
+pub const READONLY_MESSAGE: &str = "READONLY You can't write against a read only replica.";
 
 impl Register {
     /// An empty command register.
     pub fn new() -> Self {
-        Self(std::collections::HashMap::new())
+        Self {
+            commands: std::collections::HashMap::new(),
+            stats: crate::stats::new(),
+            pause: crate::pause::new(),
+            monitor: crate::monitor::new(),
+            latency: crate::latency::new(),
+            acl: crate::acl::new(),
+            config: crate::config::share(crate::config::Config::new()),
+            databases: crate::store::new_databases(1),
+        }
+    }
+
+    /// Sets the configuration deciding whether writes are refused, as a replica.
+    pub fn with_config(mut self, config: crate::config::SharedConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets the databases that keys are evicted from when they exceed their memory budget.
+    pub fn with_databases(mut self, databases: crate::store::SharedDatabases) -> Self {
+        self.databases = databases;
+        self
+    }
+
+    /// Gets the access control lists the commands are checked against.
+    pub fn acl(&self) -> crate::acl::SharedAcl {
+        self.acl.clone()
+    }
+
+    /// Gets the latency monitor the slow commands are recorded in.
+    pub fn latency(&self) -> crate::latency::SharedLatency {
+        self.latency.clone()
+    }
+
+    /// Gets the feed of the commands run, streamed to the connections running MONITOR.
+    pub fn monitor(&self) -> crate::monitor::SharedMonitor {
+        self.monitor.clone()
+    }
+
+    /// Gets the pause holding back the commands, such as one set by CLIENT PAUSE.
+    pub fn pause(&self) -> crate::pause::SharedPause {
+        self.pause.clone()
+    }
+
+    /// Gets the statistics the calls of the commands are recorded in.
+    pub fn stats(&self) -> crate::stats::SharedStats {
+        self.stats.clone()
     }
 
     /// Registers one command.
     pub fn register(&mut self, command: Box<dyn Command>) {
-        self.0.insert(command.name().to_uppercase(), command);
+        self.commands.insert(command.name().to_uppercase(), command);
     }
 
     /// Registers multiple commands.
@@ -47,7 +288,78 @@ impl Register {
         }
     }
 
-    /// Handles the command.
+    /// Renames a command so it can only be run by its new name, or disables it if the new name is
+    /// empty. The command keeps its name in its details and statistics.
+    pub fn rename(&mut self, name: &str, new_name: &str) -> anyhow::Result<()> {
+        let new_name = new_name.to_uppercase();
+        if self.commands.contains_key(&new_name) {
+            return Err(anyhow::anyhow!(
+                "Failed to rename '{name}', the command '{new_name}' already exists"
+            ));
+        }
+        let command = self
+            .commands
+            .remove(&name.to_uppercase())
+            .ok_or_else(|| anyhow::anyhow!("Failed to rename '{name}', no such command"))?;
+        if !new_name.is_empty() {
+            self.commands.insert(new_name, command);
+        }
+        Ok(())
+    }
+
+    /// Gets a command by its case-insensitive name.
+    pub fn get(&self, name: &str) -> Option<&dyn Command> {
+        self.commands
+            .get(&name.to_uppercase())
+            .map(|command| command.as_ref())
+    }
+
+    /// Gets the registered commands, sorted by name.
+    pub fn commands(&self) -> Vec<&dyn Command> {
+        let mut commands = self
+            .commands
+            .values()
+            .map(|command| command.as_ref())
+            .collect::<Vec<_>>();
+        commands.sort_unstable_by_key(|command| command.name());
+        commands
+    }
+
+    /// Checks that the server's state allows running a command with the flags.
+    async fn check_flags(
+        &self,
+        flags: &[&str],
+        store: &crate::store::SharedStore,
+    ) -> anyhow::Result<()> {
+        if flags.contains(&"write") {
+            let config = self.config.read().await;
+            if config.replica_read_only && !config.replicaof.is_empty() {
+                return Err(anyhow::anyhow!(READONLY_MESSAGE));
+            }
+        }
+        // The usage is tracked as the store changes, so the store is only locked for writing when
+        // keys need evicting.
+        if flags.contains(&"denyoom") && store.read().await.is_over_budget() {
+            crate::store::free_memory(&self.databases).await?;
+        }
+        Ok(())
+    }
+
+    /// Handles the command, refusing commands that need authentication on an unauthenticated
+    /// connection, and commands, keys or channels the connection's user cannot access.
+    ///
+    /// Commands flagged `write` are refused on a read-only replica, and those flagged `denyoom`
+    /// when the store is over its memory budget and cannot evict keys.
+    ///
+    /// While commands are paused, the command waits for the pause to end. Admin commands are never
+    /// paused, so that the pause can be lifted.
+    ///
+    /// The command is then fed to the monitoring connections, except for admin commands and
+    /// those that can authenticate, so that passwords are not shown.
+    ///
+    /// The time spent running the command, not waiting for a pause, is recorded in the command's
+    /// statistics along with whether it failed, and by the latency monitor as a `fast-command`
+    /// event for fast commands and a `command` event otherwise.
     pub async fn handle(
         &self,
         command: String,
@@ -55,9 +367,45 @@ impl Register {
         store: &crate::store::SharedStore,
         state: &mut crate::state::State,
     ) -> crate::resp::RespType {
-        match self.0.get(&command.to_uppercase()) {
-            Some(command) => command.handle(args, store, state).await,
-            _ => {
+        let registered = self.commands.get(&command.to_uppercase());
+        // As in Redis, unauthenticated clients cannot tell which commands exist.
+        if !state.authenticated
+            && !registered.is_some_and(|registered| registered.flags().contains(&"no_auth"))
+        {
+            return crate::resp::RespType::SimpleError("NOAUTH Authentication required.".into());
+        }
+        match registered {
+            Some(registered) => {
+                if let Err(err) = self.acl.check(&state.user, registered.as_ref(), &args) {
+                    log::error!("{err}");
+                    return crate::resp::RespType::SimpleError(err.to_string());
+                }
+                let flags = registered.flags();
+                if let Err(err) = self.check_flags(&flags, store).await {
+                    log::error!("{err}");
+                    return crate::resp::RespType::SimpleError(err.to_string());
+                }
+                if !flags.contains(&"admin") {
+                    self.pause.wait(flags.contains(&"write")).await;
+                }
+                if !flags.contains(&"admin") && !flags.contains(&"no_auth") {
+                    self.monitor.feed(state, &command, &args);
+                }
+                let start = tokio::time::Instant::now();
+                let response = registered.handle(args, store, state).await;
+                let duration = start.elapsed();
+                let failed = matches!(response, crate::resp::RespType::SimpleError(_));
+                self.stats
+                    .record_command(&registered.name(), duration, failed);
+                let event = if flags.contains(&"fast") {
+                    "fast-command"
+                } else {
+                    "command"
+                };
+                self.latency.record(event, duration);
+                response
+            }
+            None => {
                 crate::resp::RespType::SimpleError(format!("ERR Command ({command}) is not valid"))
             }
         }
@@ -66,13 +414,12 @@ impl Register {
 
 impl PartialEq for Register {
     fn eq(&self, other: &Self) -> bool {
-        if self.0.len() != other.0.len() {
+        if self.commands.len() != other.commands.len() {
             return false;
         }
 
-        self.0.iter().all(|(key, command)| match other.0.get(key) {
-            Some(other_command) if command.name() == other_command.name() => true,
-            _ => false,
+        self.commands.iter().all(|(key, command)| {
+            matches!(other.commands.get(key), Some(other_command) if command.name() == other_command.name())
         })
     }
 }
@@ -80,7 +427,7 @@ impl PartialEq for Register {
 impl std::fmt::Debug for Register {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut commands = self
-            .0
+            .commands
             .values()
             .map(|command| command.name())
             .collect::<Vec<_>>();
@@ -125,7 +472,7 @@ mod tests {
 
     #[async_trait::async_trait]
     impl Command for A {
-        fn name(&self) -> String {
+        fn static_name() -> String {
             "A".into()
         }
 
@@ -144,7 +491,7 @@ mod tests {
 
     #[async_trait::async_trait]
     impl Command for B {
-        fn name(&self) -> String {
+        fn static_name() -> String {
             "B".into()
         }
 
@@ -158,6 +505,26 @@ mod tests {
         }
     }
 
+    #[derive(Clone)]
+    struct Slow;
+
+    #[async_trait::async_trait]
+    impl Command for Slow {
+        fn static_name() -> String {
+            "SLOW".into()
+        }
+
+        async fn handle(
+            &self,
+            _: Vec<crate::resp::RespType>,
+            _: &crate::store::SharedStore,
+            _: &mut crate::state::State,
+        ) -> crate::resp::RespType {
+            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+            crate::resp::RespType::SimpleString("SLOW".into())
+        }
+    }
+
     // --- Fixtures ---
     #[fixture]
     fn store() -> crate::store::SharedStore {
@@ -169,30 +536,196 @@ mod tests {
         crate::state::State::new(0)
     }
 
+    fn make_register(commands: Vec<(&str, Box<dyn Command>)>) -> Register {
+        Register {
+            commands: commands
+                .into_iter()
+                .map(|(name, command)| (name.to_string(), command))
+                .collect(),
+            stats: crate::stats::new(),
+            pause: crate::pause::new(),
+            monitor: crate::monitor::new(),
+            latency: crate::latency::new(),
+            acl: crate::acl::new(),
+            config: crate::config::new(),
+            databases: crate::store::new_databases(1),
+        }
+    }
+
     // --- Tests ---
+    #[rstest]
+    fn test_name_defaults_to_static_name() {
+        assert_eq!(A::static_name(), A.name());
+        assert_eq!(B::static_name(), (Box::new(B) as Box<dyn Command>).name());
+    }
+
+    #[rstest]
+    fn test_wrong_type_error() {
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "WRONGTYPE Operation against a key holding the wrong kind of value".into()
+            ),
+            wrong_type_error()
+        );
+    }
+
+    #[rstest]
+    fn test_dispatch_subcommand_help() {
+        let args = vec![crate::resp::RespType::BulkString(Some("help".into()))];
+        let expected = crate::resp::RespType::Array(vec![
+            crate::resp::RespType::SimpleString(
+                "CMD <subcommand> [<arg> [value] [opt] ...]. Subcommands are:".into(),
+            ),
+            crate::resp::RespType::SimpleString("GET <key>".into()),
+            crate::resp::RespType::SimpleString("    Get the key.".into()),
+            crate::resp::RespType::SimpleString("HELP".into()),
+            crate::resp::RespType::SimpleString("    Print this help.".into()),
+        ]);
+        assert_eq!(
+            Some(expected),
+            dispatch_subcommand("CMD", &args, &[("GET <key>", "Get the key.")])
+        );
+    }
+
+    #[rstest]
+    #[case::known("get", None)]
+    #[case::known_upper("GET", None)]
+    #[case::unknown(
+        "foo",
+        Some(crate::resp::RespType::SimpleError(
+            "ERR Unknown subcommand or wrong number of arguments for 'foo'. Try CMD HELP.".into()
+        ))
+    )]
+    fn test_dispatch_subcommand(
+        #[case] subcommand: &str,
+        #[case] expected: Option<crate::resp::RespType>,
+    ) {
+        let args = vec![crate::resp::RespType::BulkString(Some(
+            subcommand.to_string().into(),
+        ))];
+        assert_eq!(
+            expected,
+            dispatch_subcommand("CMD", &args, &[("GET <key>", "Get the key.")])
+        );
+    }
+
+    #[rstest]
+    #[case::missing(vec![])]
+    #[case::invalid(vec![crate::resp::RespType::Array(vec![])])]
+    fn test_dispatch_subcommand_unparsable(#[case] args: Vec<crate::resp::RespType>) {
+        assert_eq!(None, dispatch_subcommand("CMD", &args, &[]));
+    }
+
+    #[rstest]
+    #[case::first(0, 3, Some(0))]
+    #[case::last(2, 3, Some(2))]
+    #[case::out_of_range(3, 3, None)]
+    #[case::negative_last(-1, 3, Some(2))]
+    #[case::negative_first(-3, 3, Some(0))]
+    #[case::negative_out_of_range(-4, 3, None)]
+    #[case::empty(0, 0, None)]
+    fn test_resolve_index(
+        #[case] index: i64,
+        #[case] length: usize,
+        #[case] expected: Option<usize>,
+    ) {
+        assert_eq!(expected, resolve_index(index, length));
+    }
+
+    #[rstest]
+    #[case::keys(&["2", "a", "b", "left"], vec!["a", "b"])]
+    #[case::missing_keys(&["3", "a"], vec!["a"])]
+    #[case::invalid_numkeys(&["a", "b"], vec![])]
+    #[case::empty(&[], vec![])]
+    fn test_numkeys_keys(#[case] args: &[&str], #[case] expected: Vec<&str>) {
+        let args = args
+            .iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, numkeys_keys(&args));
+    }
+
     #[rstest]
     fn test_new() {
-        let expected = Register(std::collections::HashMap::new());
+        let expected = make_register(vec![]);
         assert_eq!(expected, Register::new());
     }
 
     #[rstest]
     fn test_register() {
-        let mut expected = Register(std::collections::HashMap::new());
-        expected.0.insert("A".into(), Box::new(A));
+        let expected = make_register(vec![("A", Box::new(A))]);
         let mut result = Register::new();
         result.register(Box::new(A));
         assert_eq!(expected, result);
     }
 
+    #[rstest]
+    #[case::exact("A", Some("A"))]
+    #[case::lowercase("a", Some("A"))]
+    #[case::unknown("B", None)]
+    fn test_register_get(#[case] name: &str, #[case] expected: Option<&str>) {
+        let mut register = Register::new();
+        register.register(Box::new(A));
+        assert_eq!(
+            expected.map(String::from),
+            register.get(name).map(|command| command.name())
+        );
+    }
+
+    #[rstest]
+    #[case::renamed("b", Some("A"))]
+    #[case::old_name("A", None)]
+    fn test_rename(#[case] name: &str, #[case] expected: Option<&str>) {
+        let mut register = Register::new();
+        register.register(Box::new(A));
+        register.rename("a", "b").unwrap();
+        assert_eq!(
+            expected.map(String::from),
+            register.get(name).map(|command| command.name())
+        );
+    }
+
+    #[rstest]
+    fn test_rename_disable() {
+        let mut register = make_register(vec![("A", Box::new(A)), ("B", Box::new(B))]);
+        register.rename("A", "").unwrap();
+        assert_eq!(make_register(vec![("B", Box::new(B))]), register);
+    }
+
+    #[rstest]
+    #[case::unknown("C", "D", "Failed to rename 'C', no such command")]
+    #[case::existing("A", "b", "Failed to rename 'A', the command 'B' already exists")]
+    fn test_rename_errors(#[case] name: &str, #[case] new_name: &str, #[case] expected: &str) {
+        let mut register = make_register(vec![("A", Box::new(A)), ("B", Box::new(B))]);
+        let result = register.rename(name, new_name);
+        assert_eq!(expected, result.unwrap_err().to_string());
+        assert_eq!(
+            make_register(vec![("A", Box::new(A)), ("B", Box::new(B))]),
+            register
+        );
+    }
+
+    #[rstest]
+    fn test_commands() {
+        let register = make_register(vec![("B", Box::new(B)), ("A", Box::new(A))]);
+        assert_eq!(
+            vec!["A", "B"],
+            register
+                .commands()
+                .iter()
+                .map(|command| command.name())
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[rstest]
     #[case::single(vec![("A", Box::new(A) as Box<dyn CloneableCommand>)])]
     #[case::multiple(vec![("A", Box::new(A) as Box<dyn CloneableCommand>), ("B", Box::new(B) as Box<dyn CloneableCommand>)])]
     fn test_register_multiple(#[case] commands: Vec<(&str, Box<dyn CloneableCommand>)>) {
-        let expected = Register(
+        let expected = make_register(
             commands
                 .iter()
-                .map(|(name, command)| (name.to_string(), command.clone() as Box<dyn Command>))
+                .map(|(name, command)| (*name, command.clone() as Box<dyn Command>))
                 .collect(),
         );
         let mut result = Register::new();
@@ -223,14 +756,317 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_unauthenticated(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let mut register = Register::new();
+        register.register_multiple(vec![
+            Box::new(crate::commands::ping::Ping),
+            Box::new(crate::commands::quit::Quit),
+        ]);
+        state.authenticated = false;
+
+        let response = register
+            .handle("ping".into(), vec![], &store, &mut state)
+            .await;
+        let expected = crate::resp::RespType::SimpleError("NOAUTH Authentication required.".into());
+        assert_eq!(expected, response);
+
+        let response = register
+            .handle("unknown".into(), vec![], &store, &mut state)
+            .await;
+        assert_eq!(expected, response);
+
+        let response = register
+            .handle("quit".into(), vec![], &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+    }
+
+    #[rstest]
+    #[case::write("set", &["key", "value"], true, Some(READONLY_MESSAGE))]
+    #[case::read("get", &["key"], true, None)]
+    #[case::writable("set", &["key", "value"], false, None)]
+    #[tokio::test]
+    async fn test_handle_read_only_replica(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] command: &str,
+        #[case] args: &[&str],
+        #[case] replica_read_only: bool,
+        #[case] expected: Option<&str>,
+    ) {
+        let config = crate::config::new();
+        {
+            let mut config = config.write().await;
+            config.replicaof = "localhost 6380".into();
+            config.replica_read_only = replica_read_only;
+        }
+        let mut register = Register::new().with_config(config);
+        register.register_multiple(vec![
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::set::Set),
+        ]);
+
+        let args = args
+            .iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect();
+        let response = register
+            .handle(command.into(), args, &store, &mut state)
+            .await;
+        if let Some(expected) = expected {
+            assert_eq!(
+                crate::resp::RespType::SimpleError(expected.into()),
+                response
+            );
+        } else {
+            assert!(!matches!(response, crate::resp::RespType::SimpleError(_)));
+        }
+    }
+
+    #[rstest]
+    #[case::denyoom("rpush", &["list", "value"], true)]
+    #[case::write("unlink", &["key"], false)]
+    #[case::read("get", &["key"], false)]
+    #[tokio::test]
+    async fn test_handle_over_maxmemory(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] command: &str,
+        #[case] args: &[&str],
+        #[case] expected_oom: bool,
+    ) {
+        {
+            let mut store = store.write().await;
+            store.insert("key".into(), crate::store::Entry::new_string("value"));
+            store.set_eviction(1, crate::store::EvictionPolicy::NoEviction);
+        }
+        let mut register = Register::new().with_databases(std::sync::Arc::new(vec![store.clone()]));
+        register.register_multiple(vec![
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::rpush::Rpush),
+            Box::new(crate::commands::unlink::Unlink),
+        ]);
+
+        let args = args
+            .iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect();
+        let response = register
+            .handle(command.into(), args, &store, &mut state)
+            .await;
+        assert_eq!(
+            expected_oom,
+            response
+                == crate::resp::RespType::SimpleError(
+                    "OOM command not allowed when used memory > 'maxmemory'.".into()
+                )
+        );
+        assert!(store.read().await.get("list").is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_check_flags_within_budget(store: crate::store::SharedStore) {
+        store
+            .write()
+            .await
+            .set_eviction(1024, crate::store::EvictionPolicy::NoEviction);
+        let register = Register::new();
+        // A reader holding the store would block the check if it locked the store for writing.
+        let _guard = store.read().await;
+        let result = tokio::time::timeout(
+            tokio::time::Duration::from_millis(100),
+            register.check_flags(&["write", "denyoom"], &store),
+        )
+        .await;
+        assert!(result.unwrap().is_ok());
+    }
+
+    #[rstest]
+    #[case::allowed("get", &["user:1"], None)]
+    #[case::command(
+        "set",
+        &["user:1", "value"],
+        Some("NOPERM User alice has no permissions to run the 'set' command")
+    )]
+    #[case::key("get", &["session:1"], Some("NOPERM No permissions to access a key"))]
+    #[tokio::test]
+    async fn test_handle_acl(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] command: &str,
+        #[case] args: &[&str],
+        #[case] expected: Option<&str>,
+    ) {
+        let mut register = Register::new();
+        register.register_multiple(vec![
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::set::Set),
+        ]);
+        register
+            .acl()
+            .set_user("alice", &["on", "nopass", "+@read", "~user:*"])
+            .unwrap();
+        state.user = "alice".into();
+
+        let args = args
+            .iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect();
+        let response = register
+            .handle(command.into(), args, &store, &mut state)
+            .await;
+        match expected {
+            Some(expected) => assert_eq!(
+                crate::resp::RespType::SimpleError(expected.into()),
+                response
+            ),
+            None => assert_eq!(crate::resp::RespType::BulkString(None), response),
+        }
+    }
+
+    #[rstest]
+    #[case::read("get", crate::pause::PauseMode::All, true)]
+    #[case::write("set", crate::pause::PauseMode::All, true)]
+    #[case::read_write_pause("get", crate::pause::PauseMode::Write, false)]
+    #[case::write_write_pause("set", crate::pause::PauseMode::Write, true)]
+    #[case::admin("client", crate::pause::PauseMode::All, false)]
+    #[tokio::test]
+    async fn test_handle_paused(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] command: &str,
+        #[case] mode: crate::pause::PauseMode,
+        #[case] expected: bool,
+    ) {
+        tokio::time::pause();
+        let mut register = Register::new();
+        register.register_multiple(vec![
+            Box::new(crate::commands::client::Client::new(
+                crate::clients::new(),
+                register.pause(),
+            )),
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::set::Set),
+        ]);
+        register
+            .pause()
+            .pause(mode, tokio::time::Duration::from_millis(100));
+
+        let start = tokio::time::Instant::now();
+        let args = vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::BulkString(Some("value".into())),
+        ];
+        register
+            .handle(command.into(), args, &store, &mut state)
+            .await;
+        assert_eq!(expected, start.elapsed() > tokio::time::Duration::ZERO);
+    }
+
+    #[rstest]
+    #[case::fed("ping", true)]
+    #[case::admin("monitor", false)]
+    #[case::no_auth("quit", false)]
+    #[tokio::test]
+    async fn test_handle_feeds_monitor(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] command: &str,
+        #[case] expected: bool,
+    ) {
+        let mut register = Register::new();
+        register.register_multiple(vec![
+            Box::new(crate::commands::monitor::Monitor),
+            Box::new(crate::commands::ping::Ping),
+            Box::new(crate::commands::quit::Quit),
+        ]);
+        let mut receiver = register.monitor().subscribe();
+
+        register
+            .handle(command.into(), vec![], &store, &mut state)
+            .await;
+        match receiver.try_recv() {
+            Ok(line) => assert!(
+                expected && line.ends_with(&format!("\"{command}\"")),
+                "{line}"
+            ),
+            Err(_) => assert!(!expected),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_records_stats(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let mut register = Register::new();
+        register.register(Box::new(crate::commands::get::Get));
+        for _ in 0..3 {
+            register
+                .handle(
+                    "get".into(),
+                    vec![crate::resp::RespType::BulkString(Some("key".into()))],
+                    &store,
+                    &mut state,
+                )
+                .await;
+        }
+        register
+            .handle("get".into(), vec![], &store, &mut state)
+            .await;
+        register
+            .handle("unknown".into(), vec![], &store, &mut state)
+            .await;
+
+        let stats = register.stats();
+        let command_stats = stats.all_command_stats();
+        assert_eq!(1, command_stats.len());
+        assert_eq!("get", command_stats[0].0);
+        assert_eq!(4, command_stats[0].1.calls);
+        assert_eq!(1, command_stats[0].1.failed_calls);
+        assert_eq!(4, stats.total_commands_processed());
+    }
+
+    #[rstest]
+    #[case::slow("slow", true)]
+    #[case::quick("a", false)]
+    #[tokio::test]
+    async fn test_handle_records_latency(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] command: &str,
+        #[case] expected: bool,
+    ) {
+        tokio::time::pause();
+        let register = make_register(vec![("A", Box::new(A)), ("SLOW", Box::new(Slow))]);
+        register.latency().set_threshold(100);
+        register
+            .handle(command.into(), vec![], &store, &mut state)
+            .await;
+
+        let history = register.latency().history("command");
+        assert_eq!(expected, !history.is_empty());
+        if expected {
+            assert!(history[0].latency_ms >= 200);
+        }
+    }
+
     #[rstest]
     #[case::single(vec![Box::new(A) as Box<dyn CloneableCommand>], "Register { Commands: [\"A\"] }")]
     #[case::multiple(vec![Box::new(A) as Box<dyn CloneableCommand>, Box::new(B) as Box<dyn CloneableCommand>], "Register { Commands: [\"A\", \"B\"] }")]
     fn test_fmt(#[case] commands: Vec<Box<dyn CloneableCommand>>, #[case] expected: &str) {
-        let register = Register(
+        let mut register = Register::new();
+        register.register_multiple(
             commands
                 .into_iter()
-                .map(|command| (command.name(), command as Box<dyn Command>))
+                .map(|command| command as Box<dyn Command>)
                 .collect(),
         );
         assert_eq!(expected, format!("{:?}", register));
@@ -238,16 +1074,16 @@ mod tests {
 
     #[rstest]
     #[case::length(
-        Register(vec![("A".to_string(), Box::new(A) as Box<dyn Command>)].into_iter().collect()),
-        Register(vec![("A".to_string(), Box::new(A) as Box<dyn Command>), ("B".to_string(), Box::new(B) as Box<dyn Command>)].into_iter().collect())
+        make_register(vec![("A", Box::new(A) as Box<dyn Command>)]),
+        make_register(vec![("A", Box::new(A) as Box<dyn Command>), ("B", Box::new(B) as Box<dyn Command>)])
     )]
     #[case::mismatch_keys(
-        Register(vec![("A".to_string(), Box::new(A) as Box<dyn Command>)].into_iter().collect()),
-        Register(vec![("B".to_string(), Box::new(A) as Box<dyn Command>)].into_iter().collect())
+        make_register(vec![("A", Box::new(A) as Box<dyn Command>)]),
+        make_register(vec![("B", Box::new(A) as Box<dyn Command>)])
     )]
     #[case::mismatch_values(
-        Register(vec![("A".to_string(), Box::new(A) as Box<dyn Command>)].into_iter().collect()),
-        Register(vec![("A".to_string(), Box::new(B) as Box<dyn Command>)].into_iter().collect())
+        make_register(vec![("A", Box::new(A) as Box<dyn Command>)]),
+        make_register(vec![("A", Box::new(B) as Box<dyn Command>)])
     )]
     fn test_register_equal(#[case] a: Register, #[case] b: Register) {
         assert_ne!(a, b);
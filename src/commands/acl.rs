@@ -0,0 +1,480 @@
+//! This module contains the ACL command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The ACL subcommands' usages and descriptions.
+const SUBCOMMANDS: [(&str, &str); 5] = [
+    (
+        "CAT [<category>]",
+        "List the command categories, or the commands in the category.",
+    ),
+    (
+        "GETUSER <username>",
+        "Return the flags, passwords, commands, keys and channels of the user.",
+    ),
+    ("LIST", "Return the rules of every user."),
+    (
+        "SETUSER <username> [<rule> ...]",
+        "Create or update the user with the rules.",
+    ),
+    (
+        "WHOAMI",
+        "Return the name of the user the connection is authenticated as.",
+    ),
+];
+
+/// The ACL subcommands.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Cat(Option<String>),
+    GetUser(String),
+    List,
+    SetUser(String, Vec<String>),
+    WhoAmI,
+}
+
+/// Parses the ACL options.
+fn parse_acl_options<I: IntoIterator<Item = crate::resp::RespType>>(iter: I) -> Result<Subcommand> {
+    let mut iter = iter.into_iter();
+
+    let subcommand = crate::resp::extract_string(&iter.next().context("Missing subcommand")?)
+        .context("Failed to extract subcommand")?;
+
+    let subcommand = match subcommand.to_lowercase().as_str() {
+        "cat" => {
+            let category = iter
+                .next()
+                .map(|token| {
+                    crate::resp::extract_string(&token).context("Failed to extract category")
+                })
+                .transpose()?;
+            Subcommand::Cat(category.map(|category| category.to_lowercase()))
+        }
+        "getuser" => Subcommand::GetUser(
+            crate::resp::extract_string(&iter.next().context("Missing username")?)
+                .context("Failed to extract username")?,
+        ),
+        "list" => Subcommand::List,
+        "setuser" => {
+            let username = crate::resp::extract_string(&iter.next().context("Missing username")?)
+                .context("Failed to extract username")?;
+            let rules = iter
+                .by_ref()
+                .map(|rule| crate::resp::extract_string(&rule).context("Failed to extract rule"))
+                .collect::<Result<Vec<_>>>()?;
+            Subcommand::SetUser(username, rules)
+        }
+        "whoami" => Subcommand::WhoAmI,
+        _ => return Err(anyhow::anyhow!("Unknown subcommand '{subcommand}'")),
+    };
+    if iter.next().is_some() {
+        return Err(anyhow::anyhow!("Too many arguments"));
+    }
+
+    Ok(subcommand)
+}
+
+/// Makes the reply of ACL GETUSER describing the user.
+fn make_user(user: &crate::acl::User) -> crate::resp::RespType {
+    let patterns = |prefix: char, patterns: &[String]| {
+        patterns
+            .iter()
+            .map(|pattern| format!("{prefix}{pattern}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let fields = [
+        (
+            "flags",
+            crate::resp::RespType::Array(
+                user.flags()
+                    .into_iter()
+                    .map(|flag| crate::resp::RespType::BulkString(Some(flag.into())))
+                    .collect(),
+            ),
+        ),
+        (
+            "passwords",
+            crate::resp::RespType::Array(
+                user.passwords
+                    .iter()
+                    .map(|digest| crate::resp::RespType::BulkString(Some(digest.clone().into())))
+                    .collect(),
+            ),
+        ),
+        (
+            "commands",
+            crate::resp::RespType::BulkString(Some(user.commands().into())),
+        ),
+        (
+            "keys",
+            crate::resp::RespType::BulkString(Some(patterns('~', &user.key_patterns).into())),
+        ),
+        (
+            "channels",
+            crate::resp::RespType::BulkString(Some(patterns('&', &user.channel_patterns).into())),
+        ),
+    ];
+    crate::resp::RespType::Map(
+        fields
+            .into_iter()
+            .map(|(name, value)| (crate::resp::RespType::BulkString(Some(name.into())), value))
+            .collect(),
+    )
+}
+
+pub struct Acl {
+    register: crate::commands::SharedRegister,
+}
+
+impl Acl {
+    /// Creates the ACL command, managing the users of the register's access control lists.
+    pub fn new(register: crate::commands::SharedRegister) -> Self {
+        Self { register }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Acl {
+    fn static_name() -> String {
+        "ACL".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["admin", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the ACL command.
+    ///
+    /// The commands named by SETUSER rules must be registered. Changes to a user apply to the
+    /// connections already authenticated as it from their next command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        if let Some(response) = crate::commands::dispatch_subcommand("ACL", &args, &SUBCOMMANDS) {
+            return response;
+        }
+        let subcommand = match parse_acl_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'ACL' command"));
+            }
+        };
+
+        let register = self.register.read().await;
+        let acl = register.acl();
+        match subcommand {
+            Subcommand::Cat(None) => crate::resp::RespType::Array(
+                crate::acl::CATEGORIES
+                    .iter()
+                    .map(|category| {
+                        crate::resp::RespType::BulkString(Some(category.to_string().into()))
+                    })
+                    .collect(),
+            ),
+            Subcommand::Cat(Some(category)) => {
+                if category != "all" && !crate::acl::CATEGORIES.contains(&category.as_str()) {
+                    return crate::resp::RespType::SimpleError(format!(
+                        "ERR Unknown category '{category}' for 'ACL' command"
+                    ));
+                }
+                crate::resp::RespType::Array(
+                    register
+                        .commands()
+                        .into_iter()
+                        .filter(|command| {
+                            category == "all"
+                                || crate::acl::categories(&command.flags())
+                                    .contains(&category.as_str())
+                        })
+                        .map(|command| {
+                            crate::resp::RespType::BulkString(Some(
+                                command.name().to_lowercase().into(),
+                            ))
+                        })
+                        .collect(),
+                )
+            }
+            Subcommand::GetUser(username) => match acl.get_user(&username) {
+                Some(user) => make_user(&user),
+                None => crate::resp::RespType::Null(),
+            },
+            Subcommand::List => crate::resp::RespType::Array(
+                acl.users()
+                    .iter()
+                    .map(|user| crate::resp::RespType::BulkString(Some(user.to_string().into())))
+                    .collect(),
+            ),
+            Subcommand::SetUser(username, rules) => {
+                let unknown = rules.iter().find(|rule| {
+                    let name = rule.strip_prefix(['+', '-']).unwrap_or_default();
+                    !name.is_empty() && !name.starts_with('@') && register.get(name).is_none()
+                });
+                if let Some(rule) = unknown {
+                    return crate::resp::RespType::SimpleError(format!(
+                        "ERR Error in ACL SETUSER modifier '{rule}': Unknown command or category name in ACL"
+                    ));
+                }
+                match acl.set_user(&username, &rules) {
+                    Ok(()) => crate::resp::RespType::SimpleString("OK".into()),
+                    Err(err) => {
+                        log::error!("{err}");
+                        crate::resp::RespType::SimpleError(format!("ERR {err}"))
+                    }
+                }
+            }
+            Subcommand::WhoAmI => {
+                crate::resp::RespType::BulkString(Some(state.user.clone().into()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn register() -> crate::commands::SharedRegister {
+        let mut register = crate::commands::Register::new();
+        register.register_multiple(vec![
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::ping::Ping),
+            Box::new(crate::commands::set::Set),
+        ]);
+        std::sync::Arc::new(tokio::sync::RwLock::new(register))
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    fn make_bulk_strings(values: &[&str]) -> crate::resp::RespType {
+        crate::resp::RespType::Array(
+            values
+                .iter()
+                .map(|value| crate::resp::RespType::BulkString(Some(value.to_string().into())))
+                .collect(),
+        )
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("ACL", Acl::static_name());
+    }
+
+    #[rstest]
+    #[case::categories(&["CAT"], &crate::acl::CATEGORIES)]
+    #[case::all(&["cat", "all"], &["get", "ping", "set"])]
+    #[case::read(&["CAT", "read"], &["get"])]
+    #[case::fast(&["CAT", "FAST"], &["get", "ping"])]
+    #[case::empty(&["CAT", "admin"], &[])]
+    #[tokio::test]
+    async fn test_handle_cat(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        register: crate::commands::SharedRegister,
+        #[case] args: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let response = Acl::new(register)
+            .handle(make_args(args), &store, &mut state)
+            .await;
+        assert_eq!(make_bulk_strings(expected), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_setuser(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        register: crate::commands::SharedRegister,
+    ) {
+        let response = Acl::new(register.clone())
+            .handle(
+                make_args(&[
+                    "SETUSER", "alice", "on", ">secret", "~user:*", "&news", "+@read", "+SET",
+                ]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+
+        let acl = register.read().await.acl();
+        let user = acl.get_user("alice").unwrap();
+        assert!(user.enabled);
+        assert!(user.check_password("secret"));
+        assert_eq!(vec!["user:*".to_string()], user.key_patterns);
+        assert_eq!(vec!["news".to_string()], user.channel_patterns);
+        assert!(user.can_run("get", &["read"]));
+        assert!(user.can_run("set", &["write"]));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_getuser(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        register: crate::commands::SharedRegister,
+    ) {
+        register
+            .read()
+            .await
+            .acl()
+            .set_user("alice", &["on", ">secret", "~a*", "~b*", "+get"])
+            .unwrap();
+
+        let response = Acl::new(register)
+            .handle(make_args(&["GETUSER", "alice"]), &store, &mut state)
+            .await;
+        let field = |name: &str, value: crate::resp::RespType| {
+            (
+                crate::resp::RespType::BulkString(Some(name.to_string().into())),
+                value,
+            )
+        };
+        let bulk_string =
+            |value: &str| crate::resp::RespType::BulkString(Some(value.to_string().into()));
+        assert_eq!(
+            crate::resp::RespType::Map(vec![
+                field("flags", make_bulk_strings(&["on"])),
+                field(
+                    "passwords",
+                    make_bulk_strings(&[&crate::acl::hash_password("secret")])
+                ),
+                field("commands", bulk_string("-@all +get")),
+                field("keys", bulk_string("~a* ~b*")),
+                field("channels", bulk_string("")),
+            ]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_getuser_missing(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        register: crate::commands::SharedRegister,
+    ) {
+        let response = Acl::new(register)
+            .handle(make_args(&["GETUSER", "alice"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Null(), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_list(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        register: crate::commands::SharedRegister,
+    ) {
+        register
+            .read()
+            .await
+            .acl()
+            .set_user("alice", &["off", "~*", "+ping"])
+            .unwrap();
+
+        let response = Acl::new(register)
+            .handle(make_args(&["list"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            make_bulk_strings(&[
+                "user alice off ~* resetchannels -@all +ping",
+                "user default on nopass ~* &* +@all",
+            ]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_whoami(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        register: crate::commands::SharedRegister,
+    ) {
+        state.user = "alice".into();
+        let response = Acl::new(register)
+            .handle(make_args(&["WHOAMI"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("alice".into())),
+            response
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::unknown_category(
+        make_args(&["CAT", "unknown"]),
+        "ERR Unknown category 'unknown' for 'ACL' command"
+    )]
+    #[case::missing_username(
+        make_args(&["GETUSER"]),
+        "ERR Missing username for 'ACL' command"
+    )]
+    #[case::too_many_arguments(
+        make_args(&["WHOAMI", "extra"]),
+        "ERR Too many arguments for 'ACL' command"
+    )]
+    #[case::unknown_command(
+        make_args(&["SETUSER", "alice", "+get", "+unknown"]),
+        "ERR Error in ACL SETUSER modifier '+unknown': Unknown command or category name in ACL"
+    )]
+    #[case::unknown_category_rule(
+        make_args(&["SETUSER", "alice", "+@unknown"]),
+        "ERR Error in ACL SETUSER modifier '+@unknown': Unknown command or category name in ACL"
+    )]
+    #[case::invalid_rule(
+        make_args(&["SETUSER", "alice", "invalid"]),
+        "ERR Error in ACL SETUSER modifier 'invalid': Syntax error"
+    )]
+    #[case::unknown_subcommand(
+        make_args(&["DRYRUN"]),
+        "ERR Unknown subcommand or wrong number of arguments for 'DRYRUN'. Try ACL HELP."
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        register: crate::commands::SharedRegister,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Acl::new(register.clone())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        assert!(register.read().await.acl().get_user("alice").is_none());
+    }
+}
@@ -0,0 +1,231 @@
+//! This module contains the AUTH command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The AUTH options.
+#[derive(Debug, PartialEq)]
+struct AuthOptions {
+    /// The user to authenticate as, if given.
+    username: Option<String>,
+    password: String,
+}
+
+/// Parses the AUTH options.
+fn parse_auth_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<AuthOptions> {
+    let mut iter = iter.into_iter();
+
+    let first = crate::resp::extract_string(&iter.next().context("Missing password")?)
+        .context("Failed to extract password")?;
+    let Some(token) = iter.next() else {
+        return Ok(AuthOptions {
+            username: None,
+            password: first,
+        });
+    };
+    let password = crate::resp::extract_string(&token).context("Failed to extract password")?;
+    if iter.next().is_some() {
+        return Err(anyhow::anyhow!("Too many arguments"));
+    }
+
+    Ok(AuthOptions {
+        username: Some(first),
+        password,
+    })
+}
+
+pub struct Auth {
+    acl: crate::acl::SharedAcl,
+}
+
+impl Auth {
+    /// Creates the AUTH command, checking the credentials against the users of the ACL.
+    pub fn new(acl: crate::acl::SharedAcl) -> Self {
+        Self { acl }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Auth {
+    fn static_name() -> String {
+        "AUTH".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["noscript", "loading", "stale", "fast", "no_auth"]
+    }
+
+    /// Handles the AUTH command.
+    ///
+    /// The password alone authenticates as the default user. A failed attempt leaves the
+    /// connection authenticated as it was.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let options = match parse_auth_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'AUTH' command"));
+            }
+        };
+
+        let default_nopass = self
+            .acl
+            .get_user(crate::acl::DEFAULT_USER)
+            .is_some_and(|user| user.nopass);
+        if options.username.is_none() && default_nopass {
+            return crate::resp::RespType::SimpleError(
+                "ERR AUTH <password> called without any password configured for the default user. Are you sure your configuration is correct?".into(),
+            );
+        }
+        let username = options
+            .username
+            .as_deref()
+            .unwrap_or(crate::acl::DEFAULT_USER);
+        match self.acl.authenticate(username, &options.password) {
+            Ok(user) => {
+                state.authenticated = true;
+                state.user = user.name;
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+            Err(err) => {
+                log::error!("Client {} failed to authenticate.", state.client_id);
+                crate::resp::RespType::SimpleError(err.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        let mut state = crate::state::State::new(0);
+        state.authenticated = false;
+        state
+    }
+
+    /// An ACL where the default user's password is `secret` and `alice`'s is `wonderland`.
+    #[fixture]
+    fn acl() -> crate::acl::SharedAcl {
+        let acl = crate::acl::new();
+        acl.set_default_password("secret");
+        acl.set_user("alice", &["on", ">wonderland"]).unwrap();
+        acl
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("AUTH", Auth::static_name());
+    }
+
+    #[rstest]
+    #[case::password(&["secret"], "default")]
+    #[case::default_user(&["default", "secret"], "default")]
+    #[case::user(&["alice", "wonderland"], "alice")]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        acl: crate::acl::SharedAcl,
+        #[case] args: &[&str],
+        #[case] expected_user: &str,
+    ) {
+        let response = Auth::new(acl)
+            .handle(make_args(args), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert!(state.authenticated);
+        assert_eq!(expected_user, state.user);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_username_without_password(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = Auth::new(crate::acl::new())
+            .handle(make_args(&["default", "password"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert!(state.authenticated);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::wrong_password(
+        make_args(&["password"]),
+        "WRONGPASS invalid username-password pair or user is disabled."
+    )]
+    #[case::wrong_user(
+        make_args(&["user", "secret"]),
+        "WRONGPASS invalid username-password pair or user is disabled."
+    )]
+    #[case::invalid_password(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract password for 'AUTH' command"
+    )]
+    #[case::too_many_arguments(
+        make_args(&["default", "secret", "extra"]),
+        "ERR Too many arguments for 'AUTH' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        acl: crate::acl::SharedAcl,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Auth::new(acl).handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        assert!(!state.authenticated);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_without_password(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = Auth::new(crate::acl::new())
+            .handle(make_args(&["password"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR AUTH <password> called without any password configured for the default user. Are you sure your configuration is correct?".into()
+            ),
+            response
+        );
+        assert!(!state.authenticated);
+    }
+}
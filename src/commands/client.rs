@@ -0,0 +1,458 @@
+//! This module contains the CLIENT command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The CLIENT subcommands' usages and descriptions.
+const SUBCOMMANDS: [(&str, &str); 7] = [
+    ("ID", "Return the ID of the current connection."),
+    ("INFO", "Return information about the current connection."),
+    ("LIST", "Return information about the client connections."),
+    ("GETNAME", "Return the name of the current connection."),
+    (
+        "PAUSE <timeout> [WRITE|ALL]",
+        "Suspend the commands of all clients for the timeout in milliseconds, or only the write commands.",
+    ),
+    (
+        "SETNAME <name>",
+        "Assign the name to the current connection.",
+    ),
+    ("UNPAUSE", "Resume the suspended commands."),
+];
+
+/// The CLIENT subcommands.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Id,
+    Info,
+    List,
+    GetName,
+    Pause(u64, crate::pause::PauseMode),
+    SetName(String),
+    Unpause,
+}
+
+/// Checks that the client name only has printable ASCII characters other than spaces, so that it
+/// can be listed alongside other connection details.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.chars().any(|c| !('!'..='~').contains(&c)) {
+        return Err(anyhow::anyhow!(
+            "Client names cannot contain spaces, newlines or special characters"
+        ));
+    }
+    Ok(())
+}
+
+/// Parses the CLIENT options.
+fn parse_client_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Subcommand> {
+    let mut iter = iter.into_iter();
+
+    let subcommand = crate::resp::extract_string(&iter.next().context("Missing subcommand")?)
+        .context("Failed to extract subcommand")?;
+
+    match subcommand.to_lowercase().as_str() {
+        "id" => Ok(Subcommand::Id),
+        "info" => Ok(Subcommand::Info),
+        "list" => Ok(Subcommand::List),
+        "getname" => Ok(Subcommand::GetName),
+        "pause" => {
+            let timeout = crate::resp::extract_string(&iter.next().context("Missing timeout")?)
+                .context("Failed to extract timeout")?
+                .parse::<u64>()
+                .context("Failed to convert timeout to a non-negative number")?;
+            let mode = match iter.next() {
+                Some(token) => {
+                    let mode =
+                        crate::resp::extract_string(&token).context("Failed to extract mode")?;
+                    match mode.to_lowercase().as_str() {
+                        "all" => crate::pause::PauseMode::All,
+                        "write" => crate::pause::PauseMode::Write,
+                        _ => return Err(anyhow::anyhow!("{mode} is not a valid mode")),
+                    }
+                }
+                None => crate::pause::PauseMode::All,
+            };
+            Ok(Subcommand::Pause(timeout, mode))
+        }
+        "setname" => {
+            let name = crate::resp::extract_string(&iter.next().context("Missing name")?)
+                .context("Failed to extract name")?;
+            validate_name(&name)?;
+            Ok(Subcommand::SetName(name))
+        }
+        "unpause" => Ok(Subcommand::Unpause),
+        _ => Err(anyhow::anyhow!("Unknown subcommand '{subcommand}'")),
+    }
+}
+
+pub struct Client {
+    clients: crate::clients::SharedClients,
+    pause: crate::pause::SharedPause,
+}
+
+impl Client {
+    /// Creates the CLIENT command, listing the clients of the registry and pausing the commands
+    /// with the pause.
+    pub fn new(clients: crate::clients::SharedClients, pause: crate::pause::SharedPause) -> Self {
+        Self { clients, pause }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Client {
+    fn static_name() -> String {
+        "CLIENT".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["admin", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the CLIENT command.
+    ///
+    /// The connection's details are updated before being listed, so that they include this
+    /// command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        if let Some(response) = crate::commands::dispatch_subcommand("CLIENT", &args, &SUBCOMMANDS)
+        {
+            return response;
+        }
+        let subcommand = match parse_client_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'CLIENT' command"
+                ));
+            }
+        };
+
+        match subcommand {
+            Subcommand::Id => crate::resp::RespType::Integer(state.client_id as i64),
+            Subcommand::Info => {
+                self.clients.update(state);
+                let info = self
+                    .clients
+                    .get(state.client_id)
+                    .map(|client| format!("{client}\n"))
+                    .unwrap_or_default();
+                crate::resp::RespType::BulkString(Some(info.into()))
+            }
+            Subcommand::List => {
+                self.clients.update(state);
+                let list = self
+                    .clients
+                    .list()
+                    .iter()
+                    .map(|client| format!("{client}\n"))
+                    .collect::<String>();
+                crate::resp::RespType::BulkString(Some(list.into()))
+            }
+            Subcommand::GetName => match &state.name {
+                Some(name) => crate::resp::RespType::BulkString(Some(name.clone().into())),
+                None => crate::resp::RespType::Null(),
+            },
+            Subcommand::Pause(timeout, mode) => {
+                self.pause
+                    .pause(mode, tokio::time::Duration::from_millis(timeout));
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+            Subcommand::SetName(name) => {
+                state.set_name(name);
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+            Subcommand::Unpause => {
+                self.pause.unpause();
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn databases() -> crate::store::SharedDatabases {
+        crate::store::new_databases(1)
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn register() -> crate::commands::SharedRegister {
+        let mut register = crate::commands::Register::new();
+        register.register(Box::new(Client::new(
+            crate::clients::new(),
+            crate::pause::new(),
+        )));
+        std::sync::Arc::new(tokio::sync::RwLock::new(register))
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    /// Sends a command over the stream and reads the response.
+    async fn send(
+        stream: &mut tokio::io::DuplexStream,
+        args: &[&str],
+    ) -> anyhow::Result<crate::resp::RespType> {
+        let message = crate::resp::RespType::Array(make_args(&[&["CLIENT"], args].concat()));
+        stream.write_all(&message.serialize()).await?;
+        let mut buffer = bytes::BytesMut::with_capacity(512);
+        stream.read_buf(&mut buffer).await?;
+        crate::resp::RespType::from_bytes(&mut buffer)
+    }
+
+    /// Spawns a handler with a new client id, returning the client side of the stream.
+    fn spawn_handler(
+        databases: &crate::store::SharedDatabases,
+        register: &crate::commands::SharedRegister,
+    ) -> tokio::io::DuplexStream {
+        let (client_stream, server_stream) = tokio::io::duplex(512);
+        let mut handler =
+            crate::handler::RespHandler::new(server_stream, crate::state::next_client_id());
+        let (databases, register) = (databases.clone(), register.clone());
+        tokio::spawn(async move { handler.run(databases, register).await });
+        client_stream
+    }
+
+    /// Whether a command would wait for the pause.
+    async fn is_paused(pause: &crate::pause::Pause, is_write: bool) -> bool {
+        futures_util::FutureExt::now_or_never(pause.wait(is_write)).is_none()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("CLIENT", Client::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_id(store: crate::store::SharedStore) {
+        let mut state = crate::state::State::new(42);
+        let response = Client::new(crate::clients::new(), crate::pause::new())
+            .handle(make_args(&["ID"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(42), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_info(store: crate::store::SharedStore, mut state: crate::state::State) {
+        tokio::time::pause();
+        let clients = crate::clients::new();
+        let _first = clients.register(0, "127.0.0.1:5000".into());
+        let _second = clients.register(1, "127.0.0.1:5001".into());
+        state.db = 1;
+        state.last_command = Some("client".into());
+
+        let response = Client::new(clients, crate::pause::new())
+            .handle(make_args(&["INFO"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some(
+                "id=0 addr=127.0.0.1:5000 name= age=0 idle=0 db=1 sub=0 cmd=client\n".into()
+            )),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_list(store: crate::store::SharedStore, mut state: crate::state::State) {
+        tokio::time::pause();
+        let clients = crate::clients::new();
+        let _second = clients.register(1, "127.0.0.1:5001".into());
+        tokio::time::advance(tokio::time::Duration::from_secs(2)).await;
+        let _first = clients.register(0, "127.0.0.1:5000".into());
+        state.name = Some("first".into());
+        state.last_command = Some("client".into());
+
+        let response = Client::new(clients, crate::pause::new())
+            .handle(make_args(&["list"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some(
+                "id=0 addr=127.0.0.1:5000 name=first age=0 idle=0 db=0 sub=0 cmd=client\n\
+                 id=1 addr=127.0.0.1:5001 name= age=2 idle=2 db=0 sub=0 cmd=NULL\n"
+                    .into()
+            )),
+            response
+        );
+    }
+
+    #[rstest]
+    #[case::default(&["PAUSE", "100"], true)]
+    #[case::all(&["pause", "100", "all"], true)]
+    #[case::write(&["PAUSE", "100", "WRITE"], false)]
+    #[tokio::test]
+    async fn test_handle_pause(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected_read: bool,
+    ) {
+        tokio::time::pause();
+        let pause = crate::pause::new();
+        let response = Client::new(crate::clients::new(), pause.clone())
+            .handle(make_args(args), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(expected_read, is_paused(&pause, false).await);
+        assert!(is_paused(&pause, true).await);
+
+        tokio::time::advance(tokio::time::Duration::from_millis(100)).await;
+        assert!(!is_paused(&pause, true).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_unpause(store: crate::store::SharedStore, mut state: crate::state::State) {
+        tokio::time::pause();
+        let pause = crate::pause::new();
+        pause.pause(
+            crate::pause::PauseMode::All,
+            tokio::time::Duration::from_secs(10),
+        );
+        let response = Client::new(crate::clients::new(), pause.clone())
+            .handle(make_args(&["UNPAUSE"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert!(!is_paused(&pause, true).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_getname_unset(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = Client::new(crate::clients::new(), crate::pause::new())
+            .handle(make_args(&["GETNAME"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Null(), response);
+    }
+
+    #[rstest]
+    #[case::set("name", Some("name"))]
+    #[case::clear("", None)]
+    #[tokio::test]
+    async fn test_handle_setname(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] name: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        state.name = Some("old".into());
+        let response = Client::new(crate::clients::new(), crate::pause::new())
+            .handle(make_args(&["setname", name]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(expected.map(String::from), state.name);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_setname_then_getname_over_stream(
+        databases: crate::store::SharedDatabases,
+        register: crate::commands::SharedRegister,
+    ) -> anyhow::Result<()> {
+        let mut stream = spawn_handler(&databases, &register);
+        assert_eq!(
+            crate::resp::RespType::SimpleString("OK".into()),
+            send(&mut stream, &["SETNAME", "connection"]).await?
+        );
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("connection".into())),
+            send(&mut stream, &["GETNAME"]).await?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_distinct_ids(
+        databases: crate::store::SharedDatabases,
+        register: crate::commands::SharedRegister,
+    ) -> anyhow::Result<()> {
+        let mut first = spawn_handler(&databases, &register);
+        let mut second = spawn_handler(&databases, &register);
+        let first_id = send(&mut first, &["ID"]).await?;
+        let second_id = send(&mut second, &["ID"]).await?;
+        assert!(matches!(first_id, crate::resp::RespType::Integer(_)));
+        assert!(matches!(second_id, crate::resp::RespType::Integer(_)));
+        assert_ne!(first_id, second_id);
+        Ok(())
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_subcommand(&[], "ERR Missing subcommand for 'CLIENT' command")]
+    #[case::unknown_subcommand(&["invalid"], "ERR Unknown subcommand or wrong number of arguments for 'invalid'. Try CLIENT HELP.")]
+    #[case::missing_name(&["SETNAME"], "ERR Missing name for 'CLIENT' command")]
+    #[case::missing_timeout(&["PAUSE"], "ERR Missing timeout for 'CLIENT' command")]
+    #[case::negative_timeout(
+        &["PAUSE", "-1"],
+        "ERR Failed to convert timeout to a non-negative number for 'CLIENT' command"
+    )]
+    #[case::invalid_mode(&["PAUSE", "100", "read"], "ERR read is not a valid mode for 'CLIENT' command")]
+    #[case::name_with_space(
+        &["SETNAME", "a name"],
+        "ERR Client names cannot contain spaces, newlines or special characters for 'CLIENT' command"
+    )]
+    #[case::name_with_newline(
+        &["SETNAME", "a\nname"],
+        "ERR Client names cannot contain spaces, newlines or special characters for 'CLIENT' command"
+    )]
+    #[case::name_with_control_character(
+        &["SETNAME", "a\tname"],
+        "ERR Client names cannot contain spaces, newlines or special characters for 'CLIENT' command"
+    )]
+    #[case::name_with_non_ascii(
+        &["SETNAME", "naïve"],
+        "ERR Client names cannot contain spaces, newlines or special characters for 'CLIENT' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected: &str,
+    ) {
+        let response = Client::new(crate::clients::new(), crate::pause::new())
+            .handle(make_args(args), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        assert_eq!(None, state.name);
+    }
+}
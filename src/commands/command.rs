@@ -0,0 +1,350 @@
+//! This module contains the COMMAND command.
+use anyhow::{Context, Result};
+
+/// The COMMAND subcommands' usages and descriptions.
+const SUBCOMMANDS: [(&str, &str); 3] = [
+    (
+        "COUNT",
+        "Return the total number of commands in this server.",
+    ),
+    (
+        "INFO <command-name> [<command-name> ...]",
+        "Return details about the given commands.",
+    ),
+    ("LIST", "Return a list of all commands in this server."),
+];
+
+/// The COMMAND subcommands, with `All` being COMMAND without a subcommand.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    All,
+    Count,
+    Info(Vec<String>),
+    List,
+}
+
+/// Parses the COMMAND options.
+fn parse_command_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Subcommand> {
+    let mut iter = iter.into_iter();
+
+    let Some(subcommand) = iter.next() else {
+        return Ok(Subcommand::All);
+    };
+    let subcommand =
+        crate::resp::extract_string(&subcommand).context("Failed to extract subcommand")?;
+
+    match subcommand.to_lowercase().as_str() {
+        "count" => Ok(Subcommand::Count),
+        "info" => {
+            let names = iter
+                .map(|name| crate::resp::extract_string(&name).context("Failed to extract name"))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Subcommand::Info(names))
+        }
+        "list" => Ok(Subcommand::List),
+        _ => Err(anyhow::anyhow!("Unknown subcommand '{subcommand}'")),
+    }
+}
+
+/// Makes the details of a command: its name, arity, flags and key positions.
+fn make_info(command: &dyn crate::commands::Command) -> crate::resp::RespType {
+    let (first_key, last_key, step) = command.key_positions();
+    crate::resp::RespType::Array(vec![
+        crate::resp::RespType::BulkString(Some(command.name().to_lowercase().into())),
+        crate::resp::RespType::Integer(command.arity()),
+        crate::resp::RespType::Array(
+            command
+                .flags()
+                .into_iter()
+                .map(|flag| crate::resp::RespType::SimpleString(flag.into()))
+                .collect(),
+        ),
+        crate::resp::RespType::Integer(first_key),
+        crate::resp::RespType::Integer(last_key),
+        crate::resp::RespType::Integer(step),
+    ])
+}
+
+pub struct Command {
+    register: crate::commands::SharedRegister,
+}
+
+impl Command {
+    /// Creates the COMMAND command, describing the commands of the register.
+    pub fn new(register: crate::commands::SharedRegister) -> Self {
+        Self { register }
+    }
+}
+
+#[async_trait::async_trait]
+impl crate::commands::Command for Command {
+    fn static_name() -> String {
+        "COMMAND".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["loading", "stale"]
+    }
+
+    /// Handles the COMMAND command.
+    ///
+    /// Without a subcommand, every command is described. Unknown commands are described by a
+    /// null.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        if let Some(response) = crate::commands::dispatch_subcommand("COMMAND", &args, &SUBCOMMANDS)
+        {
+            return response;
+        }
+        let subcommand = match parse_command_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'COMMAND' command"
+                ));
+            }
+        };
+
+        let register = self.register.read().await;
+        match subcommand {
+            Subcommand::All => crate::resp::RespType::Array(
+                register.commands().into_iter().map(make_info).collect(),
+            ),
+            Subcommand::Count => crate::resp::RespType::Integer(register.commands().len() as i64),
+            Subcommand::Info(names) => crate::resp::RespType::Array(
+                names
+                    .iter()
+                    .map(|name| match register.get(name) {
+                        Some(command) => make_info(command),
+                        None => crate::resp::RespType::Null(),
+                    })
+                    .collect(),
+            ),
+            Subcommand::List => crate::resp::RespType::Array(
+                register
+                    .commands()
+                    .into_iter()
+                    .map(|command| {
+                        crate::resp::RespType::BulkString(Some(
+                            command.name().to_lowercase().into(),
+                        ))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::Command as _;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn command() -> Command {
+        let mut register = crate::commands::Register::new();
+        register.register_multiple(vec![
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::set::Set),
+            Box::new(crate::commands::sinter::Sinter),
+        ]);
+        Command::new(std::sync::Arc::new(tokio::sync::RwLock::new(register)))
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    fn make_expected(
+        name: &str,
+        arity: i64,
+        flags: &[&str],
+        key_positions: [i64; 3],
+    ) -> crate::resp::RespType {
+        let mut info = vec![
+            crate::resp::RespType::BulkString(Some(name.to_string().into())),
+            crate::resp::RespType::Integer(arity),
+            crate::resp::RespType::Array(
+                flags
+                    .iter()
+                    .map(|flag| crate::resp::RespType::SimpleString(flag.to_string()))
+                    .collect(),
+            ),
+        ];
+        info.extend(key_positions.map(crate::resp::RespType::Integer));
+        crate::resp::RespType::Array(info)
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("COMMAND", Command::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_all(
+        command: Command,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = command.handle(vec![], &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::Array(vec![
+                make_expected("get", 2, &["readonly", "fast"], [1, 1, 1]),
+                make_expected("set", -3, &["write", "denyoom"], [1, 1, 1]),
+                make_expected("sinter", -2, &["readonly"], [1, -1, 1]),
+            ]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[case::upper("COUNT")]
+    #[case::lower("count")]
+    #[tokio::test]
+    async fn test_handle_count(
+        command: Command,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] subcommand: &str,
+    ) {
+        let response = command
+            .handle(make_args(&[subcommand]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(3), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_list(
+        command: Command,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = command
+            .handle(make_args(&["LIST"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some("get".into())),
+                crate::resp::RespType::BulkString(Some("set".into())),
+                crate::resp::RespType::BulkString(Some("sinter".into())),
+            ]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_info(
+        command: Command,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = command
+            .handle(
+                make_args(&["INFO", "get", "SET", "sinter"]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Array(vec![
+                make_expected("get", 2, &["readonly", "fast"], [1, 1, 1]),
+                make_expected("set", -3, &["write", "denyoom"], [1, 1, 1]),
+                make_expected("sinter", -2, &["readonly"], [1, -1, 1]),
+            ]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_info_unknown_command(
+        command: Command,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = command
+            .handle(make_args(&["info", "get", "unknown"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Array(vec![
+                make_expected("get", 2, &["readonly", "fast"], [1, 1, 1]),
+                crate::resp::RespType::Null(),
+            ]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_info_without_names(
+        command: Command,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = command
+            .handle(make_args(&["INFO"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Array(vec![]), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::invalid_subcommand(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract subcommand for 'COMMAND' command"
+    )]
+    #[case::unknown_subcommand(
+        make_args(&["invalid"]),
+        "ERR Unknown subcommand or wrong number of arguments for 'invalid'. Try COMMAND HELP."
+    )]
+    #[case::invalid_name(
+        vec![
+            crate::resp::RespType::BulkString(Some("INFO".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract name for 'COMMAND' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        command: Command,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = command.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
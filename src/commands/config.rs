@@ -0,0 +1,606 @@
+//! This module contains the CONFIG command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The CONFIG subcommands' usages and descriptions.
+const SUBCOMMANDS: [(&str, &str); 4] = [
+    (
+        "GET <pattern> [<pattern> ...]",
+        "Return the parameters matching the glob-like patterns and their values.",
+    ),
+    ("RESETSTAT", "Reset the statistics reported by INFO."),
+    (
+        "REWRITE",
+        "Rewrite the configuration file with the current configuration.",
+    ),
+    (
+        "SET <parameter> <value> [<parameter> <value> ...]",
+        "Set the parameters to the values.",
+    ),
+];
+
+/// The CONFIG subcommands.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Get(Vec<String>),
+    ResetStat,
+    Rewrite,
+    Set(Vec<(String, String)>),
+}
+
+/// Parses the CONFIG options.
+fn parse_config_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Subcommand> {
+    let mut iter = iter.into_iter();
+
+    let subcommand = crate::resp::extract_string(&iter.next().context("Missing subcommand")?)
+        .context("Failed to extract subcommand")?;
+
+    match subcommand.to_lowercase().as_str() {
+        "get" => {
+            let mut patterns = vec![];
+            for token in iter {
+                patterns.push(
+                    crate::resp::extract_string(&token).context("Failed to extract parameter")?,
+                );
+            }
+            if patterns.is_empty() {
+                return Err(anyhow::anyhow!("At least one parameter must be provided"));
+            }
+            Ok(Subcommand::Get(patterns))
+        }
+        "resetstat" => Ok(Subcommand::ResetStat),
+        "rewrite" => Ok(Subcommand::Rewrite),
+        "set" => {
+            let mut pairs = vec![];
+            while let Some(token) = iter.next() {
+                let name =
+                    crate::resp::extract_string(&token).context("Failed to extract parameter")?;
+                let value = crate::resp::extract_string(
+                    &iter
+                        .next()
+                        .context(format!("Missing value for parameter '{name}'"))?,
+                )
+                .context("Failed to extract value")?;
+                pairs.push((name, value));
+            }
+            if pairs.is_empty() {
+                return Err(anyhow::anyhow!("At least one parameter must be provided"));
+            }
+            Ok(Subcommand::Set(pairs))
+        }
+        _ => Err(anyhow::anyhow!("Unknown subcommand '{subcommand}'")),
+    }
+}
+
+pub struct Config {
+    config: crate::config::SharedConfig,
+    databases: crate::store::SharedDatabases,
+    latency: crate::latency::SharedLatency,
+    stats: crate::stats::SharedStats,
+    acl: crate::acl::SharedAcl,
+}
+
+impl Config {
+    /// Creates the CONFIG command backed by the shared configuration.
+    ///
+    /// Memory settings are applied to every database, the latency threshold to the latency
+    /// monitor and `requirepass` to the default user. The statistics are cleared by RESETSTAT.
+    pub fn new(
+        config: crate::config::SharedConfig,
+        databases: crate::store::SharedDatabases,
+        latency: crate::latency::SharedLatency,
+        stats: crate::stats::SharedStats,
+        acl: crate::acl::SharedAcl,
+    ) -> Self {
+        Self {
+            config,
+            databases,
+            latency,
+            stats,
+            acl,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Config {
+    fn static_name() -> String {
+        "CONFIG".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["admin", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the CONFIG command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        if let Some(response) = crate::commands::dispatch_subcommand("CONFIG", &args, &SUBCOMMANDS)
+        {
+            return response;
+        }
+        let subcommand = match parse_config_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'CONFIG' command"
+                ));
+            }
+        };
+
+        match subcommand {
+            Subcommand::Get(patterns) => {
+                let config = self.config.read().await;
+                let mut result: Vec<(String, String)> = vec![];
+                for pattern in patterns {
+                    for (name, value) in config.get_matching(pattern) {
+                        if !result.iter().any(|(existing, _)| *existing == name) {
+                            result.push((name, value));
+                        }
+                    }
+                }
+
+                crate::resp::RespType::Map(
+                    result
+                        .into_iter()
+                        .map(|(name, value)| {
+                            (
+                                crate::resp::RespType::BulkString(Some(name.into())),
+                                crate::resp::RespType::BulkString(Some(value.into())),
+                            )
+                        })
+                        .collect(),
+                )
+            }
+            Subcommand::ResetStat => {
+                self.stats.reset();
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+            Subcommand::Rewrite => match self.config.read().await.rewrite() {
+                Ok(()) => crate::resp::RespType::SimpleString("OK".into()),
+                Err(err) => {
+                    log::error!("{err:#}");
+                    crate::resp::RespType::SimpleError(format!("ERR {err:#}"))
+                }
+            },
+            Subcommand::Set(pairs) => {
+                let mut config = self.config.write().await;
+                let mut updated = config.clone();
+                for (name, value) in pairs {
+                    if crate::config::is_immutable(&name) {
+                        return crate::resp::RespType::SimpleError(format!(
+                            "ERR CONFIG SET failed (possibly related to argument '{name}') - can't set immutable config"
+                        ));
+                    }
+                    if let Err(err) = updated.set(name, value) {
+                        log::error!("{err}");
+                        return crate::resp::RespType::SimpleError(format!(
+                            "ERR {err} for 'CONFIG' command"
+                        ));
+                    }
+                }
+                // The databases share their budget, so setting it through one sets it for all.
+                if let Some(store) = self.databases.first() {
+                    store
+                        .read()
+                        .await
+                        .set_eviction(updated.maxmemory, updated.maxmemory_policy);
+                }
+                self.latency
+                    .set_threshold(updated.latency_monitor_threshold);
+                if updated.requirepass != config.requirepass {
+                    self.acl.set_default_password(&updated.requirepass);
+                }
+                *config = updated;
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn config() -> crate::config::SharedConfig {
+        crate::config::new()
+    }
+
+    #[fixture]
+    fn databases() -> crate::store::SharedDatabases {
+        crate::store::new_databases(2)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    fn make_map(pairs: &[(&str, &str)]) -> crate::resp::RespType {
+        crate::resp::RespType::Map(
+            pairs
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        crate::resp::RespType::BulkString(Some(name.to_string().into())),
+                        crate::resp::RespType::BulkString(Some(value.to_string().into())),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("CONFIG", Config::static_name());
+    }
+
+    #[rstest]
+    #[case::known(&["GET", "maxmemory"], &[("maxmemory", "0")])]
+    #[case::glob(&["get", "maxmemory*"], &[("maxmemory", "0"), ("maxmemory-policy", "noeviction")])]
+    #[case::multiple(&["GET", "save", "maxmemory"], &[("save", "3600 1 300 100 60 10000"), ("maxmemory", "0")])]
+    #[case::overlapping(&["GET", "maxmemory", "maxmemory*"], &[("maxmemory", "0"), ("maxmemory-policy", "noeviction")])]
+    #[case::unknown(&["GET", "unknown"], &[])]
+    #[tokio::test]
+    async fn test_handle_get(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+        #[case] args: &[&str],
+        #[case] expected: &[(&str, &str)],
+    ) {
+        let response = Config::new(
+            config,
+            databases(),
+            crate::latency::new(),
+            crate::stats::new(),
+            crate::acl::new(),
+        )
+        .handle(make_args(args), &store, &mut state)
+        .await;
+        assert_eq!(make_map(expected), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_set(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+    ) {
+        let command = Config::new(
+            config.clone(),
+            databases(),
+            crate::latency::new(),
+            crate::stats::new(),
+            crate::acl::new(),
+        );
+        let response = command
+            .handle(
+                make_args(&["SET", "maxmemory", "100", "maxmemory-policy", "allkeys-lru"]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+
+        let response = command
+            .handle(make_args(&["GET", "maxmemory*"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            make_map(&[("maxmemory", "100"), ("maxmemory-policy", "allkeys-lru")]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_set_invalid_is_atomic(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+    ) {
+        let response = Config::new(
+            config.clone(),
+            databases(),
+            crate::latency::new(),
+            crate::stats::new(),
+            crate::acl::new(),
+        )
+        .handle(
+            make_args(&["SET", "save", "", "maxmemory", "abc"]),
+            &store,
+            &mut state,
+        )
+        .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR Invalid value for 'maxmemory': abc for 'CONFIG' command".into()
+            ),
+            response
+        );
+        assert_eq!(crate::config::Config::new(), *config.read().await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_set_immutable(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+    ) {
+        let response = Config::new(
+            config.clone(),
+            databases(),
+            crate::latency::new(),
+            crate::stats::new(),
+            crate::acl::new(),
+        )
+        .handle(
+            make_args(&["SET", "save", "", "databases", "1"]),
+            &store,
+            &mut state,
+        )
+        .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR CONFIG SET failed (possibly related to argument 'databases') - can't set immutable config".into()
+            ),
+            response
+        );
+        assert_eq!(crate::config::Config::new(), *config.read().await);
+    }
+
+    #[rstest]
+    #[case::noeviction("noeviction")]
+    #[case::allkeys_lru("allkeys-lru")]
+    #[tokio::test]
+    async fn test_handle_set_maxmemory_applies_to_databases(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+        databases: crate::store::SharedDatabases,
+        #[case] policy: &str,
+    ) {
+        let response = Config::new(
+            config,
+            databases.clone(),
+            crate::latency::new(),
+            crate::stats::new(),
+            crate::acl::new(),
+        )
+        .handle(
+            make_args(&["SET", "maxmemory", "1", "maxmemory-policy", policy]),
+            &store,
+            &mut state,
+        )
+        .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+
+        databases[0]
+            .write()
+            .await
+            .insert("key".into(), crate::store::Entry::new_string("value"));
+        for store in databases.iter() {
+            assert!(store.read().await.is_over_budget());
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_set_latency_monitor_threshold(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+    ) {
+        let latency = crate::latency::new();
+        let response = Config::new(
+            config,
+            databases(),
+            latency.clone(),
+            crate::stats::new(),
+            crate::acl::new(),
+        )
+        .handle(
+            make_args(&["SET", "latency-monitor-threshold", "100"]),
+            &store,
+            &mut state,
+        )
+        .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+
+        latency.record("command", std::time::Duration::from_millis(100));
+        assert_eq!(1, latency.history("command").len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_set_requirepass(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+    ) {
+        let acl = crate::acl::new();
+        let response = Config::new(
+            config,
+            databases(),
+            crate::latency::new(),
+            crate::stats::new(),
+            acl.clone(),
+        )
+        .handle(
+            make_args(&["SET", "requirepass", "secret"]),
+            &store,
+            &mut state,
+        )
+        .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert!(acl.requires_authentication());
+        assert!(acl.authenticate("default", "secret").is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_resetstat(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+    ) {
+        let stats = crate::stats::new();
+        stats.record_connection();
+        stats.record_command("get", std::time::Duration::ZERO, false);
+
+        let response = Config::new(
+            config,
+            databases(),
+            crate::latency::new(),
+            stats.clone(),
+            crate::acl::new(),
+        )
+        .handle(make_args(&["RESETSTAT"]), &store, &mut state)
+        .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(0, stats.total_connections_received());
+        assert_eq!(0, stats.total_commands_processed());
+        assert!(stats.all_command_stats().is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_rewrite(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+    ) {
+        let path = std::env::temp_dir().join(format!(
+            "redis-rs-config-command-{}-rewrite.conf",
+            std::process::id()
+        ));
+        std::fs::write(&path, "# Settings\nmaxmemory 100\n").unwrap();
+        config.write().await.config_file = Some(path.clone());
+
+        let command = Config::new(
+            config,
+            databases(),
+            crate::latency::new(),
+            crate::stats::new(),
+            crate::acl::new(),
+        );
+        let response = command
+            .handle(make_args(&["SET", "maxmemory", "200"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        let response = command
+            .handle(make_args(&["REWRITE"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(
+            "# Settings\nmaxmemory 200\n",
+            std::fs::read_to_string(&path).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_rewrite_without_file(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+    ) {
+        let response = Config::new(
+            config,
+            databases(),
+            crate::latency::new(),
+            crate::stats::new(),
+            crate::acl::new(),
+        )
+        .handle(make_args(&["REWRITE"]), &store, &mut state)
+        .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR The server is running without a config file".into()
+            ),
+            response
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_subcommand(vec![], "ERR Missing subcommand for 'CONFIG' command")]
+    #[case::invalid_subcommand(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract subcommand for 'CONFIG' command"
+    )]
+    #[case::unknown_subcommand(make_args(&["invalid"]), "ERR Unknown subcommand or wrong number of arguments for 'invalid'. Try CONFIG HELP.")]
+    #[case::get_missing_parameter(
+        make_args(&["GET"]),
+        "ERR At least one parameter must be provided for 'CONFIG' command"
+    )]
+    #[case::get_invalid_parameter(
+        vec![
+            crate::resp::RespType::BulkString(Some("GET".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract parameter for 'CONFIG' command"
+    )]
+    #[case::set_missing_parameter(
+        make_args(&["SET"]),
+        "ERR At least one parameter must be provided for 'CONFIG' command"
+    )]
+    #[case::set_missing_value(
+        make_args(&["SET", "maxmemory"]),
+        "ERR Missing value for parameter 'maxmemory' for 'CONFIG' command"
+    )]
+    #[case::set_unknown_parameter(
+        make_args(&["SET", "unknown", "value"]),
+        "ERR Unknown parameter 'unknown' for 'CONFIG' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        config: crate::config::SharedConfig,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Config::new(
+            config,
+            databases(),
+            crate::latency::new(),
+            crate::stats::new(),
+            crate::acl::new(),
+        )
+        .handle(args, &store, &mut state)
+        .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,439 @@
+//! This module contains the COPY command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The COPY options.
+#[derive(Debug, PartialEq)]
+struct CopyOptions {
+    source: String,
+    destination: String,
+    /// The index of the destination database, defaulting to the selected database.
+    db: Option<usize>,
+    replace: bool,
+}
+
+/// Parses the COPY options.
+fn parse_copy_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<CopyOptions> {
+    let mut iter = iter.into_iter();
+
+    let source = crate::resp::extract_string(&iter.next().context("Missing source")?)
+        .context("Failed to extract source")?;
+    let destination = crate::resp::extract_string(&iter.next().context("Missing destination")?)
+        .context("Failed to extract destination")?;
+
+    let mut options = CopyOptions {
+        source,
+        destination,
+        db: None,
+        replace: false,
+    };
+    while let Some(token) = &iter.next() {
+        let option = crate::resp::extract_string(token).context("Failed to extract option")?;
+
+        match option.to_lowercase().as_str() {
+            "db" => {
+                let db = crate::resp::extract_string(&iter.next().context("Missing db")?)
+                    .context("Failed to extract db")?
+                    .parse::<usize>()
+                    .context("Failed to convert db to a number")?;
+                options.db = Some(db);
+            }
+            "replace" => options.replace = true,
+            _ => return Err(anyhow::anyhow!("{option} is not a valid option")),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Inserts a copy of the source entry at the destination.
+///
+/// Returns 1 if the entry was copied, or 0 if there is no source entry or the destination key
+/// exists and is not replaced.
+fn insert_copy(
+    store: &mut crate::store::Store,
+    destination: String,
+    entry: Option<crate::store::Entry>,
+    replace: bool,
+) -> crate::resp::RespType {
+    let Some(entry) = entry else {
+        return crate::resp::RespType::Integer(0);
+    };
+    if !replace && store.peek(&destination).is_some() {
+        return crate::resp::RespType::Integer(0);
+    }
+    store.insert(
+        destination,
+        crate::store::Entry {
+            last_access: crate::store::LastAccess::now(),
+            ..entry
+        },
+    );
+    crate::resp::RespType::Integer(1)
+}
+
+pub struct Copy {
+    databases: crate::store::SharedDatabases,
+}
+
+impl Copy {
+    /// Creates the COPY command for the databases.
+    pub fn new(databases: crate::store::SharedDatabases) -> Self {
+        Self { databases }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Copy {
+    fn static_name() -> String {
+        "COPY".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "denyoom"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 2, 1)
+    }
+
+    /// Handles the COPY command.
+    ///
+    /// The value and the time to live of the source are copied, and later changes to either key do
+    /// not affect the other. When copying into another
+    /// database, both databases are locked in index order so that concurrent copies cannot
+    /// deadlock.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let options = match parse_copy_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'COPY' command"));
+            }
+        };
+
+        let db = options.db.unwrap_or(state.db);
+        if db >= self.databases.len() {
+            return crate::resp::RespType::SimpleError("ERR DB index is out of range".into());
+        }
+
+        if db == state.db {
+            if options.source == options.destination {
+                return crate::resp::RespType::SimpleError(
+                    "ERR source and destination objects are the same".into(),
+                );
+            }
+            let mut store = store.write().await;
+            let entry = store.get(&options.source).cloned();
+            return insert_copy(&mut store, options.destination, entry, options.replace);
+        }
+
+        let (low, high) = (state.db.min(db), state.db.max(db));
+        let mut low = self.databases[low].write().await;
+        let mut high = self.databases[high].write().await;
+        let (source_store, destination_store) = if state.db < db {
+            (&mut low, &mut high)
+        } else {
+            (&mut high, &mut low)
+        };
+        let entry = source_store.get(&options.source).cloned();
+        insert_copy(
+            destination_store,
+            options.destination,
+            entry,
+            options.replace,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn databases() -> crate::store::SharedDatabases {
+        crate::store::new_databases(3)
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("COPY", Copy::static_name());
+    }
+
+    #[rstest]
+    #[case::without_db(&["source", "destination"])]
+    #[case::current_db(&["source", "destination", "DB", "0"])]
+    #[tokio::test]
+    async fn test_handle(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+    ) {
+        tokio::time::pause();
+        let entry = crate::store::Entry::new_string("value").with_deletion(100_u64);
+        databases[0]
+            .write()
+            .await
+            .insert("source".into(), entry.clone());
+
+        let response = Copy::new(databases.clone())
+            .handle(make_args(args), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+
+        let store = databases[0].read().await;
+        assert_eq!(Some(&entry), store.peek("source"));
+        assert_eq!(Some(&entry), store.peek("destination"));
+    }
+
+    #[rstest]
+    #[case::lower_db(2, "1")]
+    #[case::higher_db(0, "2")]
+    #[tokio::test]
+    async fn test_handle_other_db(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] source_db: usize,
+        #[case] destination_db: &str,
+    ) {
+        state.db = source_db;
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.push("element".into());
+        }
+        databases[source_db]
+            .write()
+            .await
+            .insert("source".into(), entry.clone());
+
+        let response = Copy::new(databases.clone())
+            .handle(
+                make_args(&["source", "destination", "DB", destination_db]),
+                &databases[source_db],
+                &mut state,
+            )
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+
+        let source_store = databases[source_db].read().await;
+        assert_eq!(Some(&entry), source_store.peek("source"));
+        assert_eq!(None, source_store.peek("destination"));
+        let destination_db = destination_db.parse::<usize>().unwrap();
+        let destination_store = databases[destination_db].read().await;
+        assert_eq!(Some(&entry), destination_store.peek("destination"));
+        assert_eq!(None, destination_store.peek("source"));
+    }
+
+    #[rstest]
+    #[case::current_db(&["source", "destination"], 0)]
+    #[case::other_db(&["source", "destination", "DB", "1"], 1)]
+    #[tokio::test]
+    async fn test_handle_independent_copy(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] destination_db: usize,
+    ) {
+        let mut entry = crate::store::Entry::new_hash();
+        if let crate::store::EntryValue::Hash(hash) = &mut entry.value {
+            hash.insert("field".into(), "value".into());
+        }
+        databases[0]
+            .write()
+            .await
+            .insert("source".into(), entry.clone());
+
+        let response = Copy::new(databases.clone())
+            .handle(make_args(args), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+
+        {
+            let mut destination_store = databases[destination_db].write().await;
+            let destination = destination_store.get_entry("destination").unwrap();
+            let crate::store::EntryValue::Hash(hash) = &mut destination.value else {
+                panic!("Unexpected value: {:?}.", destination.value);
+            };
+            hash.insert("field".into(), "changed".into());
+            hash.insert("other".into(), "value".into());
+        }
+        assert_eq!(Some(&entry), databases[0].read().await.peek("source"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_same_key_other_db(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+    ) {
+        let entry = crate::store::Entry::new_string("value");
+        databases[0]
+            .write()
+            .await
+            .insert("key".into(), entry.clone());
+
+        let response = Copy::new(databases.clone())
+            .handle(
+                make_args(&["key", "key", "db", "1"]),
+                &databases[0],
+                &mut state,
+            )
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+        assert_eq!(Some(&entry), databases[1].read().await.peek("key"));
+    }
+
+    #[rstest]
+    #[case::same_db(&["source", "destination"], 0)]
+    #[case::other_db(&["source", "destination", "DB", "1"], 1)]
+    #[tokio::test]
+    async fn test_handle_existing_destination(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] destination_db: usize,
+    ) {
+        databases[0].write().await.insert(
+            "source".into(),
+            crate::store::Entry::new_string("new value"),
+        );
+        databases[destination_db].write().await.insert(
+            "destination".into(),
+            crate::store::Entry::new_string("old value"),
+        );
+
+        let command = Copy::new(databases.clone());
+        let response = command
+            .handle(make_args(args), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+        assert_eq!(
+            Some(&crate::store::Entry::new_string("old value")),
+            databases[destination_db].read().await.peek("destination")
+        );
+
+        let args = [args, &["REPLACE"]].concat();
+        let response = command
+            .handle(make_args(&args), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+        assert_eq!(
+            Some(&crate::store::Entry::new_string("new value")),
+            databases[destination_db].read().await.peek("destination")
+        );
+    }
+
+    #[rstest]
+    #[case::same_db(&["source", "destination"], 0)]
+    #[case::other_db(&["source", "destination", "DB", "1"], 1)]
+    #[tokio::test]
+    async fn test_handle_missing_source(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] destination_db: usize,
+    ) {
+        let response = Copy::new(databases.clone())
+            .handle(make_args(args), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+        assert_eq!(0, databases[destination_db].read().await.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_expired_source(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+    ) {
+        tokio::time::pause();
+        databases[0].write().await.insert(
+            "source".into(),
+            crate::store::Entry::new_string("value").with_deletion(100_u64),
+        );
+        tokio::time::advance(tokio::time::Duration::from_millis(100)).await;
+
+        let response = Copy::new(databases.clone())
+            .handle(
+                make_args(&["source", "destination", "DB", "1"]),
+                &databases[0],
+                &mut state,
+            )
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+        assert_eq!(0, databases[1].read().await.len());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_source(vec![], "ERR Missing source for 'COPY' command")]
+    #[case::invalid_source(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract source for 'COPY' command"
+    )]
+    #[case::missing_destination(make_args(&["source"]), "ERR Missing destination for 'COPY' command")]
+    #[case::unknown_option(
+        make_args(&["source", "destination", "invalid"]),
+        "ERR invalid is not a valid option for 'COPY' command"
+    )]
+    #[case::missing_db(make_args(&["source", "destination", "DB"]), "ERR Missing db for 'COPY' command")]
+    #[case::non_numeric_db(
+        make_args(&["source", "destination", "DB", "abc"]),
+        "ERR Failed to convert db to a number for 'COPY' command"
+    )]
+    #[case::db_out_of_range(
+        make_args(&["source", "destination", "DB", "3"]),
+        "ERR DB index is out of range"
+    )]
+    #[case::same_key(make_args(&["source", "source"]), "ERR source and destination objects are the same")]
+    #[case::same_key_current_db(
+        make_args(&["source", "source", "DB", "0"]),
+        "ERR source and destination objects are the same"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        databases[0]
+            .write()
+            .await
+            .insert("source".into(), crate::store::Entry::new_string("value"));
+
+        let response = Copy::new(databases.clone())
+            .handle(args, &databases[0], &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        assert_eq!(1, databases[0].read().await.len());
+    }
+}
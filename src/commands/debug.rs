@@ -0,0 +1,368 @@
+//! This module contains the DEBUG command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The DEBUG subcommands' usages and descriptions.
+const SUBCOMMANDS: [(&str, &str); 5] = [
+    (
+        "CHANGE-REPL-ID",
+        "Change the replication IDs of the instance. Does nothing, as replication is not supported.",
+    ),
+    (
+        "JMAP",
+        "Dump the memory map of the allocator. Does nothing, kept for compatibility.",
+    ),
+    ("OBJECT <key>", "Show low level information about the key."),
+    (
+        "SET-ACTIVE-EXPIRE <0|1>",
+        "Disable or enable the active expiration of keys in the background.",
+    ),
+    (
+        "SLEEP <seconds>",
+        "Stop the server for the number of seconds.",
+    ),
+];
+
+/// The DEBUG subcommands, where `NoOp` is any subcommand that only replies OK.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    NoOp,
+    Object(String),
+    SetActiveExpire(bool),
+    Sleep(f64),
+}
+
+/// Parses the DEBUG options.
+fn parse_debug_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Subcommand> {
+    let mut iter = iter.into_iter();
+
+    let subcommand = crate::resp::extract_string(&iter.next().context("Missing subcommand")?)
+        .context("Failed to extract subcommand")?;
+
+    match subcommand.to_lowercase().as_str() {
+        "change-repl-id" | "jmap" => Ok(Subcommand::NoOp),
+        "object" => {
+            let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+                .context("Failed to extract key")?;
+            Ok(Subcommand::Object(key))
+        }
+        "set-active-expire" => {
+            let enabled = match crate::resp::extract_string(&iter.next().context("Missing flag")?)
+                .context("Failed to extract flag")?
+                .as_str()
+            {
+                "0" => false,
+                "1" => true,
+                _ => return Err(anyhow::anyhow!("Failed to convert flag to 0 or 1")),
+            };
+            Ok(Subcommand::SetActiveExpire(enabled))
+        }
+        "sleep" => {
+            let seconds = crate::resp::extract_string(&iter.next().context("Missing seconds")?)
+                .context("Failed to extract seconds")?
+                .parse::<f64>()
+                .ok()
+                .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                .context("Failed to convert seconds to a non-negative number")?;
+            Ok(Subcommand::Sleep(seconds))
+        }
+        _ => Err(anyhow::anyhow!("Unknown subcommand '{subcommand}'")),
+    }
+}
+
+pub struct Debug {
+    active_expire: crate::store::SharedActiveExpire,
+    config: crate::config::SharedConfig,
+}
+
+impl Debug {
+    /// Creates the DEBUG command, able to toggle the active expiration flag and reporting list
+    /// encodings according to the configuration.
+    pub fn new(
+        active_expire: crate::store::SharedActiveExpire,
+        config: crate::config::SharedConfig,
+    ) -> Self {
+        Self {
+            active_expire,
+            config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Debug {
+    fn static_name() -> String {
+        "DEBUG".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["admin", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the DEBUG command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        if let Some(response) = crate::commands::dispatch_subcommand("DEBUG", &args, &SUBCOMMANDS) {
+            return response;
+        }
+        let subcommand = match parse_debug_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'DEBUG' command"
+                ));
+            }
+        };
+
+        match subcommand {
+            Subcommand::NoOp => crate::resp::RespType::SimpleString("OK".into()),
+            Subcommand::Object(key) => {
+                let list_max_listpack_size = self.config.read().await.list_max_listpack_size;
+                match store.read().await.get(&key) {
+                    Some(entry) => crate::resp::RespType::SimpleString(format!(
+                        "refcount:1 encoding:{} serializedlength:{}",
+                        crate::commands::object::encoding(&entry.value, list_max_listpack_size),
+                        entry.approximate_size(&key) - key.len() as u64
+                    )),
+                    None => crate::resp::RespType::SimpleError("ERR no such key".into()),
+                }
+            }
+            Subcommand::SetActiveExpire(enabled) => {
+                self.active_expire
+                    .store(enabled, std::sync::atomic::Ordering::Relaxed);
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+            Subcommand::Sleep(seconds) => {
+                tokio::time::sleep(tokio::time::Duration::from_secs_f64(seconds)).await;
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn debug() -> Debug {
+        Debug::new(crate::store::new_active_expire(), crate::config::new())
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("DEBUG", Debug::static_name());
+    }
+
+    #[rstest]
+    #[case::whole("2", 2000)]
+    #[case::fraction("0.5", 500)]
+    #[case::zero("0", 0)]
+    #[tokio::test]
+    async fn test_handle_sleep(
+        debug: Debug,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] seconds: &str,
+        #[case] expected_ms: u64,
+    ) {
+        tokio::time::pause();
+        let start = tokio::time::Instant::now();
+        let response = debug
+            .handle(make_args(&["SLEEP", seconds]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        // The paused clock advances to the timer's millisecond resolution.
+        let expected = tokio::time::Duration::from_millis(expected_ms);
+        let elapsed = start.elapsed();
+        assert!(expected <= elapsed && elapsed <= expected + tokio::time::Duration::from_millis(1));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_sleep_blocks_until_elapsed(
+        debug: Debug,
+        store: crate::store::SharedStore,
+    ) {
+        tokio::time::pause();
+        let task = tokio::spawn(async move {
+            let mut state = crate::state::State::new(0);
+            debug
+                .handle(make_args(&["sleep", "1"]), &store, &mut state)
+                .await
+        });
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(999)).await;
+        assert!(!task.is_finished());
+        assert_eq!(
+            crate::resp::RespType::SimpleString("OK".into()),
+            task.await.unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::string(
+        crate::store::Entry::new_string("value"),
+        "refcount:1 encoding:embstr serializedlength:5"
+    )]
+    #[case::int(
+        crate::store::Entry::new_string("123"),
+        "refcount:1 encoding:int serializedlength:3"
+    )]
+    #[case::list(
+        crate::store::Entry::new_list(),
+        "refcount:1 encoding:listpack serializedlength:0"
+    )]
+    #[tokio::test]
+    async fn test_handle_object(
+        debug: Debug,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] entry: crate::store::Entry,
+        #[case] expected: &str,
+    ) {
+        store.write().await.insert(key.clone(), entry);
+        let response = debug
+            .handle(make_args(&["OBJECT", &key]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleString(expected.into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[case::disable("0", false)]
+    #[case::enable("1", true)]
+    #[tokio::test]
+    async fn test_handle_set_active_expire(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] flag: &str,
+        #[case] expected: bool,
+    ) {
+        let active_expire = crate::store::new_active_expire();
+        active_expire.store(!expected, std::sync::atomic::Ordering::Relaxed);
+        let response = Debug::new(active_expire.clone(), crate::config::new())
+            .handle(make_args(&["SET-ACTIVE-EXPIRE", flag]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(
+            expected,
+            active_expire.load(std::sync::atomic::Ordering::Relaxed)
+        );
+    }
+
+    #[rstest]
+    #[case::change_repl_id("CHANGE-REPL-ID")]
+    #[case::jmap("jmap")]
+    #[tokio::test]
+    async fn test_handle_no_op(
+        debug: Debug,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] subcommand: &str,
+    ) {
+        let response = debug
+            .handle(make_args(&[subcommand]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_object_missing_key(
+        debug: Debug,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = debug
+            .handle(make_args(&["OBJECT", &key]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR no such key".into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[case::missing_subcommand(vec![], "ERR Missing subcommand for 'DEBUG' command")]
+    #[case::invalid_subcommand(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract subcommand for 'DEBUG' command"
+    )]
+    #[case::unknown_subcommand(
+        make_args(&["invalid"]),
+        "ERR Unknown subcommand or wrong number of arguments for 'invalid'. Try DEBUG HELP."
+    )]
+    #[case::missing_key(make_args(&["OBJECT"]), "ERR Missing key for 'DEBUG' command")]
+    #[case::missing_flag(
+        make_args(&["SET-ACTIVE-EXPIRE"]),
+        "ERR Missing flag for 'DEBUG' command"
+    )]
+    #[case::invalid_flag(
+        make_args(&["SET-ACTIVE-EXPIRE", "2"]),
+        "ERR Failed to convert flag to 0 or 1 for 'DEBUG' command"
+    )]
+    #[case::missing_seconds(make_args(&["SLEEP"]), "ERR Missing seconds for 'DEBUG' command")]
+    #[case::non_numeric_seconds(
+        make_args(&["SLEEP", "abc"]),
+        "ERR Failed to convert seconds to a non-negative number for 'DEBUG' command"
+    )]
+    #[case::negative_seconds(
+        make_args(&["SLEEP", "-1"]),
+        "ERR Failed to convert seconds to a non-negative number for 'DEBUG' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        debug: Debug,
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = debug.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! This module contains the DEL command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the DEL options.
+fn parse_del_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Vec<String>> {
+    let keys = iter
+        .into_iter()
+        .map(|key| crate::resp::extract_string(&key).context("Failed to extract key"))
+        .collect::<Result<Vec<_>>>()?;
+    if keys.is_empty() {
+        return Err(anyhow::anyhow!("Missing key"));
+    }
+
+    Ok(keys)
+}
+
+pub struct Del;
+
+#[async_trait::async_trait]
+impl Command for Del {
+    fn static_name() -> String {
+        "DEL".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, -1, 1)
+    }
+
+    /// Handles the DEL command.
+    ///
+    /// Expired keys are not counted as deleted.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let keys = match parse_del_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'DEL' command"));
+            }
+        };
+
+        let mut store = store.write().await;
+        let count = keys
+            .iter()
+            .filter(|key| store.remove(key).is_some())
+            .count();
+
+        crate::resp::RespType::Integer(count as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(keys: &[&str]) -> Vec<crate::resp::RespType> {
+        keys.iter()
+            .map(|key| crate::resp::RespType::BulkString(Some(key.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("DEL", Del::static_name());
+    }
+
+    #[rstest]
+    #[case::single(&["a"], 1, &["b", "c"])]
+    #[case::multiple(&["a", "c"], 2, &["b"])]
+    #[case::missing(&["d"], 0, &["a", "b", "c"])]
+    #[case::mixed(&["a", "d", "a"], 1, &["b", "c"])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] keys: &[&str],
+        #[case] expected_removed: i64,
+        #[case] expected: &[&str],
+    ) {
+        for key in ["a", "b", "c"] {
+            store
+                .write()
+                .await
+                .insert(key.into(), crate::store::Entry::new_string("value"));
+        }
+        let response = Del.handle(make_args(keys), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(expected_removed), response);
+
+        let mut remaining = store
+            .write()
+            .await
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        remaining.sort_unstable();
+        assert_eq!(expected, remaining);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_expired(store: crate::store::SharedStore, mut state: crate::state::State) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.write().await.insert(
+            "key".into(),
+            crate::store::Entry::new_string("value").with_deletion(duration),
+        );
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        let response = Del.handle(make_args(&["key"]), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'DEL' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'DEL' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Del.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
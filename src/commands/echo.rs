@@ -5,10 +5,18 @@ pub struct Echo;
 
 #[async_trait::async_trait]
 impl Command for Echo {
-    fn name(&self) -> String {
+    fn static_name() -> String {
         "ECHO".into()
     }
 
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["fast"]
+    }
+
     /// Handles the ECHO command.
     async fn handle(
         &self,
@@ -17,7 +25,7 @@ impl Command for Echo {
         _: &mut crate::state::State,
     ) -> crate::resp::RespType {
         if let Some(message_token) = args.first() {
-            let message = crate::resp::extract_string(message_token).ok();
+            let message = crate::resp::extract_bytes(message_token).ok();
             crate::resp::RespType::BulkString(message)
         } else {
             log::trace!("No message provided.");
@@ -26,8 +34,6 @@ impl Command for Echo {
     }
 }
 
-/// Handles the ECHO command.
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -47,7 +53,7 @@ mod test {
     // --- Tests ---
     #[rstest]
     fn test_name() {
-        assert_eq!("ECHO", Echo.name());
+        assert_eq!("ECHO", Echo::static_name());
     }
 
     #[rstest]
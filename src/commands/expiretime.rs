@@ -0,0 +1,280 @@
+//! This module contains the EXPIRETIME and PEXPIRETIME commands.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the EXPIRETIME and PEXPIRETIME options.
+fn parse_expiretime_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<String> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    Ok(key)
+}
+
+/// Gets the Unix time in milliseconds at which the key expires.
+///
+/// Returns -1 if the key has no expiry and -2 if the key does not exist.
+async fn get_expiretime_ms(
+    args: Vec<crate::resp::RespType>,
+    store: &crate::store::SharedStore,
+    name: &str,
+) -> std::result::Result<i64, crate::resp::RespType> {
+    let key = match parse_expiretime_options(args) {
+        Ok(result) => result,
+        Err(err) => {
+            log::error!("{err}");
+            return Err(crate::resp::RespType::SimpleError(format!(
+                "ERR {err} for '{name}' command"
+            )));
+        }
+    };
+
+    let store = store.read().await;
+    Ok(match store.get(&key) {
+        Some(entry) => entry.deletion_unix_time_ms().unwrap_or(-1),
+        None => -2,
+    })
+}
+
+pub struct Expiretime;
+
+#[async_trait::async_trait]
+impl Command for Expiretime {
+    fn static_name() -> String {
+        "EXPIRETIME".into()
+    }
+
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the EXPIRETIME command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        match get_expiretime_ms(args, store, "EXPIRETIME").await {
+            Ok(time) if time < 0 => crate::resp::RespType::Integer(time),
+            Ok(time) => crate::resp::RespType::Integer(time / 1000),
+            Err(err) => err,
+        }
+    }
+}
+
+pub struct Pexpiretime;
+
+#[async_trait::async_trait]
+impl Command for Pexpiretime {
+    fn static_name() -> String {
+        "PEXPIRETIME".into()
+    }
+
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the PEXPIRETIME command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        match get_expiretime_ms(args, store, "PEXPIRETIME").await {
+            Ok(time) => crate::resp::RespType::Integer(time),
+            Err(err) => err,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str) -> Vec<crate::resp::RespType> {
+        vec![crate::resp::RespType::BulkString(Some(
+            key.to_string().into(),
+        ))]
+    }
+
+    fn unix_time_ms() -> i64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("EXPIRETIME", Expiretime::static_name());
+        assert_eq!("PEXPIRETIME", Pexpiretime::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_with_expiry(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        tokio::time::pause();
+        let duration = 10_000;
+        let before = unix_time_ms();
+        store.write().await.insert(
+            key.clone(),
+            crate::store::Entry::new_string("value").with_deletion(duration as u64),
+        );
+
+        let seconds = Expiretime.handle(make_args(&key), &store, &mut state).await;
+        let milliseconds = Pexpiretime
+            .handle(make_args(&key), &store, &mut state)
+            .await;
+        let after = unix_time_ms();
+
+        let crate::resp::RespType::Integer(milliseconds) = milliseconds else {
+            panic!("Expected an integer, got {milliseconds:?}");
+        };
+        assert!((before + duration..=after + duration).contains(&milliseconds));
+        let crate::resp::RespType::Integer(seconds) = seconds else {
+            panic!("Expected an integer, got {seconds:?}");
+        };
+        assert!(((before + duration) / 1000..=(after + duration) / 1000).contains(&seconds));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_without_expiry(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        assert_eq!(
+            crate::resp::RespType::Integer(-1),
+            Expiretime.handle(make_args(&key), &store, &mut state).await
+        );
+        assert_eq!(
+            crate::resp::RespType::Integer(-1),
+            Pexpiretime
+                .handle(make_args(&key), &store, &mut state)
+                .await
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        assert_eq!(
+            crate::resp::RespType::Integer(-2),
+            Expiretime.handle(make_args(&key), &store, &mut state).await
+        );
+        assert_eq!(
+            crate::resp::RespType::Integer(-2),
+            Pexpiretime
+                .handle(make_args(&key), &store, &mut state)
+                .await
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_expired(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        tokio::time::pause();
+        let duration = 100;
+        store.write().await.insert(
+            key.clone(),
+            crate::store::Entry::new_string("value").with_deletion(duration),
+        );
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert_eq!(
+            crate::resp::RespType::Integer(-2),
+            Pexpiretime
+                .handle(make_args(&key), &store, &mut state)
+                .await
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'EXPIRETIME' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'EXPIRETIME' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Expiretime.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_pexpiretime_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = Pexpiretime.handle(vec![], &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR Missing key for 'PEXPIRETIME' command".into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! This module contains the FLUSHALL command.
+use crate::commands::Command;
+
+pub struct Flushall {
+    databases: crate::store::SharedDatabases,
+}
+
+impl Flushall {
+    /// Creates the FLUSHALL command for the databases.
+    pub fn new(databases: crate::store::SharedDatabases) -> Self {
+        Self { databases }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Flushall {
+    fn static_name() -> String {
+        "FLUSHALL".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "dangerous"]
+    }
+
+    /// Handles the FLUSHALL command.
+    ///
+    /// Every database is locked in index order before any is cleared.
+    async fn handle(
+        &self,
+        _: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let mut stores = vec![];
+        for store in self.databases.iter() {
+            stores.push(store.write().await);
+        }
+        for store in stores.iter_mut() {
+            store.clear();
+        }
+
+        crate::resp::RespType::SimpleString("OK".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("FLUSHALL", Flushall::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let databases = crate::store::new_databases(3);
+        for (i, store) in databases.iter().enumerate() {
+            for j in 0..=i {
+                store
+                    .write()
+                    .await
+                    .insert(format!("key {j}"), crate::store::Entry::new_string("value"));
+            }
+        }
+
+        let response = Flushall::new(databases.clone())
+            .handle(vec![], &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        for store in databases.iter() {
+            assert_eq!(0, store.write().await.len());
+        }
+    }
+}
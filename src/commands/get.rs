@@ -14,10 +14,22 @@ pub struct Get;
 
 #[async_trait::async_trait]
 impl Command for Get {
-    fn name(&self) -> String {
+    fn static_name() -> String {
         "GET".into()
     }
 
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
     /// Handles the GET command.
     async fn handle(
         &self,
@@ -25,7 +37,7 @@ impl Command for Get {
         store: &crate::store::SharedStore,
         state: &mut crate::state::State,
     ) -> crate::resp::RespType {
-        let key = match parse_get_options(args.into_iter()) {
+        let key = match parse_get_options(args) {
             Ok(result) => result,
             Err(err) => {
                 log::error!("{err}");
@@ -33,22 +45,17 @@ impl Command for Get {
             }
         };
 
-        let mut store = store.lock().await;
+        let store = store.read().await;
         let missing_value = match state.protocol_version {
             crate::state::ProtocolVersion::V2 => crate::resp::RespType::BulkString(None),
             crate::state::ProtocolVersion::V3 => crate::resp::RespType::Null(),
         };
         match store.get(&key) {
-            Some(crate::store::Entry {
-                value,
-                deletion_time: _,
-            }) => match value {
+            Some(crate::store::Entry { value, .. }) => match value {
                 crate::store::EntryValue::String(value) => {
                     crate::resp::RespType::BulkString(Some(value.clone()))
                 }
-                _ => crate::resp::RespType::SimpleError(
-                    "WRONGTYPE stored type is not a string".into(),
-                ),
+                _ => crate::commands::wrong_type_error(),
             },
             _ => missing_value,
         }
@@ -84,7 +91,7 @@ mod tests {
     // --- Tests ---
     #[rstest]
     fn test_name() {
-        assert_eq!("GET", Get.name());
+        assert_eq!("GET", Get::static_name());
     }
 
     #[rstest]
@@ -96,13 +103,43 @@ mod tests {
         value: String,
     ) {
         store
-            .lock()
+            .write()
             .await
             .insert(key.clone(), crate::store::Entry::new_string(value.clone()));
 
         let args = vec![crate::resp::RespType::SimpleString(key)];
         let response = Get.handle(args, &store, &mut state).await;
-        assert_eq!(crate::resp::RespType::BulkString(Some(value)), response);
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some(value.into())),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_concurrent_reads(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        value: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string(value.clone()));
+
+        let _reader = store.read().await;
+        let args = vec![crate::resp::RespType::SimpleString(key)];
+        let response = tokio::time::timeout(
+            tokio::time::Duration::from_secs(1),
+            Get.handle(args, &store, &mut state),
+        )
+        .await
+        .expect("GET should not wait for another reader.");
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some(value.into())),
+            response
+        );
     }
 
     #[rstest]
@@ -142,7 +179,7 @@ mod tests {
     ) {
         state.protocol_version = protocol_version;
         let deletion_time = 0u32;
-        store.lock().await.insert(
+        store.write().await.insert(
             key.clone(),
             crate::store::Entry::new_string(value.clone()).with_deletion(deletion_time),
         );
@@ -151,7 +188,7 @@ mod tests {
         let response = Get.handle(args, &store, &mut state).await;
         assert_eq!(expected, response);
 
-        assert!(store.lock().await.get(&key).is_none());
+        assert!(store.write().await.get(&key).is_none());
     }
 
     #[rstest]
@@ -172,19 +209,22 @@ mod tests {
         state.protocol_version = protocol_version;
         tokio::time::pause();
         let deletion_time = 300;
-        store.lock().await.insert(
+        store.write().await.insert(
             key.clone(),
             crate::store::Entry::new_string(value.clone()).with_deletion(deletion_time),
         );
 
         let args = vec![crate::resp::RespType::SimpleString(key)];
         let response = Get.handle(args.clone(), &store, &mut state).await;
-        assert_eq!(crate::resp::RespType::BulkString(Some(value)), response);
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some(value.into())),
+            response
+        );
 
         tokio::time::advance(tokio::time::Duration::from_millis(deletion_time)).await;
         let response = Get.handle(args, &store, &mut state).await;
         assert_eq!(expected, response);
-        assert!(store.lock().await.get("expiredkey").is_none());
+        assert!(store.write().await.get("expiredkey").is_none());
     }
 
     #[rstest]
@@ -222,12 +262,13 @@ mod tests {
         key: String,
     ) {
         store
-            .lock()
+            .write()
             .await
             .insert(key.clone(), crate::store::Entry::new_list());
-        let args = vec![crate::resp::RespType::BulkString(Some(key.clone()))];
-        let expected =
-            crate::resp::RespType::SimpleError("WRONGTYPE stored type is not a string".into());
+        let args = vec![crate::resp::RespType::BulkString(Some(key.clone().into()))];
+        let expected = crate::resp::RespType::SimpleError(
+            "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+        );
         let response = Get.handle(args, &store, &mut state).await;
         assert_eq!(expected, response);
     }
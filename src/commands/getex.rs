@@ -0,0 +1,423 @@
+//! This module contains the GETEX command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The error for an expiry that is not a positive time.
+#[derive(Debug, thiserror::Error)]
+#[error("ERR invalid expire time in 'getex' command")]
+struct InvalidExpireTime;
+
+/// The change to the key's time to live.
+#[derive(Debug, PartialEq)]
+enum Expiry {
+    /// Expires the key at the instant.
+    At(tokio::time::Instant),
+    /// Removes the key's time to live.
+    Persist,
+}
+
+/// Converts a Unix time in milliseconds into an instant, where a time in the past is now.
+fn instant_from_unix_time_ms(unix_time_ms: u64) -> Option<tokio::time::Instant> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    tokio::time::Instant::now()
+        .checked_add(tokio::time::Duration::from_millis(unix_time_ms).saturating_sub(now))
+}
+
+/// Parses the GETEX options.
+fn parse_getex_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, Option<Expiry>)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let mut expiry = None;
+    while let Some(token) = &iter.next() {
+        let option = crate::resp::extract_string(token).context("Failed to extract option")?;
+        if expiry.is_some() {
+            return Err(anyhow::anyhow!(
+                "Only one of EX, PX, EXAT, PXAT or PERSIST can be given"
+            ));
+        }
+
+        let option = option.to_lowercase();
+        if option == "persist" {
+            expiry = Some(Expiry::Persist);
+            continue;
+        }
+        let (is_unix_time, multiplier) = match option.as_str() {
+            "ex" => (false, 1000),
+            "px" => (false, 1),
+            "exat" => (true, 1000),
+            "pxat" => (true, 1),
+            _ => return Err(anyhow::anyhow!("{option} is not a valid option")),
+        };
+        let time = crate::resp::extract_string(
+            &iter
+                .next()
+                .context(format!("Missing time for {} option", option.to_uppercase()))?,
+        )
+        .context("Failed to extract time")?
+        .parse::<i64>()
+        .context("Failed to convert time to a number")?;
+        let deletion_time = u64::try_from(time)
+            .ok()
+            .filter(|time| *time > 0)
+            .and_then(|time| time.checked_mul(multiplier))
+            .and_then(|time_ms| {
+                if is_unix_time {
+                    instant_from_unix_time_ms(time_ms)
+                } else {
+                    tokio::time::Instant::now()
+                        .checked_add(tokio::time::Duration::from_millis(time_ms))
+                }
+            })
+            .ok_or(InvalidExpireTime)?;
+        expiry = Some(Expiry::At(deletion_time));
+    }
+
+    Ok((key, expiry))
+}
+
+pub struct Getex;
+
+#[async_trait::async_trait]
+impl Command for Getex {
+    fn static_name() -> String {
+        "GETEX".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the GETEX command.
+    ///
+    /// The value is read and the time to live is changed under the same lock.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, expiry) = match parse_getex_options(args) {
+            Ok(result) => result,
+            Err(err) if err.is::<InvalidExpireTime>() => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(err.to_string());
+            }
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'GETEX' command"
+                ));
+            }
+        };
+
+        let mut store = store.write().await;
+        let value = match store.get_entry(&key) {
+            Some(crate::store::Entry {
+                value: crate::store::EntryValue::String(value),
+                ..
+            }) => value.clone(),
+            Some(_) => return crate::commands::wrong_type_error(),
+            None => {
+                return match state.protocol_version {
+                    crate::state::ProtocolVersion::V2 => crate::resp::RespType::BulkString(None),
+                    crate::state::ProtocolVersion::V3 => crate::resp::RespType::Null(),
+                };
+            }
+        };
+
+        match expiry {
+            Some(Expiry::At(deletion_time)) => {
+                store.set_expiry(&key, deletion_time);
+            }
+            Some(Expiry::Persist) => {
+                store.clear_expiry(&key);
+            }
+            None => {}
+        }
+        crate::resp::RespType::BulkString(Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    fn unix_time_ms() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("GETEX", Getex::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_without_options(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        tokio::time::pause();
+        let entry = crate::store::Entry::new_string("value").with_deletion(100_u64);
+        store.write().await.insert(key.clone(), entry.clone());
+
+        let response = Getex.handle(make_args(&[&key]), &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("value".into())),
+            response
+        );
+        assert_eq!(Some(&entry), store.read().await.peek(&key));
+    }
+
+    #[rstest]
+    #[case::ex("EX", "10", 10_000)]
+    #[case::px("px", "500", 500)]
+    #[tokio::test]
+    async fn test_handle_duration(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] option: &str,
+        #[case] time: &str,
+        #[case] expected_ms: u64,
+    ) {
+        tokio::time::pause();
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+
+        let response = Getex
+            .handle(make_args(&[&key, option, time]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("value".into())),
+            response
+        );
+
+        tokio::time::advance(tokio::time::Duration::from_millis(expected_ms - 1)).await;
+        assert!(store.read().await.peek(&key).is_some());
+        tokio::time::advance(tokio::time::Duration::from_millis(1)).await;
+        assert!(store.read().await.peek(&key).is_none());
+    }
+
+    #[rstest]
+    #[case::exat("EXAT", 1000)]
+    #[case::pxat("PXAT", 1)]
+    #[tokio::test]
+    async fn test_handle_unix_time(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] option: &str,
+        #[case] unit_ms: u64,
+    ) {
+        tokio::time::pause();
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+
+        let time = (unix_time_ms() + 60_000) / unit_ms;
+        let response = Getex
+            .handle(
+                make_args(&[&key, option, &time.to_string()]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("value".into())),
+            response
+        );
+
+        let deletion_time = store
+            .read()
+            .await
+            .peek(&key)
+            .unwrap()
+            .deletion_unix_time_ms()
+            .unwrap() as u64;
+        // The deletion time is derived from the system clock, which keeps running.
+        assert!(deletion_time.abs_diff(time * unit_ms) < 1000);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_unix_time_in_past(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        tokio::time::pause();
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+
+        let response = Getex
+            .handle(make_args(&[&key, "PXAT", "1"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("value".into())),
+            response
+        );
+        assert!(store.read().await.peek(&key).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_persist(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        tokio::time::pause();
+        store.write().await.insert(
+            key.clone(),
+            crate::store::Entry::new_string("value").with_deletion(100_u64),
+        );
+
+        let response = Getex
+            .handle(make_args(&[&key, "PERSIST"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("value".into())),
+            response
+        );
+
+        tokio::time::advance(tokio::time::Duration::from_millis(100)).await;
+        assert_eq!(
+            Some(&crate::store::Entry::new_string("value")),
+            store.read().await.peek(&key)
+        );
+    }
+
+    #[rstest]
+    #[case::resp2(
+        crate::state::ProtocolVersion::V2,
+        crate::resp::RespType::BulkString(None)
+    )]
+    #[case::resp3(crate::state::ProtocolVersion::V3, crate::resp::RespType::Null())]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] protocol_version: crate::state::ProtocolVersion,
+        #[case] expected: crate::resp::RespType,
+    ) {
+        state.protocol_version = protocol_version;
+        let response = Getex
+            .handle(make_args(&[&key, "PERSIST"]), &store, &mut state)
+            .await;
+        assert_eq!(expected, response);
+        assert!(store.read().await.peek(&key).is_none());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        tokio::time::pause();
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_list());
+
+        let response = Getex
+            .handle(make_args(&[&key, "PX", "100"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+        assert_eq!(None, store.read().await.peek(&key).unwrap().deletion_time);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'GETEX' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'GETEX' command"
+    )]
+    #[case::unknown_option(
+        make_args(&["key", "invalid"]),
+        "ERR invalid is not a valid option for 'GETEX' command"
+    )]
+    #[case::missing_time(make_args(&["key", "ex"]), "ERR Missing time for EX option for 'GETEX' command")]
+    #[case::non_numeric_time(
+        make_args(&["key", "PX", "abc"]),
+        "ERR Failed to convert time to a number for 'GETEX' command"
+    )]
+    #[case::multiple_options(
+        make_args(&["key", "PX", "100", "PERSIST"]),
+        "ERR Only one of EX, PX, EXAT, PXAT or PERSIST can be given for 'GETEX' command"
+    )]
+    #[case::zero_time(make_args(&["key", "EX", "0"]), "ERR invalid expire time in 'getex' command")]
+    #[case::negative_time(
+        make_args(&["key", "PXAT", "-1"]),
+        "ERR invalid expire time in 'getex' command"
+    )]
+    #[case::overflowing_time(
+        make_args(&["key", "EX", &i64::MAX.to_string()]),
+        "ERR invalid expire time in 'getex' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Getex.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! This module contains the HDEL command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the HDEL options.
+fn parse_hdel_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, Vec<String>)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let fields = iter
+        .map(|field| crate::resp::extract_string(&field).context("Failed to extract field"))
+        .collect::<Result<Vec<_>>>()?;
+    if fields.is_empty() {
+        return Err(anyhow::anyhow!("Missing field"));
+    }
+
+    Ok((key, fields))
+}
+
+pub struct Hdel;
+
+#[async_trait::async_trait]
+impl Command for Hdel {
+    fn static_name() -> String {
+        "HDEL".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the HDEL command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, fields) = match parse_hdel_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'HDEL' command"));
+            }
+        };
+
+        let mut store = store.write().await;
+        let Some(entry) = store.get_entry(&key) else {
+            return crate::resp::RespType::Integer(0);
+        };
+        let hash = match &mut entry.value {
+            crate::store::EntryValue::Hash(hash) => hash,
+            _ => return crate::commands::wrong_type_error(),
+        };
+
+        let removed = fields
+            .iter()
+            .filter_map(|field| hash.remove_entry(field))
+            .collect::<Vec<_>>();
+        let is_empty = hash.is_empty();
+        let freed = removed
+            .iter()
+            .map(|(field, value)| field.len() + value.len())
+            .sum();
+        store.track_resize(freed, 0);
+        if is_empty {
+            store.remove(&key);
+        }
+
+        crate::resp::RespType::Integer(removed.len() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str, fields: &[&str]) -> Vec<crate::resp::RespType> {
+        std::iter::once(key)
+            .chain(fields.iter().copied())
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    async fn insert_hash(store: &crate::store::SharedStore, key: &str, fields: &[&str]) {
+        let mut entry = crate::store::Entry::new_hash();
+        if let crate::store::EntryValue::Hash(hash) = &mut entry.value {
+            hash.extend(
+                fields
+                    .iter()
+                    .map(|field| (field.to_string(), "value".to_string())),
+            );
+        }
+        store.write().await.insert(key.into(), entry);
+    }
+
+    async fn get_fields(store: &crate::store::SharedStore, key: &str) -> Vec<String> {
+        match &store.write().await.get(key).unwrap().value {
+            crate::store::EntryValue::Hash(hash) => {
+                let mut fields = hash.keys().cloned().collect::<Vec<_>>();
+                fields.sort_unstable();
+                fields
+            }
+            _ => panic!("Unexpected type"),
+        }
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("HDEL", Hdel::static_name());
+    }
+
+    #[rstest]
+    #[case::single(&["a"], 1, &["b", "c"])]
+    #[case::multiple(&["a", "c"], 2, &["b"])]
+    #[case::absent(&["d"], 0, &["a", "b", "c"])]
+    #[case::mixed(&["a", "d", "a"], 1, &["b", "c"])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] fields: &[&str],
+        #[case] expected_removed: i64,
+        #[case] expected: &[&str],
+    ) {
+        insert_hash(&store, &key, &["a", "b", "c"]).await;
+        let response = Hdel
+            .handle(make_args(&key, fields), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(expected_removed), response);
+        assert_eq!(expected, get_fields(&store, &key).await);
+        let store = store.read().await;
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_removes_empty_hash(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        insert_hash(&store, &key, &["a", "b"]).await;
+        let response = Hdel
+            .handle(make_args(&key, &["a", "b"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(2), response);
+        assert!(store.write().await.get(&key).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Hdel
+            .handle(make_args(&key, &["a"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Hdel
+            .handle(make_args(&key, &["a"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'HDEL' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'HDEL' command"
+    )]
+    #[case::missing_field(make_args("key", &[]), "ERR Missing field for 'HDEL' command")]
+    #[case::invalid_field(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract field for 'HDEL' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Hdel.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -3,44 +3,110 @@ use anyhow::{Context, Result};
 
 use crate::commands::Command;
 
-pub struct Hello;
+pub struct Hello {
+    acl: crate::acl::SharedAcl,
+}
+
+impl Hello {
+    /// Creates the HELLO command, checking the AUTH option's credentials against the users of the
+    /// ACL.
+    pub fn new(acl: crate::acl::SharedAcl) -> Self {
+        Self { acl }
+    }
+}
+
+/// The HELLO options.
+#[derive(Debug, Default, PartialEq)]
+struct HelloOptions {
+    version: Option<String>,
+    /// The username and password to authenticate with.
+    auth: Option<(String, String)>,
+    name: Option<String>,
+}
 
 /// Parses the HELLO options.
 fn parse_hello_options<I: IntoIterator<Item = crate::resp::RespType>>(
     iter: I,
-) -> Result<Option<String>> {
+) -> Result<HelloOptions> {
     let mut iter = iter.into_iter();
 
-    if let Some(version) = iter.next() {
-        Ok(Some(
-            crate::resp::extract_string(&version).context("Failed to parse protocol version")?,
-        ))
-    } else {
-        Ok(None)
+    let mut options = HelloOptions::default();
+    let Some(version) = iter.next() else {
+        return Ok(options);
+    };
+    options.version =
+        Some(crate::resp::extract_string(&version).context("Failed to parse protocol version")?);
+
+    while let Some(token) = iter.next() {
+        let option = crate::resp::extract_string(&token).context("Failed to extract option")?;
+        match option.to_lowercase().as_str() {
+            "auth" => {
+                let username =
+                    crate::resp::extract_string(&iter.next().context("Missing username")?)
+                        .context("Failed to extract username")?;
+                let password =
+                    crate::resp::extract_string(&iter.next().context("Missing password")?)
+                        .context("Failed to extract password")?;
+                options.auth = Some((username, password));
+            }
+            "setname" => {
+                let name = crate::resp::extract_string(&iter.next().context("Missing name")?)
+                    .context("Failed to extract name")?;
+                crate::commands::client::validate_name(&name)?;
+                options.name = Some(name);
+            }
+            _ => return Err(anyhow::anyhow!("{option} is not a valid option")),
+        }
     }
+
+    Ok(options)
 }
 
 #[async_trait::async_trait]
 impl Command for Hello {
-    fn name(&self) -> String {
+    fn static_name() -> String {
         "HELLO".into()
     }
 
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["noscript", "loading", "stale", "fast", "no_auth"]
+    }
+
     /// Handles the HELLO command.
+    ///
+    /// Every option is checked before the connection is changed, so a failing HELLO changes
+    /// nothing.
     async fn handle(
         &self,
         args: Vec<crate::resp::RespType>,
         _: &crate::store::SharedStore,
         state: &mut crate::state::State,
     ) -> crate::resp::RespType {
-        let protocol_version = parse_hello_options(args);
-        if let Err(err) = protocol_version {
-            log::error!("{err}");
-            return crate::resp::RespType::SimpleError(format!("ERR {err} for 'HELLO' command"));
-        }
+        let options = match parse_hello_options(args) {
+            Ok(options) => options,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'HELLO' command"
+                ));
+            }
+        };
 
-        let protocol_version = protocol_version.expect("Error arm checcked.");
-        if let Some(protocol_version) = protocol_version {
+        let user = match &options.auth {
+            Some((username, password)) => match self.acl.authenticate(username, password) {
+                Ok(user) => Some(user),
+                Err(err) => {
+                    log::error!("{err}");
+                    return crate::resp::RespType::SimpleError(err.to_string());
+                }
+            },
+            None => None,
+        };
+        if let Some(protocol_version) = options.version {
             if let Err(err) = state.update_version_from_string(protocol_version) {
                 log::error!("{err}");
                 return crate::resp::RespType::SimpleError(format!(
@@ -48,6 +114,13 @@ impl Command for Hello {
                 ));
             }
         }
+        if let Some(user) = user {
+            state.authenticated = true;
+            state.user = user.name;
+        }
+        if let Some(name) = options.name {
+            state.set_name(name);
+        }
 
         crate::resp::RespType::Map(vec![
             (
@@ -56,7 +129,7 @@ impl Command for Hello {
             ),
             (
                 crate::resp::RespType::BulkString(Some("version".into())),
-                crate::resp::RespType::BulkString(Some("0.0.1".into())),
+                crate::resp::RespType::BulkString(Some(crate::commands::SERVER_VERSION.into())),
             ),
             (
                 crate::resp::RespType::BulkString(Some("proto".into())),
@@ -100,7 +173,7 @@ mod test {
     // --- Tests ---
     #[rstest]
     fn test_name() {
-        assert_eq!("HELLO", Hello.name());
+        assert_eq!("HELLO", Hello::static_name());
     }
 
     #[rstest]
@@ -136,7 +209,7 @@ mod test {
                 crate::resp::RespType::Array(vec![]),
             ),
         ]),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V2
     )]
     #[case::v2_preset_v2(
@@ -171,7 +244,7 @@ mod test {
                 crate::resp::RespType::Array(vec![]),
             ),
         ]),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V2
     )]
     #[case::v3_preset_v2(
@@ -206,19 +279,19 @@ mod test {
                 crate::resp::RespType::Array(vec![]),
             ),
         ]),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V2
     )]
     #[case::invalid_version_preset_v2(
         vec![crate::resp::RespType::SimpleString("a".into())],
         crate::resp::RespType::SimpleError("ERR Invalid protocol version: a for 'HELLO' command".into()),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V2
     )]
     #[case::invalid_argument_preset_v2(
         vec![crate::resp::RespType::Null()],
         crate::resp::RespType::SimpleError("ERR Failed to parse protocol version for 'HELLO' command".into()),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V2
     )]
     #[case::default_preset_v3(
@@ -253,7 +326,7 @@ mod test {
                 crate::resp::RespType::Array(vec![]),
             ),
         ]),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V3
     )]
     #[case::v2_preset_v3(
@@ -288,7 +361,7 @@ mod test {
                 crate::resp::RespType::Array(vec![]),
             ),
         ]),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V2, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V3
     )]
     #[case::v3_preset_v3(
@@ -323,19 +396,19 @@ mod test {
                 crate::resp::RespType::Array(vec![]),
             ),
         ]),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V3,
     )]
     #[case::invalid_version_preset_v3(
         vec![crate::resp::RespType::SimpleString("a".into())],
         crate::resp::RespType::SimpleError("ERR Invalid protocol version: a for 'HELLO' command".into()),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V3,
     )]
     #[case::invalid_argument_preset_v3(
         vec![crate::resp::RespType::Null()],
         crate::resp::RespType::SimpleError("ERR Failed to parse protocol version for 'HELLO' command".into()),
-        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0 },
+        crate::state::State { protocol_version: crate::state::ProtocolVersion::V3, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false },
         crate::state::ProtocolVersion::V3,
     )]
     #[tokio::test]
@@ -348,8 +421,98 @@ mod test {
         #[case] preset_version: crate::state::ProtocolVersion,
     ) {
         state.protocol_version = preset_version;
-        let result = Hello.handle(args, &store, &mut state).await;
+        let result = Hello::new(crate::acl::new())
+            .handle(args, &store, &mut state)
+            .await;
         assert_eq!(expected, result);
         assert_eq!(expected_state, state);
     }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::setname(&["3", "SETNAME", "name"], Some("name"), false)]
+    #[case::auth(&["3", "AUTH", "default", "password"], None, true)]
+    #[case::auth_and_setname(
+        &["3", "auth", "default", "", "setname", "name"],
+        Some("name"),
+        true
+    )]
+    #[tokio::test]
+    async fn test_handle_options(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected_name: Option<&str>,
+        #[case] expected_authenticated: bool,
+    ) {
+        state.authenticated = false;
+        let result = Hello::new(crate::acl::new())
+            .handle(make_args(args), &store, &mut state)
+            .await;
+        assert!(matches!(result, crate::resp::RespType::Map(_)));
+        assert_eq!(expected_authenticated, state.authenticated);
+        assert_eq!(crate::state::ProtocolVersion::V3, state.protocol_version);
+        assert_eq!(expected_name.map(String::from), state.name);
+    }
+
+    #[rstest]
+    #[case::right_password("secret", true)]
+    #[case::wrong_password("password", false)]
+    #[tokio::test]
+    async fn test_handle_auth_requirepass(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] password: &str,
+        #[case] expected: bool,
+    ) {
+        let acl = crate::acl::new();
+        acl.set_default_password("secret");
+        state.authenticated = false;
+        let result = Hello::new(acl)
+            .handle(
+                make_args(&["3", "AUTH", "default", password]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(expected, matches!(result, crate::resp::RespType::Map(_)));
+        assert_eq!(expected, state.authenticated);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::wrong_user(
+        &["3", "AUTH", "user", "password", "SETNAME", "name"],
+        "WRONGPASS invalid username-password pair or user is disabled."
+    )]
+    #[case::invalid_version(
+        &["4", "SETNAME", "name"],
+        "ERR Invalid protocol version: 4 for 'HELLO' command"
+    )]
+    #[case::missing_username(&["3", "AUTH"], "ERR Missing username for 'HELLO' command")]
+    #[case::missing_password(&["3", "AUTH", "default"], "ERR Missing password for 'HELLO' command")]
+    #[case::missing_name(&["3", "SETNAME"], "ERR Missing name for 'HELLO' command")]
+    #[case::invalid_name(
+        &["3", "SETNAME", "a name"],
+        "ERR Client names cannot contain spaces, newlines or special characters for 'HELLO' command"
+    )]
+    #[case::unknown_option(&["3", "invalid"], "ERR invalid is not a valid option for 'HELLO' command")]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected: &str,
+    ) {
+        let result = Hello::new(crate::acl::new())
+            .handle(make_args(args), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleError(expected.into()), result);
+        assert_eq!(crate::state::State::new(0), state);
+    }
 }
@@ -0,0 +1,201 @@
+//! This module contains the HGETALL command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the HGETALL options.
+fn parse_hgetall_options<I: IntoIterator<Item = crate::resp::RespType>>(iter: I) -> Result<String> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    Ok(key)
+}
+
+pub struct Hgetall;
+
+#[async_trait::async_trait]
+impl Command for Hgetall {
+    fn static_name() -> String {
+        "HGETALL".into()
+    }
+
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the HGETALL command.
+    ///
+    /// The fields are sorted so that the reply is deterministic.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let key = match parse_hgetall_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'HGETALL' command"
+                ));
+            }
+        };
+
+        let store = store.read().await;
+        match store.get(&key) {
+            Some(crate::store::Entry {
+                value: crate::store::EntryValue::Hash(hash),
+                ..
+            }) => {
+                let mut pairs = hash.iter().collect::<Vec<_>>();
+                pairs.sort_unstable();
+                crate::resp::RespType::Map(
+                    pairs
+                        .into_iter()
+                        .map(|(field, value)| {
+                            (
+                                crate::resp::RespType::BulkString(Some(field.clone().into())),
+                                crate::resp::RespType::BulkString(Some(value.clone().into())),
+                            )
+                        })
+                        .collect(),
+                )
+            }
+            Some(_) => crate::commands::wrong_type_error(),
+            None => crate::resp::RespType::Map(vec![]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str) -> Vec<crate::resp::RespType> {
+        vec![crate::resp::RespType::BulkString(Some(
+            key.to_string().into(),
+        ))]
+    }
+
+    async fn insert_hash(store: &crate::store::SharedStore, key: &str, pairs: &[(&str, &str)]) {
+        let mut entry = crate::store::Entry::new_hash();
+        if let crate::store::EntryValue::Hash(hash) = &mut entry.value {
+            hash.extend(
+                pairs
+                    .iter()
+                    .map(|(field, value)| (field.to_string(), value.to_string())),
+            );
+        }
+        store.write().await.insert(key.into(), entry);
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("HGETALL", Hgetall::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        insert_hash(&store, &key, &[("b", "2"), ("a", "1")]).await;
+        let response = Hgetall.handle(make_args(&key), &store, &mut state).await;
+        let expected = crate::resp::RespType::Map(vec![
+            (
+                crate::resp::RespType::BulkString(Some("a".into())),
+                crate::resp::RespType::BulkString(Some("1".into())),
+            ),
+            (
+                crate::resp::RespType::BulkString(Some("b".into())),
+                crate::resp::RespType::BulkString(Some("2".into())),
+            ),
+        ]);
+        assert_eq!(expected, response);
+        assert_eq!(
+            "*4\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n",
+            response.serialize_with_version(&crate::state::ProtocolVersion::V2)
+        );
+        assert_eq!(
+            "%2\r\n$1\r\na\r\n$1\r\n1\r\n$1\r\nb\r\n$1\r\n2\r\n",
+            response.serialize_with_version(&crate::state::ProtocolVersion::V3)
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Hgetall.handle(make_args(&key), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Map(vec![]), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Hgetall.handle(make_args(&key), &store, &mut state).await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'HGETALL' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'HGETALL' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Hgetall.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! This module contains the HLEN command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the HLEN options.
+fn parse_hlen_options<I: IntoIterator<Item = crate::resp::RespType>>(iter: I) -> Result<String> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    Ok(key)
+}
+
+pub struct Hlen;
+
+#[async_trait::async_trait]
+impl Command for Hlen {
+    fn static_name() -> String {
+        "HLEN".into()
+    }
+
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the HLEN command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let key = match parse_hlen_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'HLEN' command"));
+            }
+        };
+
+        let store = store.read().await;
+        match store.get(&key) {
+            Some(crate::store::Entry {
+                value: crate::store::EntryValue::Hash(hash),
+                ..
+            }) => crate::resp::RespType::Integer(hash.len() as i64),
+            Some(_) => crate::commands::wrong_type_error(),
+            None => crate::resp::RespType::Integer(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str) -> Vec<crate::resp::RespType> {
+        vec![crate::resp::RespType::BulkString(Some(
+            key.to_string().into(),
+        ))]
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("HLEN", Hlen::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let mut entry = crate::store::Entry::new_hash();
+        if let crate::store::EntryValue::Hash(hash) = &mut entry.value {
+            hash.insert("a".into(), "1".into());
+            hash.insert("b".into(), "2".into());
+        }
+        store.write().await.insert(key.clone(), entry);
+        let response = Hlen.handle(make_args(&key), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(2), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Hlen.handle(make_args(&key), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_list());
+        let response = Hlen.handle(make_args(&key), &store, &mut state).await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'HLEN' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'HLEN' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Hlen.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
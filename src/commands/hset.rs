@@ -0,0 +1,201 @@
+//! This module contains the HSET command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the HSET options.
+fn parse_hset_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, Vec<(String, String)>)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let mut pairs = vec![];
+    while let Some(token) = iter.next() {
+        let field = crate::resp::extract_string(&token).context("Failed to extract field")?;
+        let value = crate::resp::extract_string(&iter.next().context("Missing value")?)
+            .context("Failed to extract value")?;
+        pairs.push((field, value));
+    }
+    if pairs.is_empty() {
+        return Err(anyhow::anyhow!("Missing field"));
+    }
+
+    Ok((key, pairs))
+}
+
+pub struct Hset;
+
+#[async_trait::async_trait]
+impl Command for Hset {
+    fn static_name() -> String {
+        "HSET".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -4
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "denyoom", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the HSET command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, pairs) = match parse_hset_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'HSET' command"));
+            }
+        };
+
+        let mut store = store.write().await;
+        let entry_ref = store.get_or_insert(key.clone(), crate::store::Entry::new_hash());
+        let hash = match &mut entry_ref.value {
+            crate::store::EntryValue::Hash(hash) => hash,
+            _ => return crate::commands::wrong_type_error(),
+        };
+        let (mut added, mut freed, mut taken) = (0, 0, 0);
+        for (field, value) in pairs {
+            let field_length = field.len();
+            taken += value.len();
+            match hash.insert(field, value) {
+                Some(previous) => freed += previous.len(),
+                None => {
+                    added += 1;
+                    taken += field_length;
+                }
+            }
+        }
+        store.track_resize(freed, taken);
+
+        crate::resp::RespType::Integer(added as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("HSET", Hset::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Hset
+            .handle(make_args(&[&key, "a", "1", "b", "2"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(2), response);
+
+        let response = Hset
+            .handle(make_args(&[&key, "a", "3", "c", "4"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+
+        let expected = std::collections::HashMap::from([
+            ("a".to_string(), "3".to_string()),
+            ("b".to_string(), "2".to_string()),
+            ("c".to_string(), "4".to_string()),
+        ]);
+        assert_eq!(
+            crate::store::EntryValue::Hash(expected),
+            store.write().await.get(&key).unwrap().value
+        );
+        let store = store.read().await;
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Hset
+            .handle(make_args(&[&key, "a", "1"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'HSET' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'HSET' command"
+    )]
+    #[case::missing_field(make_args(&["key"]), "ERR Missing field for 'HSET' command")]
+    #[case::invalid_field(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract field for 'HSET' command"
+    )]
+    #[case::missing_value(make_args(&["key", "a"]), "ERR Missing value for 'HSET' command")]
+    #[case::missing_second_value(
+        make_args(&["key", "a", "1", "b"]),
+        "ERR Missing value for 'HSET' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Hset.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        assert!(store.write().await.get("key").is_none());
+    }
+}
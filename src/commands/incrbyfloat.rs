@@ -0,0 +1,336 @@
+//! This module contains the INCRBYFLOAT command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The number of significant digits kept when formatting a float.
+const SIGNIFICANT_DIGITS: usize = 15;
+
+/// Parses the INCRBYFLOAT options.
+fn parse_incrbyfloat_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, String)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let increment = crate::resp::extract_string(&iter.next().context("Missing increment")?)
+        .context("Failed to extract increment")?;
+
+    Ok((key, increment))
+}
+
+/// Parses a finite float.
+fn parse_float(value: &str) -> Option<f64> {
+    value.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// Formats a float without trailing zeros or exponent notation.
+/// The value is rounded to a fixed number of significant digits to hide binary rounding noise,
+/// e.g. `0.1 + 0.2` is formatted as `0.3`.
+fn format_float(value: f64) -> String {
+    let rounded = format!("{:.*e}", SIGNIFICANT_DIGITS - 1, value)
+        .parse::<f64>()
+        .expect("Formatted float should parse.");
+    if rounded == 0.0 {
+        "0".into()
+    } else {
+        rounded.to_string()
+    }
+}
+
+pub struct Incrbyfloat;
+
+#[async_trait::async_trait]
+impl Command for Incrbyfloat {
+    fn static_name() -> String {
+        "INCRBYFLOAT".into()
+    }
+
+    fn arity(&self) -> i64 {
+        3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "denyoom", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the INCRBYFLOAT command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, increment) = match parse_incrbyfloat_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'INCRBYFLOAT' command"
+                ));
+            }
+        };
+        let Some(increment) = parse_float(&increment) else {
+            return crate::resp::RespType::SimpleError("ERR value is not a valid float".into());
+        };
+
+        let mut store = store.write().await;
+        let entry = store.get_or_insert(key.clone(), crate::store::Entry::new_string("0"));
+        let value = match &mut entry.value {
+            crate::store::EntryValue::String(value) => value,
+            _ => return crate::commands::wrong_type_error(),
+        };
+        let Some(current) = std::str::from_utf8(value).ok().and_then(parse_float) else {
+            return crate::resp::RespType::SimpleError("ERR value is not a valid float".into());
+        };
+
+        let result = current + increment;
+        if !result.is_finite() {
+            return crate::resp::RespType::SimpleError(
+                "ERR increment would produce NaN or Infinity".into(),
+            );
+        }
+
+        let freed = value.len();
+        *value = format_float(result).into();
+        let value = value.clone();
+        store.track_resize(freed, value.len());
+        crate::resp::RespType::BulkString(Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str, increment: &str) -> Vec<crate::resp::RespType> {
+        vec![
+            crate::resp::RespType::BulkString(Some(key.to_string().into())),
+            crate::resp::RespType::BulkString(Some(increment.to_string().into())),
+        ]
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("INCRBYFLOAT", Incrbyfloat::static_name());
+    }
+
+    #[rstest]
+    #[case::integer(10.0, "10")]
+    #[case::fraction(10.5, "10.5")]
+    #[case::negative(-0.25, "-0.25")]
+    #[case::zero(0.0, "0")]
+    #[case::negative_zero(-0.0, "0")]
+    #[case::rounding_noise(0.1 + 0.2, "0.3")]
+    #[case::large(1e20, "100000000000000000000")]
+    #[case::small(1.5e-7, "0.00000015")]
+    fn test_format_float(#[case] value: f64, #[case] expected: &str) {
+        assert_eq!(expected, format_float(value));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_new_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Incrbyfloat
+            .handle(make_args(&key, "10.5"), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("10.5".into())),
+            response
+        );
+        assert_eq!(
+            crate::store::Entry::new_string("10.5"),
+            *store.write().await.get(&key).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::float("10.50", "0.1", "10.6")]
+    #[case::integer("3", "1.5", "4.5")]
+    #[case::negative_increment("10.5", "-5", "5.5")]
+    #[case::to_integer("5.0e3", "200", "5200")]
+    #[case::to_zero("1.5", "-1.5", "0")]
+    #[tokio::test]
+    async fn test_handle_existing(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] existing: &str,
+        #[case] increment: &str,
+        #[case] expected: &str,
+    ) {
+        store.write().await.insert(
+            key.clone(),
+            crate::store::Entry::new_string(existing.to_string()),
+        );
+        let response = Incrbyfloat
+            .handle(make_args(&key, increment), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some(expected.to_string().into())),
+            response
+        );
+        let store = store.read().await;
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_preserves_ttl(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        tokio::time::pause();
+        let entry = crate::store::Entry::new_string("1").with_deletion(100u64);
+        let deletion_time = entry.deletion_time;
+        store.write().await.insert(key.clone(), entry);
+
+        Incrbyfloat
+            .handle(make_args(&key, "1"), &store, &mut state)
+            .await;
+        assert_eq!(
+            deletion_time,
+            store.write().await.get(&key).unwrap().deletion_time
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::text("abc")]
+    #[case::nan("nan")]
+    #[case::inf("inf")]
+    #[tokio::test]
+    async fn test_handle_non_float_value(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] existing: &str,
+    ) {
+        store.write().await.insert(
+            key.clone(),
+            crate::store::Entry::new_string(existing.to_string()),
+        );
+        let response = Incrbyfloat
+            .handle(make_args(&key, "1"), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR value is not a valid float".into()),
+            response
+        );
+        assert_eq!(
+            crate::store::Entry::new_string(existing.to_string()),
+            *store.write().await.get(&key).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_non_float_increment(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Incrbyfloat
+            .handle(make_args(&key, "abc"), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR value is not a valid float".into()),
+            response
+        );
+        assert!(store.write().await.get(&key).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_overflow(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("1.7e308"));
+        let response = Incrbyfloat
+            .handle(make_args(&key, "1.7e308"), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR increment would produce NaN or Infinity".into()
+            ),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_list());
+        let response = Incrbyfloat
+            .handle(make_args(&key, "1"), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'INCRBYFLOAT' command")]
+    #[case::missing_increment(
+        vec![crate::resp::RespType::BulkString(Some("key".into()))],
+        "ERR Missing increment for 'INCRBYFLOAT' command"
+    )]
+    #[case::invalid_increment(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract increment for 'INCRBYFLOAT' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Incrbyfloat.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! This module contains the INFO command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The sections returned when no section is requested.
+const DEFAULT_SECTIONS: [&str; 3] = ["server", "stats", "keyspace"];
+/// Every section, in the order they are returned.
+const ALL_SECTIONS: [&str; 4] = ["server", "stats", "commandstats", "keyspace"];
+
+/// Parses the INFO options.
+fn parse_info_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Vec<String>> {
+    let mut sections = vec![];
+    for token in iter {
+        let section = crate::resp::extract_string(&token).context("Failed to extract section")?;
+        match section.to_lowercase().as_str() {
+            "default" => {
+                sections.extend(DEFAULT_SECTIONS.iter().map(|section| section.to_string()))
+            }
+            "all" | "everything" => {
+                sections.extend(ALL_SECTIONS.iter().map(|section| section.to_string()))
+            }
+            section => sections.push(section.into()),
+        }
+    }
+
+    if sections.is_empty() {
+        sections.extend(DEFAULT_SECTIONS.iter().map(|section| section.to_string()));
+    }
+    Ok(sections)
+}
+
+pub struct Info {
+    port: u16,
+    start_time: tokio::time::Instant,
+    stats: crate::stats::SharedStats,
+    databases: crate::store::SharedDatabases,
+}
+
+impl Info {
+    /// Creates the INFO command for a server listening on the given port and reporting the
+    /// statistics and the databases. The server uptime is measured from when the command is
+    /// created.
+    pub fn new(
+        port: u16,
+        stats: crate::stats::SharedStats,
+        databases: crate::store::SharedDatabases,
+    ) -> Self {
+        Self {
+            port,
+            start_time: tokio::time::Instant::now(),
+            stats,
+            databases,
+        }
+    }
+
+    /// Builds the server section.
+    fn server_section(&self) -> String {
+        format!(
+            "# Server\r\nredis_version:{}\r\ntcp_port:{}\r\nuptime_in_seconds:{}\r\n",
+            crate::commands::SERVER_VERSION,
+            self.port,
+            self.start_time.elapsed().as_secs()
+        )
+    }
+
+    /// Builds the stats section.
+    fn stats_section(&self) -> String {
+        format!(
+            "# Stats\r\ntotal_connections_received:{}\r\ntotal_commands_processed:{}\r\n",
+            self.stats.total_connections_received(),
+            self.stats.total_commands_processed()
+        )
+    }
+
+    /// Builds the commandstats section, with the calls, time spent and failed calls of every
+    /// called command.
+    fn commandstats_section(&self) -> String {
+        let mut section = "# Commandstats\r\n".to_string();
+        for (name, stats) in self.stats.all_command_stats() {
+            section.push_str(&format!(
+                "cmdstat_{name}:calls={},usec={},usec_per_call={:.2},failed_calls={}\r\n",
+                stats.calls,
+                stats.usec,
+                stats.usec as f64 / stats.calls as f64,
+                stats.failed_calls
+            ));
+        }
+        section
+    }
+
+    /// Builds the keyspace section, with the number of keys of every database holding keys.
+    async fn keyspace_section(&self) -> String {
+        let mut section = "# Keyspace\r\n".to_string();
+        for (db, store) in self.databases.iter().enumerate() {
+            let keys = store.read().await.len();
+            if keys > 0 {
+                section.push_str(&format!("db{db}:keys={keys}\r\n"));
+            }
+        }
+        section
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Info {
+    fn static_name() -> String {
+        "INFO".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["loading", "stale"]
+    }
+
+    /// Handles the INFO command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let sections = match parse_info_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'INFO' command"));
+            }
+        };
+
+        let mut result = vec![];
+        for section in ALL_SECTIONS {
+            if !sections.iter().any(|requested| requested == section) {
+                continue;
+            }
+            match section {
+                "server" => result.push(self.server_section()),
+                "stats" => result.push(self.stats_section()),
+                "commandstats" => result.push(self.commandstats_section()),
+                "keyspace" => result.push(self.keyspace_section().await),
+                _ => unreachable!("Only known sections are iterated."),
+            }
+        }
+
+        crate::resp::RespType::VerbatimString("txt".into(), result.join("\r\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn databases() -> crate::store::SharedDatabases {
+        crate::store::new_databases(16)
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn extract_info(response: crate::resp::RespType) -> String {
+        match response {
+            crate::resp::RespType::VerbatimString(format, info) if format == "txt" => info,
+            _ => panic!("Expected a verbatim string, got {:?}.", response),
+        }
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("INFO", Info::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_default(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        databases: crate::store::SharedDatabases,
+    ) {
+        for i in 0..3 {
+            databases[0]
+                .write()
+                .await
+                .insert(format!("key {i}"), crate::store::Entry::new_string("value"));
+        }
+
+        let info = extract_info(
+            Info::new(6379, crate::stats::new(), databases)
+                .handle(vec![], &store, &mut state)
+                .await,
+        );
+        assert!(info.contains("# Server\r\n"));
+        assert!(info.contains("tcp_port:6379\r\n"));
+        assert!(info.contains("# Keyspace\r\n"));
+        assert!(info.contains("db0:keys=3\r\n"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_keyspace(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        databases: crate::store::SharedDatabases,
+    ) {
+        databases[2]
+            .write()
+            .await
+            .insert("key".into(), crate::store::Entry::new_string("value"));
+        databases[15]
+            .write()
+            .await
+            .insert("key".into(), crate::store::Entry::new_string("value"));
+
+        let args = vec![crate::resp::RespType::BulkString(Some("keyspace".into()))];
+        let info = extract_info(
+            Info::new(6379, crate::stats::new(), databases)
+                .handle(args, &store, &mut state)
+                .await,
+        );
+        assert_eq!("# Keyspace\r\ndb2:keys=1\r\ndb15:keys=1\r\n", info);
+    }
+
+    #[rstest]
+    #[case::server("server", "# Server\r\n", "# Keyspace\r\n")]
+    #[case::server_upper("SERVER", "# Server\r\n", "# Keyspace\r\n")]
+    #[case::keyspace("keyspace", "# Keyspace\r\n", "# Server\r\n")]
+    #[tokio::test]
+    async fn test_handle_section(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] section: &str,
+        #[case] included: &str,
+        #[case] excluded: &str,
+    ) {
+        let args = vec![crate::resp::RespType::BulkString(Some(
+            section.to_string().into(),
+        ))];
+        let info = extract_info(
+            Info::new(6379, crate::stats::new(), databases())
+                .handle(args, &store, &mut state)
+                .await,
+        );
+        assert!(info.contains(included));
+        assert!(!info.contains(excluded));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_stats(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let stats = crate::stats::new();
+        stats.record_connection();
+        for _ in 0..3 {
+            stats.record_command("get", std::time::Duration::from_micros(5), false);
+        }
+        stats.record_command("set", std::time::Duration::from_micros(2), true);
+
+        let info = Info::new(6379, stats, databases());
+        let args = vec![crate::resp::RespType::BulkString(Some("stats".into()))];
+        let response = extract_info(info.handle(args, &store, &mut state).await);
+        assert_eq!(
+            "# Stats\r\ntotal_connections_received:1\r\ntotal_commands_processed:4\r\n",
+            response
+        );
+
+        let args = vec![crate::resp::RespType::BulkString(Some(
+            "commandstats".into(),
+        ))];
+        let response = extract_info(info.handle(args, &store, &mut state).await);
+        assert_eq!(
+            "# Commandstats\r\n\
+             cmdstat_get:calls=3,usec=15,usec_per_call=5.00,failed_calls=0\r\n\
+             cmdstat_set:calls=1,usec=2,usec_per_call=2.00,failed_calls=1\r\n",
+            response
+        );
+    }
+
+    #[rstest]
+    #[case::default("default", false)]
+    #[case::all("all", true)]
+    #[case::everything("everything", true)]
+    #[tokio::test]
+    async fn test_handle_commandstats_only_in_all(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] section: &str,
+        #[case] expected: bool,
+    ) {
+        let args = vec![crate::resp::RespType::BulkString(Some(
+            section.to_string().into(),
+        ))];
+        let info = extract_info(
+            Info::new(6379, crate::stats::new(), databases())
+                .handle(args, &store, &mut state)
+                .await,
+        );
+        assert!(info.contains("# Stats\r\n"));
+        assert_eq!(expected, info.contains("# Commandstats\r\n"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_unknown_section(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let args = vec![crate::resp::RespType::BulkString(Some("unknown".into()))];
+        let response = Info::new(6379, crate::stats::new(), databases())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::VerbatimString("txt".into(), "".into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_uptime(store: crate::store::SharedStore, mut state: crate::state::State) {
+        tokio::time::pause();
+        let info = Info::new(6379, crate::stats::new(), databases());
+        tokio::time::advance(tokio::time::Duration::from_secs(5)).await;
+
+        let args = vec![crate::resp::RespType::BulkString(Some("server".into()))];
+        let response = extract_info(info.handle(args, &store, &mut state).await);
+        assert!(response.contains("uptime_in_seconds:5\r\n"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_invalid_section(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let args = vec![crate::resp::RespType::Array(vec![])];
+        let response = Info::new(6379, crate::stats::new(), databases())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR Failed to extract section for 'INFO' command".into()
+            ),
+            response
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! This module contains the KEYS command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the KEYS options.
+fn parse_keys_options<I: IntoIterator<Item = crate::resp::RespType>>(iter: I) -> Result<String> {
+    let mut iter = iter.into_iter();
+
+    let pattern = crate::resp::extract_string(&iter.next().context("Missing pattern")?)
+        .context("Failed to extract pattern")?;
+    if iter.next().is_some() {
+        return Err(anyhow::anyhow!("Too many arguments"));
+    }
+
+    Ok(pattern)
+}
+
+pub struct Keys;
+
+#[async_trait::async_trait]
+impl Command for Keys {
+    fn static_name() -> String {
+        "KEYS".into()
+    }
+
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    /// Handles the KEYS command.
+    ///
+    /// Every key is matched against the pattern, so this is slow on large databases.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let pattern = match parse_keys_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'KEYS' command"));
+            }
+        };
+
+        let pattern = crate::pattern::Pattern::new(pattern);
+        let keys = store
+            .read()
+            .await
+            .iter()
+            .filter(|(key, _)| pattern.matches(key))
+            .map(|(key, _)| crate::resp::RespType::BulkString(Some(key.clone().into())))
+            .collect();
+
+        crate::resp::RespType::Array(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    async fn populate(store: &crate::store::SharedStore) {
+        let mut store = store.write().await;
+        for key in ["hello", "hallo", "hillo", "world"] {
+            store.insert(key.into(), crate::store::Entry::new_string("value"));
+        }
+    }
+
+    fn sorted_keys(response: crate::resp::RespType) -> Vec<String> {
+        let crate::resp::RespType::Array(keys) = response else {
+            panic!("Unexpected response: {:?}.", response);
+        };
+        let mut keys = keys
+            .iter()
+            .map(|key| crate::resp::extract_string(key).unwrap())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("KEYS", Keys::static_name());
+    }
+
+    #[rstest]
+    #[case::all("*", &["hallo", "hello", "hillo", "world"])]
+    #[case::question("h?llo", &["hallo", "hello", "hillo"])]
+    #[case::class("h[ae]llo", &["hallo", "hello"])]
+    #[case::negated_class("h[^e]llo", &["hallo", "hillo"])]
+    #[case::range("h[a-f]llo", &["hallo", "hello"])]
+    #[case::exact("world", &["world"])]
+    #[case::none("missing*", &[])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] pattern: &str,
+        #[case] expected: &[&str],
+    ) {
+        populate(&store).await;
+        let response = Keys.handle(make_args(&[pattern]), &store, &mut state).await;
+        assert_eq!(expected, sorted_keys(response));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_escaped(store: crate::store::SharedStore, mut state: crate::state::State) {
+        populate(&store).await;
+        store
+            .write()
+            .await
+            .insert("key*".into(), crate::store::Entry::new_string("value"));
+        let response = Keys
+            .handle(make_args(&["key\\*"]), &store, &mut state)
+            .await;
+        assert_eq!(vec!["key*"], sorted_keys(response));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_expired(store: crate::store::SharedStore, mut state: crate::state::State) {
+        populate(&store).await;
+        tokio::time::pause();
+        let duration = 10u64;
+        store.write().await.insert(
+            "hullo".into(),
+            crate::store::Entry::new_string("value").with_deletion(duration),
+        );
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        let response = Keys.handle(make_args(&["hu*"]), &store, &mut state).await;
+        assert_eq!(Vec::<String>::new(), sorted_keys(response));
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_pattern(vec![], "ERR Missing pattern for 'KEYS' command")]
+    #[case::invalid_pattern(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract pattern for 'KEYS' command"
+    )]
+    #[case::too_many_arguments(
+        make_args(&["*", "extra"]),
+        "ERR Too many arguments for 'KEYS' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Keys.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
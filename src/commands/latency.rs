@@ -0,0 +1,323 @@
+//! This module contains the LATENCY command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The LATENCY subcommands' usages and descriptions.
+const SUBCOMMANDS: [(&str, &str); 3] = [
+    (
+        "HISTORY <event>",
+        "Return the time and latency of the latest spikes of the event.",
+    ),
+    (
+        "LATEST",
+        "Return the time and latency of the latest spike and the highest latency of every event.",
+    ),
+    (
+        "RESET [<event> ...]",
+        "Clear the spikes of the events, or of every event.",
+    ),
+];
+
+/// The LATENCY subcommands.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    History(String),
+    Latest,
+    Reset(Vec<String>),
+}
+
+/// Parses the LATENCY options.
+fn parse_latency_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Subcommand> {
+    let mut iter = iter.into_iter();
+
+    let subcommand = crate::resp::extract_string(&iter.next().context("Missing subcommand")?)
+        .context("Failed to extract subcommand")?;
+
+    match subcommand.to_lowercase().as_str() {
+        "history" => {
+            let event = crate::resp::extract_string(&iter.next().context("Missing event")?)
+                .context("Failed to extract event")?;
+            Ok(Subcommand::History(event))
+        }
+        "latest" => Ok(Subcommand::Latest),
+        "reset" => {
+            let mut events = vec![];
+            for token in iter {
+                events
+                    .push(crate::resp::extract_string(&token).context("Failed to extract event")?);
+            }
+            Ok(Subcommand::Reset(events))
+        }
+        _ => Err(anyhow::anyhow!("Unknown subcommand '{subcommand}'")),
+    }
+}
+
+pub struct Latency {
+    latency: crate::latency::SharedLatency,
+}
+
+impl Latency {
+    /// Creates the LATENCY command, reporting the spikes recorded by the latency monitor.
+    pub fn new(latency: crate::latency::SharedLatency) -> Self {
+        Self { latency }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Latency {
+    fn static_name() -> String {
+        "LATENCY".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["admin", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the LATENCY command.
+    ///
+    /// Events are only recorded once `latency-monitor-threshold` is set, for the spikes lasting at
+    /// least that many milliseconds.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        if let Some(response) = crate::commands::dispatch_subcommand("LATENCY", &args, &SUBCOMMANDS)
+        {
+            return response;
+        }
+        let subcommand = match parse_latency_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'LATENCY' command"
+                ));
+            }
+        };
+
+        match subcommand {
+            Subcommand::History(event) => crate::resp::RespType::Array(
+                self.latency
+                    .history(&event)
+                    .into_iter()
+                    .map(|sample| {
+                        crate::resp::RespType::Array(vec![
+                            crate::resp::RespType::Integer(sample.time),
+                            crate::resp::RespType::Integer(sample.latency_ms as i64),
+                        ])
+                    })
+                    .collect(),
+            ),
+            Subcommand::Latest => crate::resp::RespType::Array(
+                self.latency
+                    .latest()
+                    .into_iter()
+                    .map(|(event, sample, max_latency_ms)| {
+                        crate::resp::RespType::Array(vec![
+                            crate::resp::RespType::BulkString(Some(event.into())),
+                            crate::resp::RespType::Integer(sample.time),
+                            crate::resp::RespType::Integer(sample.latency_ms as i64),
+                            crate::resp::RespType::Integer(max_latency_ms as i64),
+                        ])
+                    })
+                    .collect(),
+            ),
+            Subcommand::Reset(events) => {
+                crate::resp::RespType::Integer(self.latency.reset(&events) as i64)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    /// A latency monitor with a spike of 300ms for commands and 150ms for expire cycles.
+    #[fixture]
+    fn latency() -> crate::latency::SharedLatency {
+        let latency = crate::latency::new();
+        latency.set_threshold(100);
+        for (event, latency_ms) in [("command", 300), ("expire-cycle", 150)] {
+            latency.record(event, std::time::Duration::from_millis(latency_ms));
+        }
+        latency
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("LATENCY", Latency::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_latest(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        latency: crate::latency::SharedLatency,
+    ) {
+        let response = Latency::new(latency.clone())
+            .handle(make_args(&["LATEST"]), &store, &mut state)
+            .await;
+
+        let crate::resp::RespType::Array(events) = response else {
+            panic!("Expected an array, got {response:?}");
+        };
+        let expected = [("command", 300, 300), ("expire-cycle", 150, 150)];
+        assert_eq!(expected.len(), events.len());
+        for (event, (name, latency_ms, max_latency_ms)) in events.into_iter().zip(expected) {
+            let time = latency.history(name)[0].time;
+            assert_eq!(
+                crate::resp::RespType::Array(vec![
+                    crate::resp::RespType::BulkString(Some(name.into())),
+                    crate::resp::RespType::Integer(time),
+                    crate::resp::RespType::Integer(latency_ms),
+                    crate::resp::RespType::Integer(max_latency_ms),
+                ]),
+                event
+            );
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_latest_empty(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = Latency::new(crate::latency::new())
+            .handle(make_args(&["latest"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Array(vec![]), response);
+    }
+
+    #[rstest]
+    #[case::recorded("expire-cycle", 1)]
+    #[case::unknown("unknown", 0)]
+    #[tokio::test]
+    async fn test_handle_history(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        latency: crate::latency::SharedLatency,
+        #[case] event: &str,
+        #[case] expected_len: usize,
+    ) {
+        let response = Latency::new(latency.clone())
+            .handle(make_args(&["HISTORY", event]), &store, &mut state)
+            .await;
+
+        let expected = latency
+            .history(event)
+            .into_iter()
+            .map(|sample| {
+                crate::resp::RespType::Array(vec![
+                    crate::resp::RespType::Integer(sample.time),
+                    crate::resp::RespType::Integer(sample.latency_ms as i64),
+                ])
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(expected_len, expected.len());
+        assert_eq!(crate::resp::RespType::Array(expected), response);
+    }
+
+    #[rstest]
+    #[case::all(&["RESET"], 2, &[])]
+    #[case::events(&["reset", "command", "unknown"], 1, &["expire-cycle"])]
+    #[tokio::test]
+    async fn test_handle_reset(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        latency: crate::latency::SharedLatency,
+        #[case] args: &[&str],
+        #[case] expected: i64,
+        #[case] remaining: &[&str],
+    ) {
+        let response = Latency::new(latency.clone())
+            .handle(make_args(args), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(expected), response);
+        assert_eq!(
+            remaining,
+            latency
+                .latest()
+                .iter()
+                .map(|(event, _, _)| event.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_help(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let response = Latency::new(crate::latency::new())
+            .handle(make_args(&["HELP"]), &store, &mut state)
+            .await;
+        let crate::resp::RespType::Array(lines) = response else {
+            panic!("Expected an array, got {response:?}");
+        };
+        assert_eq!(
+            crate::resp::RespType::SimpleString("HISTORY <event>".into()),
+            lines[1]
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_event(
+        make_args(&["HISTORY"]),
+        "ERR Missing event for 'LATENCY' command"
+    )]
+    #[case::invalid_event(
+        vec![
+            crate::resp::RespType::BulkString(Some("RESET".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract event for 'LATENCY' command"
+    )]
+    #[case::unknown_subcommand(
+        make_args(&["DOCTOR"]),
+        "ERR Unknown subcommand or wrong number of arguments for 'DOCTOR'. Try LATENCY HELP."
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Latency::new(crate::latency::new())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
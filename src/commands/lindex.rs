@@ -0,0 +1,215 @@
+//! This module contains the LINDEX command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the LINDEX options.
+fn parse_lindex_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, i64)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let index = crate::resp::extract_string(&iter.next().context("Missing index")?)
+        .context("Failed to extract index")?
+        .parse::<i64>()
+        .context("Failed to convert index to a number")?;
+
+    Ok((key, index))
+}
+
+pub struct Lindex;
+
+#[async_trait::async_trait]
+impl Command for Lindex {
+    fn static_name() -> String {
+        "LINDEX".into()
+    }
+
+    fn arity(&self) -> i64 {
+        3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the LINDEX command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, index) = match parse_lindex_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'LINDEX' command"
+                ));
+            }
+        };
+
+        let store = store.read().await;
+        match store.get(&key) {
+            Some(crate::store::Entry {
+                value: crate::store::EntryValue::List(list),
+                ..
+            }) => match crate::commands::resolve_index(index, list.len()) {
+                Some(index) => crate::resp::RespType::BulkString(Some(list[index].clone().into())),
+                None => crate::resp::RespType::Null(),
+            },
+            Some(_) => crate::commands::wrong_type_error(),
+            None => crate::resp::RespType::Null(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    #[fixture]
+    fn values() -> Vec<String> {
+        (0..5).map(|i| format!("value {i}")).collect()
+    }
+
+    fn make_args(key: &str, index: &str) -> Vec<crate::resp::RespType> {
+        vec![
+            crate::resp::RespType::BulkString(Some(key.to_string().into())),
+            crate::resp::RespType::BulkString(Some(index.to_string().into())),
+        ]
+    }
+
+    async fn insert_list(store: &crate::store::SharedStore, key: &str, values: &[String]) {
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.extend(values.iter().cloned());
+        }
+        store.write().await.insert(key.into(), entry);
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("LINDEX", Lindex::static_name());
+    }
+
+    #[rstest]
+    #[case::first("0", Some(0))]
+    #[case::positive("2", Some(2))]
+    #[case::last("4", Some(4))]
+    #[case::negative_last("-1", Some(4))]
+    #[case::negative("-3", Some(2))]
+    #[case::negative_first("-5", Some(0))]
+    #[case::out_of_range("5", None)]
+    #[case::negative_out_of_range("-6", None)]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        values: Vec<String>,
+        #[case] index: &str,
+        #[case] expected: Option<usize>,
+    ) {
+        insert_list(&store, &key, &values).await;
+        let expected = match expected {
+            Some(i) => crate::resp::RespType::BulkString(Some(values[i].clone().into())),
+            None => crate::resp::RespType::Null(),
+        };
+        let response = Lindex
+            .handle(make_args(&key, index), &store, &mut state)
+            .await;
+        assert_eq!(expected, response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Lindex
+            .handle(make_args(&key, "0"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Null(), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Lindex
+            .handle(make_args(&key, "0"), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'LINDEX' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'LINDEX' command"
+    )]
+    #[case::missing_index(
+        vec![crate::resp::RespType::BulkString(Some("key".into()))],
+        "ERR Missing index for 'LINDEX' command"
+    )]
+    #[case::invalid_index(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract index for 'LINDEX' command"
+    )]
+    #[case::non_numeric_index(
+        make_args("key", "abc"),
+        "ERR Failed to convert index to a number for 'LINDEX' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Lindex.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! This module contains the LINSERT command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Where to insert the value relative to the pivot.
+#[derive(Debug, PartialEq)]
+enum Position {
+    Before,
+    After,
+}
+
+/// Parses the LINSERT options.
+fn parse_linsert_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, Position, String, String)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let position = crate::resp::extract_string(&iter.next().context("Missing position")?)
+        .context("Failed to extract position")?;
+    let position = match position.to_lowercase().as_str() {
+        "before" => Position::Before,
+        "after" => Position::After,
+        _ => return Err(anyhow::anyhow!("syntax error")),
+    };
+
+    let pivot = crate::resp::extract_string(&iter.next().context("Missing pivot")?)
+        .context("Failed to extract pivot")?;
+
+    let value = crate::resp::extract_string(&iter.next().context("Missing value")?)
+        .context("Failed to extract value")?;
+
+    Ok((key, position, pivot, value))
+}
+
+pub struct Linsert;
+
+#[async_trait::async_trait]
+impl Command for Linsert {
+    fn static_name() -> String {
+        "LINSERT".into()
+    }
+
+    fn arity(&self) -> i64 {
+        5
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "denyoom"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the LINSERT command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, position, pivot, value) = match parse_linsert_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'LINSERT' command"
+                ));
+            }
+        };
+
+        let mut store = store.write().await;
+        let Some(entry) = store.get_entry(&key) else {
+            return crate::resp::RespType::Integer(0);
+        };
+        let list = match &mut entry.value {
+            crate::store::EntryValue::List(list) => list,
+            _ => return crate::commands::wrong_type_error(),
+        };
+
+        match list.iter().position(|element| *element == pivot) {
+            Some(index) => {
+                let index = match position {
+                    Position::Before => index,
+                    Position::After => index + 1,
+                };
+                let taken = value.len();
+                list.insert(index, value);
+                let length = list.len();
+                store.track_resize(0, taken);
+                crate::resp::RespType::Integer(length as i64)
+            }
+            None => crate::resp::RespType::Integer(-1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn values() -> Vec<String> {
+        ["a", "b", "c", "b"].iter().map(|s| s.to_string()).collect()
+    }
+
+    fn make_args(
+        key: &str,
+        position: &str,
+        pivot: &str,
+        value: &str,
+    ) -> Vec<crate::resp::RespType> {
+        [key, position, pivot, value]
+            .iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    async fn insert_list(store: &crate::store::SharedStore, key: &str, values: &[String]) {
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.extend(values.iter().cloned());
+        }
+        store.write().await.insert(key.into(), entry);
+    }
+
+    async fn get_list(store: &crate::store::SharedStore, key: &str) -> Vec<String> {
+        match &store.write().await.get(key).unwrap().value {
+            crate::store::EntryValue::List(list) => list.clone(),
+            _ => panic!("Unexpected type"),
+        }
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("LINSERT", Linsert::static_name());
+    }
+
+    #[rstest]
+    #[case::before("BEFORE", "b", &["a", "new", "b", "c", "b"])]
+    #[case::before_lower("before", "a", &["new", "a", "b", "c", "b"])]
+    #[case::after("AFTER", "b", &["a", "b", "new", "c", "b"])]
+    #[case::after_last("after", "c", &["a", "b", "c", "new", "b"])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] position: &str,
+        #[case] pivot: &str,
+        #[case] expected: &[&str],
+    ) {
+        insert_list(&store, &key, &values()).await;
+        let response = Linsert
+            .handle(make_args(&key, position, pivot, "new"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(5), response);
+        assert_eq!(expected, get_list(&store, &key).await);
+        let store = store.read().await;
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_pivot(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        insert_list(&store, &key, &values()).await;
+        let response = Linsert
+            .handle(make_args(&key, "BEFORE", "z", "new"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(-1), response);
+        assert_eq!(values(), get_list(&store, &key).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Linsert
+            .handle(make_args(&key, "BEFORE", "a", "new"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+        assert!(store.write().await.get(&key).is_none());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Linsert
+            .handle(make_args(&key, "BEFORE", "a", "new"), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'LINSERT' command")]
+    #[case::missing_position(make_args("key", "", "", "")[..1].to_vec(), "ERR Missing position for 'LINSERT' command")]
+    #[case::invalid_position(
+        make_args("key", "middle", "a", "new"),
+        "ERR syntax error for 'LINSERT' command"
+    )]
+    #[case::missing_pivot(make_args("key", "BEFORE", "", "")[..2].to_vec(), "ERR Missing pivot for 'LINSERT' command")]
+    #[case::missing_value(make_args("key", "BEFORE", "a", "")[..3].to_vec(), "ERR Missing value for 'LINSERT' command")]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Linsert.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! This module contains the LMPOP command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The end of the list to pop from.
+#[derive(Debug, PartialEq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+/// The LMPOP options.
+#[derive(Debug, PartialEq)]
+struct LmpopOptions {
+    keys: Vec<String>,
+    direction: Direction,
+    count: usize,
+}
+
+/// Parses the LMPOP options.
+fn parse_lmpop_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<LmpopOptions> {
+    let mut iter = iter.into_iter();
+
+    let numkeys = crate::resp::extract_string(&iter.next().context("Missing numkeys")?)
+        .context("Failed to extract numkeys")?
+        .parse::<usize>()
+        .ok()
+        .filter(|numkeys| *numkeys > 0)
+        .context("Failed to convert numkeys to a positive number")?;
+
+    let keys = (0..numkeys)
+        .map(|_| {
+            crate::resp::extract_string(&iter.next().context("Missing key")?)
+                .context("Failed to extract key")
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let direction = crate::resp::extract_string(&iter.next().context("Missing direction")?)
+        .context("Failed to extract direction")?;
+    let direction = match direction.to_lowercase().as_str() {
+        "left" => Direction::Left,
+        "right" => Direction::Right,
+        _ => return Err(anyhow::anyhow!("{direction} is not a valid direction")),
+    };
+
+    let mut options = LmpopOptions {
+        keys,
+        direction,
+        count: 1,
+    };
+    while let Some(token) = iter.next() {
+        let option = crate::resp::extract_string(&token).context("Failed to extract option")?;
+        match option.to_lowercase().as_str() {
+            "count" => {
+                options.count = crate::resp::extract_string(&iter.next().context("Missing count")?)
+                    .context("Failed to extract count")?
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|count| *count > 0)
+                    .context("Failed to convert count to a positive number")?;
+            }
+            _ => return Err(anyhow::anyhow!("{option} is not a valid option")),
+        }
+    }
+
+    Ok(options)
+}
+
+pub struct Lmpop;
+
+#[async_trait::async_trait]
+impl Command for Lmpop {
+    fn static_name() -> String {
+        "LMPOP".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -4
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "movablekeys"]
+    }
+
+    fn keys(&self, args: &[crate::resp::RespType]) -> Vec<String> {
+        crate::commands::numkeys_keys(args)
+    }
+
+    /// Handles the LMPOP command.
+    ///
+    /// The keys are scanned in order under one lock, popping from the first list with elements.
+    /// A key holding another type is an error unless a list before it has elements.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let options = match parse_lmpop_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'LMPOP' command"
+                ));
+            }
+        };
+
+        let mut store = store.write().await;
+        for key in options.keys {
+            let Some(entry) = store.get_entry(&key) else {
+                continue;
+            };
+            let list = match &mut entry.value {
+                crate::store::EntryValue::List(list) => list,
+                _ => return crate::commands::wrong_type_error(),
+            };
+            if list.is_empty() {
+                continue;
+            }
+
+            let count = options.count.min(list.len());
+            let popped = match options.direction {
+                Direction::Left => list.drain(..count).collect::<Vec<_>>(),
+                Direction::Right => list.drain(list.len() - count..).rev().collect(),
+            };
+            let is_empty = list.is_empty();
+            store.track_resize(popped.iter().map(String::len).sum(), 0);
+            if is_empty {
+                store.remove(&key);
+            }
+            return crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some(key.into())),
+                crate::resp::RespType::Array(
+                    popped
+                        .into_iter()
+                        .map(|value| crate::resp::RespType::BulkString(Some(value.into())))
+                        .collect(),
+                ),
+            ]);
+        }
+
+        crate::resp::RespType::Null()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    fn make_list(elements: &[&str]) -> crate::store::Entry {
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.extend(elements.iter().map(|element| element.to_string()));
+        }
+        entry
+    }
+
+    fn make_reply(key: &str, values: &[&str]) -> crate::resp::RespType {
+        crate::resp::RespType::Array(vec![
+            crate::resp::RespType::BulkString(Some(key.to_string().into())),
+            crate::resp::RespType::Array(
+                values
+                    .iter()
+                    .map(|value| crate::resp::RespType::BulkString(Some(value.to_string().into())))
+                    .collect(),
+            ),
+        ])
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("LMPOP", Lmpop::static_name());
+    }
+
+    #[rstest]
+    #[case::left(&["2", "first", "second", "LEFT"], &["a"], &["b", "c"])]
+    #[case::right(&["2", "first", "second", "right"], &["c"], &["a", "b"])]
+    #[case::left_count(&["2", "first", "second", "LEFT", "COUNT", "2"], &["a", "b"], &["c"])]
+    #[case::right_count(&["2", "first", "second", "RIGHT", "count", "2"], &["c", "b"], &["a"])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected: &[&str],
+        #[case] remaining: &[&str],
+    ) {
+        {
+            let mut store = store.write().await;
+            store.insert("first".into(), make_list(&[]));
+            store.insert("second".into(), make_list(&["a", "b", "c"]));
+        }
+
+        let response = Lmpop.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(make_reply("second", expected), response);
+
+        let store = store.read().await;
+        assert_eq!(Some(&make_list(remaining)), store.peek("second"));
+        assert_eq!(Some(&make_list(&[])), store.peek("first"));
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_first_list(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        {
+            let mut store = store.write().await;
+            store.insert("first".into(), make_list(&["a"]));
+            store.insert("second".into(), crate::store::Entry::new_string("value"));
+        }
+
+        let response = Lmpop
+            .handle(
+                make_args(&["2", "first", "second", "LEFT"]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(make_reply("first", &["a"]), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_emptied_list_is_removed(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        store
+            .write()
+            .await
+            .insert("key".into(), make_list(&["a", "b"]));
+
+        let response = Lmpop
+            .handle(
+                make_args(&["1", "key", "LEFT", "COUNT", "5"]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(make_reply("key", &["a", "b"]), response);
+        assert!(store.read().await.peek("key").is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_all_empty(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        store.write().await.insert("first".into(), make_list(&[]));
+
+        let response = Lmpop
+            .handle(
+                make_args(&["2", "first", "missing", "RIGHT"]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(crate::resp::RespType::Null(), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        {
+            let mut store = store.write().await;
+            store.insert("first".into(), crate::store::Entry::new_string("value"));
+            store.insert("second".into(), make_list(&["a"]));
+        }
+
+        let response = Lmpop
+            .handle(
+                make_args(&["2", "first", "second", "LEFT"]),
+                &store,
+                &mut state,
+            )
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+        assert_eq!(Some(&make_list(&["a"])), store.read().await.peek("second"));
+    }
+
+    #[rstest]
+    #[case::missing_numkeys(vec![], "ERR Missing numkeys for 'LMPOP' command")]
+    #[case::invalid_numkeys(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract numkeys for 'LMPOP' command"
+    )]
+    #[case::zero_numkeys(
+        make_args(&["0", "LEFT"]),
+        "ERR Failed to convert numkeys to a positive number for 'LMPOP' command"
+    )]
+    #[case::non_numeric_numkeys(
+        make_args(&["abc", "key", "LEFT"]),
+        "ERR Failed to convert numkeys to a positive number for 'LMPOP' command"
+    )]
+    #[case::missing_key(make_args(&["2", "key"]), "ERR Missing key for 'LMPOP' command")]
+    #[case::missing_direction(make_args(&["1", "key"]), "ERR Missing direction for 'LMPOP' command")]
+    #[case::invalid_direction(
+        make_args(&["1", "key", "up"]),
+        "ERR up is not a valid direction for 'LMPOP' command"
+    )]
+    #[case::missing_count(
+        make_args(&["1", "key", "LEFT", "COUNT"]),
+        "ERR Missing count for 'LMPOP' command"
+    )]
+    #[case::zero_count(
+        make_args(&["1", "key", "LEFT", "COUNT", "0"]),
+        "ERR Failed to convert count to a positive number for 'LMPOP' command"
+    )]
+    #[case::unknown_option(
+        make_args(&["1", "key", "LEFT", "invalid"]),
+        "ERR invalid is not a valid option for 'LMPOP' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Lmpop.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
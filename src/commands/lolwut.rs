@@ -0,0 +1,588 @@
+//! This module contains the LOLWUT command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The LOLWUT options, with the numbers following the version being specific to the version.
+#[derive(Debug, PartialEq)]
+struct LolwutOptions {
+    version: Option<i64>,
+    numbers: Vec<i64>,
+}
+
+/// Parses the LOLWUT options.
+fn parse_lolwut_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<LolwutOptions> {
+    let mut iter = iter.into_iter().peekable();
+
+    let mut version = None;
+    if let Some(token) = iter.peek() {
+        if crate::resp::extract_string(token)
+            .is_ok_and(|token| token.eq_ignore_ascii_case("version"))
+        {
+            iter.next();
+            version = Some(
+                crate::resp::extract_string(&iter.next().context("Missing version")?)
+                    .context("Failed to extract version")?
+                    .parse::<i64>()
+                    .context("Failed to convert version to a number")?,
+            );
+        }
+    }
+    let numbers = iter
+        .map(|token| {
+            crate::resp::extract_string(&token)
+                .context("Failed to extract argument")?
+                .parse::<i64>()
+                .context("Failed to convert argument to a number")
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(LolwutOptions { version, numbers })
+}
+
+/// A pseudo-random number generator, as the art only needs to look random.
+struct Random(u64);
+
+impl Random {
+    /// Creates a generator seeded with a random number.
+    fn new() -> Self {
+        use std::hash::{BuildHasher, Hasher};
+
+        // Each `RandomState` is seeded with random keys, so an empty hash is a random number.
+        let seed = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        Self(seed | 1)
+    }
+
+    /// Gets the next non-negative number, using xorshift64*.
+    fn next(&mut self) -> i64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 33) as i64
+    }
+
+    /// Gets the next number between 0 and 1.
+    fn next_f32(&mut self) -> f32 {
+        self.next() as f32 / (u32::MAX >> 1) as f32
+    }
+}
+
+/// A grid of pixels, each holding a color.
+struct Canvas {
+    width: i64,
+    height: i64,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// Creates a canvas filled with the color.
+    fn new(width: i64, height: i64, color: u8) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; (width * height) as usize],
+        }
+    }
+
+    /// Sets the color of the pixel, ignoring pixels outside the canvas.
+    fn draw_pixel(&mut self, x: i64, y: i64, color: u8) {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            self.pixels[(y * self.width + x) as usize] = color;
+        }
+    }
+
+    /// Gets the color of the pixel, where pixels outside the canvas are of color 0.
+    fn pixel(&self, x: i64, y: i64) -> u8 {
+        if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+            self.pixels[(y * self.width + x) as usize]
+        } else {
+            0
+        }
+    }
+
+    /// Draws a line between the two pixels, with Bresenham's algorithm.
+    fn draw_line(&mut self, (mut x1, mut y1): (i64, i64), (x2, y2): (i64, i64), color: u8) {
+        let (dx, dy) = ((x2 - x1).abs(), (y2 - y1).abs());
+        let (sx, sy) = (if x1 < x2 { 1 } else { -1 }, if y1 < y2 { 1 } else { -1 });
+        let mut err = dx - dy;
+        loop {
+            self.draw_pixel(x1, y1, color);
+            if x1 == x2 && y1 == y2 {
+                break;
+            }
+            let e2 = err * 2;
+            if e2 > -dy {
+                err -= dy;
+                x1 += sx;
+            }
+            if e2 < dx {
+                err += dx;
+                y1 += sy;
+            }
+        }
+    }
+
+    /// Draws the outline of a square centered on the pixel, rotated by the angle in radians.
+    fn draw_square(&mut self, (x, y): (i64, i64), size: f32, angle: f32, color: u8) {
+        let size = (size / std::f32::consts::SQRT_2).round();
+        let corners = (0..4)
+            .map(|corner| {
+                let k = std::f32::consts::FRAC_PI_4
+                    + angle
+                    + corner as f32 * std::f32::consts::FRAC_PI_2;
+                (
+                    (k.sin() * size + x as f32).round() as i64,
+                    (k.cos() * size + y as f32).round() as i64,
+                )
+            })
+            .collect::<Vec<_>>();
+        for corner in 0..4 {
+            self.draw_line(corners[corner], corners[(corner + 1) % 4], color);
+        }
+    }
+}
+
+/// The pixels of a Braille character, relative to its top left pixel, by the bit of its dot.
+const BRAILLE_DOTS: [(i64, i64); 8] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (1, 0),
+    (1, 1),
+    (1, 2),
+    (0, 3),
+    (1, 3),
+];
+
+/// Draws Georg Nees' Schotter, a grid of squares getting more disordered from top to bottom, in
+/// Braille characters, each showing 2 by 4 pixels.
+fn schotter(
+    random: &mut Random,
+    columns: i64,
+    squares_per_row: i64,
+    squares_per_column: i64,
+) -> String {
+    let width = columns * 2;
+    let padding = if width > 4 { 2 } else { 0 };
+    let square_side = (width - padding * 2) as f32 / squares_per_row as f32;
+    let height = (square_side * squares_per_column as f32) as i64 + padding * 2;
+    let mut canvas = Canvas::new(width, height, 0);
+
+    for y in 0..squares_per_column {
+        for x in 0..squares_per_row {
+            let mut sx = (x as f32 * square_side + square_side / 2.0 + padding as f32) as i64;
+            let mut sy = (y as f32 * square_side + square_side / 2.0 + padding as f32) as i64;
+            let mut angle = 0.0;
+            // The first two rows are left in order.
+            if y > 1 {
+                let mut offsets =
+                    [0.0; 3].map(|_: f32| random.next_f32() / squares_per_column as f32 * y as f32);
+                for offset in &mut offsets {
+                    if random.next() % 2 == 1 {
+                        *offset = -*offset;
+                    }
+                }
+                angle = offsets[0];
+                sx = (sx as f32 + offsets[1] * square_side / 3.0) as i64;
+                sy = (sy as f32 + offsets[2] * square_side / 3.0) as i64;
+            }
+            canvas.draw_square((sx, sy), square_side, angle, 1);
+        }
+    }
+
+    let mut lines = vec![];
+    for y in (0..canvas.height).step_by(4) {
+        let mut line = String::new();
+        for x in (0..canvas.width).step_by(2) {
+            let mut pattern = 0;
+            for (bit, (dx, dy)) in BRAILLE_DOTS.into_iter().enumerate() {
+                if canvas.pixel(x + dx, y + dy) != 0 {
+                    pattern |= 1 << bit;
+                }
+            }
+            line.push(char::from_u32(0x2800 + pattern).expect("Braille patterns are valid."));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// A building of the skyline.
+struct Skyscraper {
+    offset: i64,
+    width: i64,
+    height: i64,
+    color: u8,
+    windows: bool,
+}
+
+/// Draws the skyscraper from the bottom of the canvas, with a roof narrower by two pixels on each
+/// side and, if it has windows, two pixels wide windows of random lighter colors.
+fn draw_skyscraper(random: &mut Random, canvas: &mut Canvas, skyscraper: &Skyscraper) {
+    let start_y = canvas.height - 1;
+    let end_y = start_y - skyscraper.height + 1;
+    let (start_x, end_x) = (skyscraper.offset, skyscraper.offset + skyscraper.width);
+    for y in (end_y..=start_y).rev() {
+        for x in start_x..end_x {
+            if y == end_y && (x <= start_x + 1 || x >= end_x - 2) {
+                continue;
+            }
+            let mut color = skyscraper.color;
+            let is_inner = x > start_x + 1 && x < end_x - 2 && y > end_y + 1 && y < start_y - 1;
+            if skyscraper.windows && is_inner {
+                let (relative_x, relative_y) = (x - (start_x + 1), y - (end_y + 1));
+                if relative_x / 2 % 2 == 1 && relative_y % 2 == 1 {
+                    color = loop {
+                        let color = 1 + (random.next() % 2) as u8;
+                        if color != skyscraper.color {
+                            break color;
+                        }
+                    };
+                    // Both pixels of a window share its color.
+                    if relative_x % 2 == 1 {
+                        color = canvas.pixel(x - 1, y);
+                    }
+                }
+            }
+            canvas.draw_pixel(x, y, color);
+        }
+    }
+}
+
+/// Draws a city skyline in 8-bit style with ASCII characters of four shades, lighter skyscrapers
+/// standing behind darker ones with windows.
+fn skyline(random: &mut Random, columns: i64, rows: i64) -> String {
+    let mut canvas = Canvas::new(columns, rows, 3);
+    for color in [2, 1] {
+        let mut offset = -10;
+        while offset < canvas.width {
+            offset += random.next() % 8;
+            let width = 10 + random.next() % 9;
+            let height = if color == 2 {
+                canvas.height / 2 + random.next() % canvas.height / 2
+            } else {
+                canvas.height / 2 + random.next() % canvas.height / 3
+            };
+            let skyscraper = Skyscraper {
+                offset,
+                width,
+                height,
+                color,
+                windows: false,
+            };
+            draw_skyscraper(random, &mut canvas, &skyscraper);
+            offset += if color == 2 { width / 2 } else { width + 1 };
+        }
+    }
+
+    let mut offset = -10;
+    while offset < canvas.width {
+        offset += random.next() % 8;
+        let mut width = 5 + random.next() % 14;
+        if width % 4 != 0 {
+            width += width % 3;
+        }
+        let height = canvas.height / 3 + random.next() % canvas.height / 2;
+        let skyscraper = Skyscraper {
+            offset,
+            width,
+            height,
+            color: 0,
+            windows: true,
+        };
+        draw_skyscraper(random, &mut canvas, &skyscraper);
+        offset += width + 5;
+    }
+
+    let mut text = String::new();
+    for y in 0..canvas.height {
+        for x in 0..canvas.width {
+            text.push(match canvas.pixel(x, y) {
+                0 => ' ',
+                1 => '.',
+                2 => 'o',
+                _ => '#',
+            });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Gets the number at the index, or the default if missing, clamped between 1 and the maximum.
+fn number_or(numbers: &[i64], index: usize, default: i64, max: i64) -> i64 {
+    numbers.get(index).copied().unwrap_or(default).clamp(1, max)
+}
+
+/// Makes the art of the version, followed by its credits and the server version.
+fn render(random: &mut Random, version: i64, numbers: &[i64]) -> String {
+    let version_line = format!("Redis ver. {}\n", crate::commands::SERVER_VERSION);
+    match version {
+        5 => {
+            let art = schotter(
+                random,
+                number_or(numbers, 0, 66, 1000),
+                number_or(numbers, 1, 8, 200),
+                number_or(numbers, 2, 12, 200),
+            );
+            format!("{art}\nGeorg Nees - schotter, plotter on paper, 1968. {version_line}")
+        }
+        6 => {
+            let art = skyline(
+                random,
+                number_or(numbers, 0, 80, 1000),
+                number_or(numbers, 1, 20, 1000),
+            );
+            format!(
+                "{art}\nDedicated to the 8 bit game developers of past and present.\nOriginal 8 bit image from Plaguemon by hikikomori. {version_line}"
+            )
+        }
+        _ => version_line,
+    }
+}
+
+pub struct Lolwut;
+
+#[async_trait::async_trait]
+impl Command for Lolwut {
+    fn static_name() -> String {
+        "LOLWUT".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "fast"]
+    }
+
+    /// Handles the LOLWUT command.
+    ///
+    /// Version 5 takes the number of columns, squares per row and squares per column, and version
+    /// 6 the number of columns and rows. Other versions, including the server's by default, only
+    /// show the server version.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let options = match parse_lolwut_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'LOLWUT' command"
+                ));
+            }
+        };
+
+        let server_version = crate::commands::SERVER_VERSION
+            .split('.')
+            .next()
+            .and_then(|major| major.parse().ok())
+            .unwrap_or_default();
+        let version = options.version.unwrap_or(server_version);
+        crate::resp::RespType::VerbatimString(
+            "txt".into(),
+            render(&mut Random::new(), version, &options.numbers),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn random() -> Random {
+        Random(42)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("LOLWUT", Lolwut::static_name());
+    }
+
+    #[rstest]
+    fn test_random(mut random: Random) {
+        for _ in 0..1000 {
+            assert!((0.0..=1.0).contains(&random.next_f32()));
+        }
+    }
+
+    #[rstest]
+    #[case::horizontal((0, 1), (3, 1), &[(0, 1), (1, 1), (2, 1), (3, 1)])]
+    #[case::diagonal((3, 3), (1, 1), &[(1, 1), (2, 2), (3, 3)])]
+    #[case::clipped((-1, 0), (1, 0), &[(0, 0), (1, 0)])]
+    fn test_draw_line(
+        #[case] from: (i64, i64),
+        #[case] to: (i64, i64),
+        #[case] expected: &[(i64, i64)],
+    ) {
+        let mut canvas = Canvas::new(4, 4, 0);
+        canvas.draw_line(from, to, 1);
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(
+                    expected.contains(&(x, y)) as u8,
+                    canvas.pixel(x, y),
+                    "({x}, {y})"
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_draw_square() {
+        let mut canvas = Canvas::new(7, 7, 0);
+        canvas.draw_square((3, 3), 4.0 * std::f32::consts::SQRT_2, 0.0, 1);
+        // The unrotated square has its corners on the diagonals, 3 pixels from the center.
+        for (x, y) in [(6, 6), (0, 6), (0, 0), (6, 0), (3, 0), (0, 3)] {
+            assert_eq!(1, canvas.pixel(x, y), "({x}, {y})");
+        }
+        assert_eq!(0, canvas.pixel(3, 3));
+    }
+
+    #[rstest]
+    fn test_schotter(mut random: Random) {
+        let art = schotter(&mut random, 20, 4, 6);
+        let lines = art.lines().collect::<Vec<_>>();
+        // 6 rows of 9 pixels wide squares with a padding of 2 pixels make a 58 pixels high canvas.
+        assert_eq!(15, lines.len());
+        for line in lines {
+            assert_eq!(20, line.chars().count());
+            assert!(line
+                .chars()
+                .all(|char| ('\u{2800}'..='\u{28ff}').contains(&char)));
+        }
+        assert!(art.chars().any(|char| char != '\u{2800}'));
+    }
+
+    #[rstest]
+    fn test_skyline(mut random: Random) {
+        let art = skyline(&mut random, 40, 10);
+        let lines = art.lines().collect::<Vec<_>>();
+        assert_eq!(10, lines.len());
+        for line in &lines {
+            assert_eq!(40, line.len());
+            assert!(line.chars().all(|char| " .o#".contains(char)));
+        }
+        // The skyscrapers stand on the bottom row, leaving the sky above them.
+        assert!(!lines[9].contains('#'));
+        assert!(lines[0].contains('#'));
+    }
+
+    #[rstest]
+    #[case::schotter(5, &[], 66, 49)]
+    #[case::schotter_clamped(5, &[0, 0, 0], 1, 1)]
+    #[case::skyline(6, &[], 80, 20)]
+    #[case::skyline_size(6, &[30, 5], 30, 5)]
+    fn test_render_size(
+        mut random: Random,
+        #[case] version: i64,
+        #[case] numbers: &[i64],
+        #[case] expected_width: usize,
+        #[case] expected_height: usize,
+    ) {
+        let rendered = render(&mut random, version, numbers);
+        let lines = rendered.lines().collect::<Vec<_>>();
+        // The credits follow the art, after an empty line for the skyline.
+        let art = &lines[..lines.len() - if version == 5 { 1 } else { 3 }];
+        assert_eq!(expected_height, art.len());
+        assert!(art
+            .iter()
+            .all(|line| line.chars().count() == expected_width));
+    }
+
+    #[rstest]
+    #[case::schotter(
+        5,
+        "\nGeorg Nees - schotter, plotter on paper, 1968. Redis ver. 0.0.1\n"
+    )]
+    #[case::skyline(
+        6,
+        "\nDedicated to the 8 bit game developers of past and present.\nOriginal 8 bit image from Plaguemon by hikikomori. Redis ver. 0.0.1\n"
+    )]
+    fn test_render_credits(mut random: Random, #[case] version: i64, #[case] expected: &str) {
+        assert!(render(&mut random, version, &[]).ends_with(expected));
+    }
+
+    #[rstest]
+    #[case::default(&[])]
+    #[case::other_version(&["VERSION", "1"])]
+    #[tokio::test]
+    async fn test_handle_version_only(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+    ) {
+        let response = Lolwut.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::VerbatimString("txt".into(), "Redis ver. 0.0.1\n".into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_version(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let response = Lolwut
+            .handle(make_args(&["version", "6", "10", "4"]), &store, &mut state)
+            .await;
+        let crate::resp::RespType::VerbatimString(format, text) = response else {
+            panic!("Expected a verbatim string, got {response:?}");
+        };
+        assert_eq!("txt", format);
+        assert_eq!(4 + 3, text.lines().count());
+        assert!(text.lines().take(4).all(|line| line.len() == 10));
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_version(make_args(&["VERSION"]), "ERR Missing version for 'LOLWUT' command")]
+    #[case::invalid_version(
+        make_args(&["VERSION", "five"]),
+        "ERR Failed to convert version to a number for 'LOLWUT' command"
+    )]
+    #[case::invalid_argument(
+        make_args(&["VERSION", "5", "wide"]),
+        "ERR Failed to convert argument to a number for 'LOLWUT' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Lolwut.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
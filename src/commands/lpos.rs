@@ -0,0 +1,301 @@
+//! This module contains the LPOS command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The LPOS options.
+#[derive(Debug, PartialEq)]
+struct LposOptions {
+    key: String,
+    value: String,
+    rank: i64,
+    count: Option<usize>,
+    maxlen: usize,
+}
+
+/// Parses a number for an LPOS option.
+fn parse_option_number<I: Iterator<Item = crate::resp::RespType>>(
+    iter: &mut I,
+    option: &str,
+) -> Result<i64> {
+    crate::resp::extract_string(
+        &iter
+            .next()
+            .context(format!("Missing value for {option} option"))?,
+    )
+    .context(format!("Failed to extract {option} value"))?
+    .parse::<i64>()
+    .context(format!("Failed to convert {option} value to a number"))
+}
+
+/// Parses the LPOS options.
+fn parse_lpos_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<LposOptions> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let value = crate::resp::extract_string(&iter.next().context("Missing value")?)
+        .context("Failed to extract value")?;
+
+    let mut options = LposOptions {
+        key,
+        value,
+        rank: 1,
+        count: None,
+        maxlen: 0,
+    };
+    while let Some(token) = iter.next() {
+        let option = crate::resp::extract_string(&token).context("Failed to extract option")?;
+        match option.to_lowercase().as_str() {
+            "rank" => {
+                options.rank = parse_option_number(&mut iter, "RANK")?;
+                if options.rank == 0 {
+                    return Err(anyhow::anyhow!("RANK can't be zero"));
+                }
+            }
+            "count" => {
+                let count = parse_option_number(&mut iter, "COUNT")?;
+                options.count = Some(
+                    usize::try_from(count)
+                        .map_err(|_| anyhow::anyhow!("COUNT can't be negative"))?,
+                );
+            }
+            "maxlen" => {
+                let maxlen = parse_option_number(&mut iter, "MAXLEN")?;
+                options.maxlen = usize::try_from(maxlen)
+                    .map_err(|_| anyhow::anyhow!("MAXLEN can't be negative"))?;
+            }
+            _ => {
+                return Err(anyhow::anyhow!("{option} is not a valid option"));
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+/// Finds the indices of the elements matching the options.
+fn find_matches(list: &[String], options: &LposOptions) -> Vec<usize> {
+    let limit = match options.count {
+        Some(0) => usize::MAX,
+        Some(count) => count,
+        None => 1,
+    };
+    let maxlen = match options.maxlen {
+        0 => list.len(),
+        maxlen => maxlen,
+    };
+    let indices: Box<dyn Iterator<Item = usize>> = if options.rank > 0 {
+        Box::new(0..list.len())
+    } else {
+        Box::new((0..list.len()).rev())
+    };
+
+    indices
+        .take(maxlen)
+        .filter(|i| list[*i] == options.value)
+        .skip(options.rank.unsigned_abs() as usize - 1)
+        .take(limit)
+        .collect()
+}
+
+pub struct Lpos;
+
+#[async_trait::async_trait]
+impl Command for Lpos {
+    fn static_name() -> String {
+        "LPOS".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the LPOS command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let options = match parse_lpos_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'LPOS' command"));
+            }
+        };
+
+        let store = store.read().await;
+        let matches = match store.get(&options.key) {
+            Some(crate::store::Entry {
+                value: crate::store::EntryValue::List(list),
+                ..
+            }) => find_matches(list, &options),
+            Some(_) => return crate::commands::wrong_type_error(),
+            None => vec![],
+        };
+
+        match (options.count, matches.first()) {
+            (Some(_), _) => crate::resp::RespType::Array(
+                matches
+                    .into_iter()
+                    .map(|i| crate::resp::RespType::Integer(i as i64))
+                    .collect(),
+            ),
+            (None, Some(i)) => crate::resp::RespType::Integer(*i as i64),
+            (None, None) => crate::resp::RespType::Null(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn values() -> Vec<String> {
+        ["a", "b", "c", "b", "a", "b"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    async fn insert_list(store: &crate::store::SharedStore, key: &str, values: &[String]) {
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.extend(values.iter().cloned());
+        }
+        store.write().await.insert(key.into(), entry);
+    }
+
+    fn make_indices(indices: &[i64]) -> crate::resp::RespType {
+        crate::resp::RespType::Array(
+            indices
+                .iter()
+                .map(|i| crate::resp::RespType::Integer(*i))
+                .collect(),
+        )
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("LPOS", Lpos::static_name());
+    }
+
+    #[rstest]
+    #[case::single(&["key", "b"], crate::resp::RespType::Integer(1))]
+    #[case::first(&["key", "a"], crate::resp::RespType::Integer(0))]
+    #[case::rank(&["key", "b", "RANK", "2"], crate::resp::RespType::Integer(3))]
+    #[case::negative_rank(&["key", "b", "RANK", "-1"], crate::resp::RespType::Integer(5))]
+    #[case::negative_rank_second(&["key", "b", "rank", "-2"], crate::resp::RespType::Integer(3))]
+    #[case::rank_too_large(&["key", "b", "RANK", "4"], crate::resp::RespType::Null())]
+    #[case::count(&["key", "b", "COUNT", "2"], make_indices(&[1, 3]))]
+    #[case::count_all(&["key", "b", "COUNT", "0"], make_indices(&[1, 3, 5]))]
+    #[case::count_negative_rank(&["key", "b", "RANK", "-1", "COUNT", "2"], make_indices(&[5, 3]))]
+    #[case::count_no_match(&["key", "z", "COUNT", "0"], make_indices(&[]))]
+    #[case::maxlen(&["key", "c", "MAXLEN", "2"], crate::resp::RespType::Null())]
+    #[case::maxlen_match(&["key", "c", "MAXLEN", "3"], crate::resp::RespType::Integer(2))]
+    #[case::maxlen_negative_rank(&["key", "b", "RANK", "-1", "COUNT", "0", "MAXLEN", "3"], make_indices(&[5, 3]))]
+    #[case::missing_value(&["key", "z"], crate::resp::RespType::Null())]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] args: &[&str],
+        #[case] expected: crate::resp::RespType,
+    ) {
+        insert_list(&store, &key, &values()).await;
+        let response = Lpos.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(expected, response);
+    }
+
+    #[rstest]
+    #[case::single(&["key", "a"], crate::resp::RespType::Null())]
+    #[case::count(&["key", "a", "COUNT", "0"], make_indices(&[]))]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected: crate::resp::RespType,
+    ) {
+        let response = Lpos.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(expected, response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Lpos
+            .handle(make_args(&[&key, "value"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(&[], "ERR Missing key for 'LPOS' command")]
+    #[case::missing_value(&["key"], "ERR Missing value for 'LPOS' command")]
+    #[case::invalid_option(&["key", "a", "invalid"], "ERR invalid is not a valid option for 'LPOS' command")]
+    #[case::missing_rank(&["key", "a", "RANK"], "ERR Missing value for RANK option for 'LPOS' command")]
+    #[case::zero_rank(&["key", "a", "RANK", "0"], "ERR RANK can't be zero for 'LPOS' command")]
+    #[case::invalid_rank(&["key", "a", "RANK", "abc"], "ERR Failed to convert RANK value to a number for 'LPOS' command")]
+    #[case::negative_count(&["key", "a", "COUNT", "-1"], "ERR COUNT can't be negative for 'LPOS' command")]
+    #[case::negative_maxlen(&["key", "a", "MAXLEN", "-1"], "ERR MAXLEN can't be negative for 'LPOS' command")]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected: &str,
+    ) {
+        let response = Lpos.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
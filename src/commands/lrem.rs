@@ -0,0 +1,279 @@
+//! This module contains the LREM command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the LREM options.
+fn parse_lrem_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, i64, String)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let count = crate::resp::extract_string(&iter.next().context("Missing count")?)
+        .context("Failed to extract count")?
+        .parse::<i64>()
+        .context("Failed to convert count to a number")?;
+
+    let value = crate::resp::extract_string(&iter.next().context("Missing value")?)
+        .context("Failed to extract value")?;
+
+    Ok((key, count, value))
+}
+
+/// Removes up to `limit` elements matching the value, returning the number removed.
+/// Elements are removed from the head unless `from_tail` is set.
+///
+/// The list is rebuilt in a single pass. When removing from the tail, the matches are counted first
+/// so that the ones closest to the head can be kept.
+fn remove_matching(list: &mut Vec<String>, value: &str, limit: usize, from_tail: bool) -> usize {
+    let mut kept = if from_tail {
+        list.iter()
+            .filter(|element| *element == value)
+            .count()
+            .saturating_sub(limit)
+    } else {
+        0
+    };
+    let mut removed = 0;
+    list.retain(|element| {
+        if element != value || removed == limit {
+            true
+        } else if kept > 0 {
+            kept -= 1;
+            true
+        } else {
+            removed += 1;
+            false
+        }
+    });
+    removed
+}
+
+pub struct Lrem;
+
+#[async_trait::async_trait]
+impl Command for Lrem {
+    fn static_name() -> String {
+        "LREM".into()
+    }
+
+    fn arity(&self) -> i64 {
+        4
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the LREM command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, count, value) = match parse_lrem_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'LREM' command"));
+            }
+        };
+
+        let mut store = store.write().await;
+        let Some(entry) = store.get_entry(&key) else {
+            return crate::resp::RespType::Integer(0);
+        };
+        let list = match &mut entry.value {
+            crate::store::EntryValue::List(list) => list,
+            _ => return crate::commands::wrong_type_error(),
+        };
+
+        let limit = match count {
+            0 => usize::MAX,
+            count => count.unsigned_abs() as usize,
+        };
+        let removed = remove_matching(list, &value, limit, count < 0);
+        let is_empty = list.is_empty();
+        store.track_resize(removed * value.len(), 0);
+        if is_empty {
+            store.remove(&key);
+        }
+
+        crate::resp::RespType::Integer(removed as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    #[fixture]
+    fn values() -> Vec<String> {
+        ["a", "b", "a", "c", "a"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    fn make_args(key: &str, count: &str, value: &str) -> Vec<crate::resp::RespType> {
+        vec![
+            crate::resp::RespType::BulkString(Some(key.to_string().into())),
+            crate::resp::RespType::BulkString(Some(count.to_string().into())),
+            crate::resp::RespType::BulkString(Some(value.to_string().into())),
+        ]
+    }
+
+    async fn insert_list(store: &crate::store::SharedStore, key: &str, values: &[String]) {
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.extend(values.iter().cloned());
+        }
+        store.write().await.insert(key.into(), entry);
+    }
+
+    async fn get_list(store: &crate::store::SharedStore, key: &str) -> Vec<String> {
+        match &store.write().await.get(key).unwrap().value {
+            crate::store::EntryValue::List(list) => list.clone(),
+            _ => panic!("Unexpected type"),
+        }
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("LREM", Lrem::static_name());
+    }
+
+    #[rstest]
+    #[case::positive("2", "a", 2, &["b", "c", "a"])]
+    #[case::positive_more_than_matches("10", "a", 3, &["b", "c"])]
+    #[case::negative("-2", "a", 2, &["a", "b", "c"])]
+    #[case::negative_single("-1", "a", 1, &["a", "b", "a", "c"])]
+    #[case::negative_more_than_matches("-10", "a", 3, &["b", "c"])]
+    #[case::zero("0", "a", 3, &["b", "c"])]
+    #[case::no_match("0", "d", 0, &["a", "b", "a", "c", "a"])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] count: &str,
+        #[case] value: &str,
+        #[case] expected_removed: i64,
+        #[case] expected: &[&str],
+    ) {
+        insert_list(&store, &key, &values()).await;
+        let response = Lrem
+            .handle(make_args(&key, count, value), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(expected_removed), response);
+        assert_eq!(expected, get_list(&store, &key).await);
+        let store = store.read().await;
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    #[rstest]
+    #[case::zero("0")]
+    #[case::positive("2")]
+    #[case::negative("-2")]
+    #[tokio::test]
+    async fn test_handle_empties_list(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] count: &str,
+    ) {
+        insert_list(&store, &key, &["a".into(), "a".into()]).await;
+        let response = Lrem
+            .handle(make_args(&key, count, "a"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(2), response);
+        assert!(store.write().await.get(&key).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Lrem
+            .handle(make_args(&key, "0", "a"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Lrem
+            .handle(make_args(&key, "0", "value"), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'LREM' command")]
+    #[case::missing_count(
+        vec![crate::resp::RespType::BulkString(Some("key".into()))],
+        "ERR Missing count for 'LREM' command"
+    )]
+    #[case::non_numeric_count(
+        make_args("key", "abc", "a"),
+        "ERR Failed to convert count to a number for 'LREM' command"
+    )]
+    #[case::missing_value(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::BulkString(Some("0".into())),
+        ],
+        "ERR Missing value for 'LREM' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Lrem.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
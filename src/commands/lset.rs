@@ -0,0 +1,257 @@
+//! This module contains the LSET command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the LSET options.
+fn parse_lset_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, i64, String)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let index = crate::resp::extract_string(&iter.next().context("Missing index")?)
+        .context("Failed to extract index")?
+        .parse::<i64>()
+        .context("Failed to convert index to a number")?;
+
+    let value = crate::resp::extract_string(&iter.next().context("Missing value")?)
+        .context("Failed to extract value")?;
+
+    Ok((key, index, value))
+}
+
+pub struct Lset;
+
+#[async_trait::async_trait]
+impl Command for Lset {
+    fn static_name() -> String {
+        "LSET".into()
+    }
+
+    fn arity(&self) -> i64 {
+        4
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "denyoom"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the LSET command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, index, value) = match parse_lset_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'LSET' command"));
+            }
+        };
+
+        let mut store = store.write().await;
+        let Some(entry) = store.get_entry(&key) else {
+            return crate::resp::RespType::SimpleError("ERR no such key".into());
+        };
+        let list = match &mut entry.value {
+            crate::store::EntryValue::List(list) => list,
+            _ => return crate::commands::wrong_type_error(),
+        };
+
+        match crate::commands::resolve_index(index, list.len()) {
+            Some(index) => {
+                let taken = value.len();
+                let previous = std::mem::replace(&mut list[index], value);
+                store.track_resize(previous.len(), taken);
+                crate::resp::RespType::SimpleString("OK".into())
+            }
+            None => crate::resp::RespType::SimpleError("ERR index out of range".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    #[fixture]
+    fn values() -> Vec<String> {
+        (0..5).map(|i| format!("value {i}")).collect()
+    }
+
+    fn make_args(key: &str, index: &str, value: &str) -> Vec<crate::resp::RespType> {
+        vec![
+            crate::resp::RespType::BulkString(Some(key.to_string().into())),
+            crate::resp::RespType::BulkString(Some(index.to_string().into())),
+            crate::resp::RespType::BulkString(Some(value.to_string().into())),
+        ]
+    }
+
+    async fn insert_list(store: &crate::store::SharedStore, key: &str, values: &[String]) {
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.extend(values.iter().cloned());
+        }
+        store.write().await.insert(key.into(), entry);
+    }
+
+    async fn get_list(store: &crate::store::SharedStore, key: &str) -> Vec<String> {
+        match &store.write().await.get(key).unwrap().value {
+            crate::store::EntryValue::List(list) => list.clone(),
+            _ => panic!("Unexpected type"),
+        }
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("LSET", Lset::static_name());
+    }
+
+    #[rstest]
+    #[case::first("0", 0)]
+    #[case::positive("2", 2)]
+    #[case::negative_last("-1", 4)]
+    #[case::negative("-4", 1)]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        values: Vec<String>,
+        #[case] index: &str,
+        #[case] expected_index: usize,
+    ) {
+        insert_list(&store, &key, &values).await;
+        let response = Lset
+            .handle(make_args(&key, index, "new"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+
+        let mut expected = values;
+        expected[expected_index] = "new".into();
+        assert_eq!(expected, get_list(&store, &key).await);
+        let store = store.read().await;
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::out_of_range("5")]
+    #[case::negative_out_of_range("-6")]
+    #[tokio::test]
+    async fn test_handle_out_of_range(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        values: Vec<String>,
+        #[case] index: &str,
+    ) {
+        insert_list(&store, &key, &values).await;
+        let response = Lset
+            .handle(make_args(&key, index, "new"), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR index out of range".into()),
+            response
+        );
+        assert_eq!(values, get_list(&store, &key).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Lset
+            .handle(make_args(&key, "0", "new"), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR no such key".into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Lset
+            .handle(make_args(&key, "0", "new"), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'LSET' command")]
+    #[case::missing_index(
+        vec![crate::resp::RespType::BulkString(Some("key".into()))],
+        "ERR Missing index for 'LSET' command"
+    )]
+    #[case::non_numeric_index(
+        make_args("key", "abc", "new"),
+        "ERR Failed to convert index to a number for 'LSET' command"
+    )]
+    #[case::missing_value(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::BulkString(Some("0".into())),
+        ],
+        "ERR Missing value for 'LSET' command"
+    )]
+    #[case::invalid_value(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::BulkString(Some("0".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract value for 'LSET' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Lset.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
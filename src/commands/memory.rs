@@ -0,0 +1,348 @@
+//! This module contains the MEMORY command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The MEMORY subcommands' usages and descriptions.
+const SUBCOMMANDS: [(&str, &str); 2] = [
+    (
+        "STATS",
+        "Return the number of keys and the memory used by the dataset.",
+    ),
+    (
+        "USAGE <key> [SAMPLES <count>]",
+        "Return the number of bytes of memory used by the key and its value.",
+    ),
+];
+
+/// The MEMORY subcommands.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Stats,
+    Usage(String),
+}
+
+/// Parses the MEMORY options.
+fn parse_memory_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Subcommand> {
+    let mut iter = iter.into_iter();
+
+    let subcommand = crate::resp::extract_string(&iter.next().context("Missing subcommand")?)
+        .context("Failed to extract subcommand")?;
+
+    match subcommand.to_lowercase().as_str() {
+        "stats" => Ok(Subcommand::Stats),
+        "usage" => {
+            let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+                .context("Failed to extract key")?;
+            while let Some(token) = iter.next() {
+                let option =
+                    crate::resp::extract_string(&token).context("Failed to extract option")?;
+                match option.to_lowercase().as_str() {
+                    // Every element is counted, so the number of sampled elements is ignored.
+                    "samples" => {
+                        crate::resp::extract_string(&iter.next().context("Missing count")?)
+                            .context("Failed to extract count")?
+                            .parse::<u64>()
+                            .context("Failed to convert count to a non-negative number")?;
+                    }
+                    _ => return Err(anyhow::anyhow!("{option} is not a valid option")),
+                }
+            }
+            Ok(Subcommand::Usage(key))
+        }
+        _ => Err(anyhow::anyhow!("Unknown subcommand '{subcommand}'")),
+    }
+}
+
+pub struct Memory {
+    databases: crate::store::SharedDatabases,
+}
+
+impl Memory {
+    /// Creates the MEMORY command, reporting the memory used by the databases.
+    pub fn new(databases: crate::store::SharedDatabases) -> Self {
+        Self { databases }
+    }
+
+    /// Builds the reply of MEMORY STATS, with the totals over every database followed by the
+    /// number of keys and keys with a time to live of every database holding keys.
+    async fn stats(&self) -> crate::resp::RespType {
+        let mut keys = 0;
+        let mut dataset_bytes = 0;
+        let mut databases = vec![];
+        for (db, store) in self.databases.iter().enumerate() {
+            let store = store.read().await;
+            let (mut db_keys, mut db_expires) = (0, 0);
+            for (key, entry) in store.iter() {
+                db_keys += 1;
+                if entry.deletion_time.is_some() {
+                    db_expires += 1;
+                }
+                dataset_bytes += entry.memory_usage(key);
+            }
+            if db_keys > 0 {
+                databases.push((db, db_keys, db_expires));
+            }
+            keys += db_keys;
+        }
+
+        let mut stats = vec![
+            (
+                "keys.count".to_string(),
+                crate::resp::RespType::Integer(keys),
+            ),
+            (
+                "keys.bytes-per-key".to_string(),
+                crate::resp::RespType::Integer(dataset_bytes as i64 / keys.max(1)),
+            ),
+            (
+                "dataset.bytes".to_string(),
+                crate::resp::RespType::Integer(dataset_bytes as i64),
+            ),
+        ];
+        for (db, db_keys, db_expires) in databases {
+            stats.push((
+                format!("db.{db}"),
+                crate::resp::RespType::Map(vec![
+                    (
+                        crate::resp::RespType::BulkString(Some("keys".into())),
+                        crate::resp::RespType::Integer(db_keys),
+                    ),
+                    (
+                        crate::resp::RespType::BulkString(Some("expires".into())),
+                        crate::resp::RespType::Integer(db_expires),
+                    ),
+                ]),
+            ));
+        }
+        crate::resp::RespType::Map(
+            stats
+                .into_iter()
+                .map(|(name, value)| (crate::resp::RespType::BulkString(Some(name.into())), value))
+                .collect(),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Memory {
+    fn static_name() -> String {
+        "MEMORY".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (2, 2, 1)
+    }
+
+    /// Handles the MEMORY command.
+    ///
+    /// The memory used is an estimate, counting the contents and headers of the strings held but
+    /// not the allocator's overhead. Inspecting a key does not mark it as accessed.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        if let Some(response) = crate::commands::dispatch_subcommand("MEMORY", &args, &SUBCOMMANDS)
+        {
+            return response;
+        }
+        let subcommand = match parse_memory_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'MEMORY' command"
+                ));
+            }
+        };
+
+        match subcommand {
+            Subcommand::Stats => self.stats().await,
+            Subcommand::Usage(key) => match store.read().await.peek(&key) {
+                Some(entry) => crate::resp::RespType::Integer(entry.memory_usage(&key) as i64),
+                None => crate::resp::RespType::Null(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn databases() -> crate::store::SharedDatabases {
+        crate::store::new_databases(3)
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    fn make_pair(
+        name: &str,
+        value: crate::resp::RespType,
+    ) -> (crate::resp::RespType, crate::resp::RespType) {
+        (
+            crate::resp::RespType::BulkString(Some(name.to_string().into())),
+            value,
+        )
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("MEMORY", Memory::static_name());
+    }
+
+    #[rstest]
+    #[case::usage(&["USAGE", "key"])]
+    #[case::samples(&["usage", "key", "SAMPLES", "5"])]
+    #[tokio::test]
+    async fn test_handle_usage(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+    ) {
+        let entry = crate::store::Entry::new_string("value");
+        let expected = entry.memory_usage("key") as i64;
+        databases[0].write().await.insert("key".into(), entry);
+
+        let response = Memory::new(databases.clone())
+            .handle(make_args(args), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(expected), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_usage_missing_key(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+    ) {
+        let response = Memory::new(databases.clone())
+            .handle(make_args(&["USAGE", "key"]), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Null(), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_stats(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+    ) {
+        let string = crate::store::Entry::new_string("value");
+        let list = crate::store::Entry::new_list().with_deletion(10000u64);
+        let dataset_bytes = string.memory_usage("a") + 2 * list.memory_usage("b");
+        databases[0].write().await.insert("a".into(), string);
+        databases[0].write().await.insert("b".into(), list.clone());
+        databases[2].write().await.insert("c".into(), list);
+
+        let response = Memory::new(databases.clone())
+            .handle(make_args(&["STATS"]), &databases[0], &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Map(vec![
+                make_pair("keys.count", crate::resp::RespType::Integer(3)),
+                make_pair(
+                    "keys.bytes-per-key",
+                    crate::resp::RespType::Integer(dataset_bytes as i64 / 3)
+                ),
+                make_pair(
+                    "dataset.bytes",
+                    crate::resp::RespType::Integer(dataset_bytes as i64)
+                ),
+                make_pair(
+                    "db.0",
+                    crate::resp::RespType::Map(vec![
+                        make_pair("keys", crate::resp::RespType::Integer(2)),
+                        make_pair("expires", crate::resp::RespType::Integer(1)),
+                    ])
+                ),
+                make_pair(
+                    "db.2",
+                    crate::resp::RespType::Map(vec![
+                        make_pair("keys", crate::resp::RespType::Integer(1)),
+                        make_pair("expires", crate::resp::RespType::Integer(1)),
+                    ])
+                ),
+            ]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_stats_empty(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+    ) {
+        let response = Memory::new(databases.clone())
+            .handle(make_args(&["stats"]), &databases[0], &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Map(vec![
+                make_pair("keys.count", crate::resp::RespType::Integer(0)),
+                make_pair("keys.bytes-per-key", crate::resp::RespType::Integer(0)),
+                make_pair("dataset.bytes", crate::resp::RespType::Integer(0)),
+            ]),
+            response
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_key(make_args(&["USAGE"]), "ERR Missing key for 'MEMORY' command")]
+    #[case::missing_count(
+        make_args(&["USAGE", "key", "SAMPLES"]),
+        "ERR Missing count for 'MEMORY' command"
+    )]
+    #[case::invalid_count(
+        make_args(&["USAGE", "key", "SAMPLES", "-1"]),
+        "ERR Failed to convert count to a non-negative number for 'MEMORY' command"
+    )]
+    #[case::unknown_option(
+        make_args(&["USAGE", "key", "invalid"]),
+        "ERR invalid is not a valid option for 'MEMORY' command"
+    )]
+    #[case::unknown_subcommand(
+        make_args(&["DOCTOR"]),
+        "ERR Unknown subcommand or wrong number of arguments for 'DOCTOR'. Try MEMORY HELP."
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Memory::new(databases.clone())
+            .handle(args, &databases[0], &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
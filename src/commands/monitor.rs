@@ -0,0 +1,64 @@
+//! This module contains the MONITOR command.
+use crate::commands::Command;
+
+pub struct Monitor;
+
+#[async_trait::async_trait]
+impl Command for Monitor {
+    fn static_name() -> String {
+        "MONITOR".into()
+    }
+
+    fn arity(&self) -> i64 {
+        1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["admin", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the MONITOR command.
+    ///
+    /// Once the reply is written, the connection streams every command run by the server until
+    /// it disconnects.
+    async fn handle(
+        &self,
+        _: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        state.monitoring = true;
+        crate::resp::RespType::SimpleString("OK".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("MONITOR", Monitor::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let response = Monitor.handle(vec![], &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert!(state.monitoring);
+    }
+}
@@ -0,0 +1,274 @@
+//! This module contains the MOVE command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The MOVE options.
+#[derive(Debug, PartialEq)]
+struct MoveOptions {
+    key: String,
+    /// The index of the destination database.
+    db: usize,
+}
+
+/// Parses the MOVE options.
+fn parse_move_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<MoveOptions> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+    let db = crate::resp::extract_string(&iter.next().context("Missing db")?)
+        .context("Failed to extract db")?
+        .parse::<usize>()
+        .context("Failed to convert db to a number")?;
+
+    Ok(MoveOptions { key, db })
+}
+
+pub struct Move {
+    databases: crate::store::SharedDatabases,
+}
+
+impl Move {
+    /// Creates the MOVE command for the databases.
+    pub fn new(databases: crate::store::SharedDatabases) -> Self {
+        Self { databases }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Move {
+    fn static_name() -> String {
+        "MOVE".into()
+    }
+
+    fn arity(&self) -> i64 {
+        3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the MOVE command.
+    ///
+    /// The key keeps its value and time to live. It is not moved if it is missing or the
+    /// destination database already holds it. Both databases are locked in index order so that
+    /// concurrent moves cannot deadlock.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let options = match parse_move_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'MOVE' command"));
+            }
+        };
+
+        if options.db >= self.databases.len() {
+            return crate::resp::RespType::SimpleError("ERR DB index is out of range".into());
+        }
+        if options.db == state.db {
+            return crate::resp::RespType::SimpleError(
+                "ERR source and destination objects are the same".into(),
+            );
+        }
+
+        let (low, high) = (state.db.min(options.db), state.db.max(options.db));
+        let mut low = self.databases[low].write().await;
+        let mut high = self.databases[high].write().await;
+        let (source_store, destination_store) = if state.db < options.db {
+            (&mut low, &mut high)
+        } else {
+            (&mut high, &mut low)
+        };
+        if destination_store.peek(&options.key).is_some() {
+            return crate::resp::RespType::Integer(0);
+        }
+        match source_store.remove(&options.key) {
+            Some(entry) => {
+                destination_store.insert(options.key, entry);
+                crate::resp::RespType::Integer(1)
+            }
+            None => crate::resp::RespType::Integer(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn databases() -> crate::store::SharedDatabases {
+        crate::store::new_databases(3)
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("MOVE", Move::static_name());
+    }
+
+    #[rstest]
+    #[case::higher_db(0, "2")]
+    #[case::lower_db(2, "1")]
+    #[tokio::test]
+    async fn test_handle(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] source_db: usize,
+        #[case] destination_db: &str,
+    ) {
+        tokio::time::pause();
+        state.db = source_db;
+        let entry = crate::store::Entry::new_string("value").with_deletion(100_u64);
+        databases[source_db]
+            .write()
+            .await
+            .insert("key".into(), entry.clone());
+
+        let response = Move::new(databases.clone())
+            .handle(
+                make_args(&["key", destination_db]),
+                &databases[source_db],
+                &mut state,
+            )
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+
+        assert_eq!(None, databases[source_db].read().await.peek("key"));
+        let destination_db = destination_db.parse::<usize>().unwrap();
+        assert_eq!(
+            Some(&entry),
+            databases[destination_db].read().await.peek("key")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_existing_destination(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+    ) {
+        databases[0]
+            .write()
+            .await
+            .insert("key".into(), crate::store::Entry::new_string("new value"));
+        databases[1]
+            .write()
+            .await
+            .insert("key".into(), crate::store::Entry::new_string("old value"));
+
+        let response = Move::new(databases.clone())
+            .handle(make_args(&["key", "1"]), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+        assert_eq!(
+            Some(&crate::store::Entry::new_string("new value")),
+            databases[0].read().await.peek("key")
+        );
+        assert_eq!(
+            Some(&crate::store::Entry::new_string("old value")),
+            databases[1].read().await.peek("key")
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+    ) {
+        let response = Move::new(databases.clone())
+            .handle(make_args(&["key", "1"]), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+        assert_eq!(0, databases[1].read().await.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_expired_key(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+    ) {
+        tokio::time::pause();
+        databases[0].write().await.insert(
+            "key".into(),
+            crate::store::Entry::new_string("value").with_deletion(100_u64),
+        );
+        tokio::time::advance(tokio::time::Duration::from_millis(100)).await;
+
+        let response = Move::new(databases.clone())
+            .handle(make_args(&["key", "1"]), &databases[0], &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+        assert_eq!(0, databases[1].read().await.len());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'MOVE' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'MOVE' command"
+    )]
+    #[case::missing_db(make_args(&["key"]), "ERR Missing db for 'MOVE' command")]
+    #[case::non_numeric_db(
+        make_args(&["key", "abc"]),
+        "ERR Failed to convert db to a number for 'MOVE' command"
+    )]
+    #[case::negative_db(
+        make_args(&["key", "-1"]),
+        "ERR Failed to convert db to a number for 'MOVE' command"
+    )]
+    #[case::db_out_of_range(make_args(&["key", "3"]), "ERR DB index is out of range")]
+    #[case::same_db(
+        make_args(&["key", "0"]),
+        "ERR source and destination objects are the same"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        databases: crate::store::SharedDatabases,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        databases[0]
+            .write()
+            .await
+            .insert("key".into(), crate::store::Entry::new_string("value"));
+
+        let response = Move::new(databases.clone())
+            .handle(args, &databases[0], &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        assert_eq!(1, databases[0].read().await.len());
+    }
+}
@@ -0,0 +1,442 @@
+//! This module contains the OBJECT command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The maximum length of a string to be reported with the embedded string encoding.
+const EMBSTR_MAX_LENGTH: usize = 44;
+/// The maximum length of a hash or set to be reported with the listpack encoding.
+const LISTPACK_MAX_LENGTH: usize = 128;
+/// The maximum size of a set of integers to be reported with the intset encoding.
+const INTSET_MAX_LENGTH: usize = 512;
+
+/// The OBJECT subcommands' usages and descriptions.
+const SUBCOMMANDS: [(&str, &str); 3] = [
+    (
+        "ENCODING <key>",
+        "Return the kind of internal representation used to store the value of the key.",
+    ),
+    (
+        "IDLETIME <key>",
+        "Return the number of seconds since the key was last accessed.",
+    ),
+    (
+        "REFCOUNT <key>",
+        "Return the number of references to the value of the key.",
+    ),
+];
+
+/// The OBJECT subcommands.
+#[derive(Debug, PartialEq)]
+enum Subcommand {
+    Encoding(String),
+    Idletime(String),
+    Refcount(String),
+}
+
+/// Parses the OBJECT options.
+fn parse_object_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Subcommand> {
+    let mut iter = iter.into_iter();
+
+    let subcommand = crate::resp::extract_string(&iter.next().context("Missing subcommand")?)
+        .context("Failed to extract subcommand")?;
+
+    let subcommand = match subcommand.to_lowercase().as_str() {
+        "encoding" => Subcommand::Encoding,
+        "idletime" => Subcommand::Idletime,
+        "refcount" => Subcommand::Refcount,
+        _ => return Err(anyhow::anyhow!("Unknown subcommand '{subcommand}'")),
+    };
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+    Ok(subcommand(key))
+}
+
+/// Gets the name of the encoding that would be used for the entry value, where lists longer than
+/// `list_max_listpack_size` use the quicklist encoding.
+pub fn encoding(value: &crate::store::EntryValue, list_max_listpack_size: usize) -> &'static str {
+    match value {
+        crate::store::EntryValue::String(value)
+            if std::str::from_utf8(value).is_ok_and(|value| value.parse::<i64>().is_ok()) =>
+        {
+            "int"
+        }
+        crate::store::EntryValue::String(value) if value.len() <= EMBSTR_MAX_LENGTH => "embstr",
+        crate::store::EntryValue::String(_) => "raw",
+        crate::store::EntryValue::List(list) if list.len() <= list_max_listpack_size => "listpack",
+        crate::store::EntryValue::List(_) => "quicklist",
+        crate::store::EntryValue::Hash(hash) if hash.len() <= LISTPACK_MAX_LENGTH => "listpack",
+        crate::store::EntryValue::Hash(_) => "hashtable",
+        crate::store::EntryValue::Set(set)
+            if set.len() <= INTSET_MAX_LENGTH
+                && set.iter().all(|member| member.parse::<i64>().is_ok()) =>
+        {
+            "intset"
+        }
+        crate::store::EntryValue::Set(set) if set.len() <= LISTPACK_MAX_LENGTH => "listpack",
+        crate::store::EntryValue::Set(_) => "hashtable",
+    }
+}
+
+pub struct Object {
+    config: crate::config::SharedConfig,
+}
+
+impl Object {
+    /// Creates the OBJECT command, reporting list encodings according to the configuration.
+    pub fn new(config: crate::config::SharedConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Object {
+    fn static_name() -> String {
+        "OBJECT".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (2, 2, 1)
+    }
+
+    /// Handles the OBJECT command.
+    ///
+    /// Inspecting a key does not mark it as accessed.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        if let Some(response) = crate::commands::dispatch_subcommand("OBJECT", &args, &SUBCOMMANDS)
+        {
+            return response;
+        }
+        let subcommand = match parse_object_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'OBJECT' command"
+                ));
+            }
+        };
+
+        let list_max_listpack_size = self.config.read().await.list_max_listpack_size;
+        let store = store.read().await;
+        match subcommand {
+            Subcommand::Encoding(key) => match store.peek(&key) {
+                Some(entry) => crate::resp::RespType::BulkString(Some(
+                    encoding(&entry.value, list_max_listpack_size).into(),
+                )),
+                None => crate::resp::RespType::SimpleError("ERR no such key".into()),
+            },
+            Subcommand::Idletime(key) => match store.peek(&key) {
+                Some(entry) => {
+                    crate::resp::RespType::Integer(entry.last_access.idle_time().as_secs() as i64)
+                }
+                None => crate::resp::RespType::SimpleError("ERR no such key".into()),
+            },
+            // Values are never shared between keys.
+            Subcommand::Refcount(key) => match store.peek(&key) {
+                Some(_) => crate::resp::RespType::Integer(1),
+                None => crate::resp::RespType::SimpleError("ERR no such key".into()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_list(length: usize) -> crate::store::Entry {
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.extend((0..length).map(|i| i.to_string()));
+        }
+        entry
+    }
+
+    fn make_hash(length: usize) -> crate::store::Entry {
+        let mut entry = crate::store::Entry::new_hash();
+        if let crate::store::EntryValue::Hash(hash) = &mut entry.value {
+            hash.extend((0..length).map(|i| (i.to_string(), i.to_string())));
+        }
+        entry
+    }
+
+    fn make_set<T: ToString>(members: impl Iterator<Item = T>) -> crate::store::Entry {
+        let mut entry = crate::store::Entry::new_set();
+        if let crate::store::EntryValue::Set(set) = &mut entry.value {
+            set.extend(members.map(|member| member.to_string()));
+        }
+        entry
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("OBJECT", Object::static_name());
+    }
+
+    #[rstest]
+    #[case::int(crate::store::Entry::new_string("12345"), "int")]
+    #[case::negative_int(crate::store::Entry::new_string("-12"), "int")]
+    #[case::short_string(crate::store::Entry::new_string("value"), "embstr")]
+    #[case::embstr_boundary(crate::store::Entry::new_string("a".repeat(EMBSTR_MAX_LENGTH)), "embstr")]
+    #[case::long_string(crate::store::Entry::new_string("a".repeat(EMBSTR_MAX_LENGTH + 1)), "raw")]
+    #[case::short_list(make_list(3), "listpack")]
+    #[case::list_boundary(make_list(128), "listpack")]
+    #[case::long_list(make_list(129), "quicklist")]
+    #[case::short_hash(make_hash(3), "listpack")]
+    #[case::long_hash(make_hash(LISTPACK_MAX_LENGTH + 1), "hashtable")]
+    #[case::int_set(make_set(0..INTSET_MAX_LENGTH), "intset")]
+    #[case::long_int_set(make_set(0..INTSET_MAX_LENGTH + 1), "hashtable")]
+    #[case::short_set(make_set(["a", "1"].iter()), "listpack")]
+    #[case::long_set(make_set((0..LISTPACK_MAX_LENGTH + 1).map(|i| format!("m{i}"))), "hashtable")]
+    #[tokio::test]
+    async fn test_handle_encoding(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] entry: crate::store::Entry,
+        #[case] expected: &str,
+    ) {
+        store.write().await.insert(key.clone(), entry);
+        let args = vec![
+            crate::resp::RespType::BulkString(Some("ENCODING".into())),
+            crate::resp::RespType::BulkString(Some(key.into())),
+        ];
+        let response = Object::new(crate::config::new())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some(expected.to_string().into())),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_encoding_list_threshold(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let config = crate::config::new();
+        config
+            .write()
+            .await
+            .set("list-max-listpack-size", "2")
+            .unwrap();
+        let object = Object::new(config);
+        let args = || {
+            vec![
+                crate::resp::RespType::BulkString(Some("ENCODING".into())),
+                crate::resp::RespType::BulkString(Some(key.clone().into())),
+            ]
+        };
+        let push = |value: &str| {
+            vec![
+                crate::resp::RespType::BulkString(Some(key.clone().into())),
+                crate::resp::RespType::BulkString(Some(value.to_string().into())),
+            ]
+        };
+
+        crate::commands::rpush::Rpush
+            .handle(push("a"), &store, &mut state)
+            .await;
+        crate::commands::rpush::Rpush
+            .handle(push("b"), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("listpack".into())),
+            object.handle(args(), &store, &mut state).await
+        );
+
+        crate::commands::rpush::Rpush
+            .handle(push("c"), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::BulkString(Some("quicklist".into())),
+            object.handle(args(), &store, &mut state).await
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_refcount(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let args = vec![
+            crate::resp::RespType::BulkString(Some("REFCOUNT".into())),
+            crate::resp::RespType::BulkString(Some(key.into())),
+        ];
+        let response = Object::new(crate::config::new())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_idletime(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        tokio::time::pause();
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let args = || {
+            vec![
+                crate::resp::RespType::BulkString(Some("IDLETIME".into())),
+                crate::resp::RespType::BulkString(Some(key.clone().into())),
+            ]
+        };
+        let response = Object::new(crate::config::new())
+            .handle(args(), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+
+        tokio::time::advance(tokio::time::Duration::from_secs(5)).await;
+        let response = Object::new(crate::config::new())
+            .handle(args(), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(5), response);
+
+        store.read().await.get(&key);
+        let response = Object::new(crate::config::new())
+            .handle(args(), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_help(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let args = vec![crate::resp::RespType::BulkString(Some("HELP".into()))];
+        match Object::new(crate::config::new())
+            .handle(args, &store, &mut state)
+            .await
+        {
+            crate::resp::RespType::Array(lines) => assert!(!lines.is_empty()),
+            response => panic!("Expected an array, got {response:?}."),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_unknown_subcommand(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let args = vec![crate::resp::RespType::BulkString(Some("FOO".into()))];
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR Unknown subcommand or wrong number of arguments for 'FOO'. Try OBJECT HELP."
+                    .into()
+            ),
+            Object::new(crate::config::new())
+                .handle(args, &store, &mut state)
+                .await
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::encoding("encoding")]
+    #[case::idletime("idletime")]
+    #[case::refcount("refcount")]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] subcommand: &str,
+    ) {
+        let args = vec![
+            crate::resp::RespType::BulkString(Some(subcommand.to_string().into())),
+            crate::resp::RespType::BulkString(Some(key.into())),
+        ];
+        let response = Object::new(crate::config::new())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR no such key".into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[case::missing_subcommand(vec![], "ERR Missing subcommand for 'OBJECT' command")]
+    #[case::invalid_subcommand(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract subcommand for 'OBJECT' command"
+    )]
+    #[case::unknown_subcommand(
+        vec![crate::resp::RespType::BulkString(Some("invalid".into()))],
+        "ERR Unknown subcommand or wrong number of arguments for 'invalid'. Try OBJECT HELP."
+    )]
+    #[case::missing_key(
+        vec![crate::resp::RespType::BulkString(Some("ENCODING".into()))],
+        "ERR Missing key for 'OBJECT' command"
+    )]
+    #[case::invalid_key(
+        vec![
+            crate::resp::RespType::BulkString(Some("ENCODING".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract key for 'OBJECT' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Object::new(crate::config::new())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
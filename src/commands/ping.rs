@@ -5,10 +5,18 @@ pub struct Ping;
 
 #[async_trait::async_trait]
 impl Command for Ping {
-    fn name(&self) -> String {
+    fn static_name() -> String {
         "PING".into()
     }
 
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["fast"]
+    }
+
     /// Handles the PING command.
     async fn handle(
         &self,
@@ -38,7 +46,7 @@ mod test {
     // --- Tests ---
     #[rstest]
     fn test_name() {
-        assert_eq!("PING", Ping.name());
+        assert_eq!("PING", Ping::static_name());
     }
 
     #[rstest]
@@ -0,0 +1,65 @@
+//! This module contains the QUIT command.
+use crate::commands::Command;
+
+pub struct Quit;
+
+#[async_trait::async_trait]
+impl Command for Quit {
+    fn static_name() -> String {
+        "QUIT".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["fast", "no_auth"]
+    }
+
+    /// Handles the QUIT command.
+    ///
+    /// The connection is closed once the reply is written.
+    async fn handle(
+        &self,
+        _: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        state.close_after_reply = true;
+        crate::resp::RespType::SimpleString("OK".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("QUIT", Quit::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(store: crate::store::SharedStore, mut state: crate::state::State) {
+        assert_eq!(
+            crate::resp::RespType::SimpleString("OK".into()),
+            Quit.handle(vec![], &store, &mut state).await
+        );
+        assert!(state.close_after_reply);
+    }
+}
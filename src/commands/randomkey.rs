@@ -0,0 +1,103 @@
+//! This module contains the RANDOMKEY command.
+use crate::commands::Command;
+
+pub struct Randomkey;
+
+#[async_trait::async_trait]
+impl Command for Randomkey {
+    fn static_name() -> String {
+        "RANDOMKEY".into()
+    }
+
+    fn arity(&self) -> i64 {
+        1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    /// Handles the RANDOMKEY command.
+    async fn handle(
+        &self,
+        _: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        match store.read().await.random_key() {
+            Some(key) => crate::resp::RespType::BulkString(Some(key.into())),
+            None => crate::resp::RespType::Null(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("RANDOMKEY", Randomkey::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let keys = (0..5).map(|i| format!("key {i}")).collect::<Vec<_>>();
+        for key in &keys {
+            store
+                .write()
+                .await
+                .insert(key.clone(), crate::store::Entry::new_string("value"));
+        }
+
+        for _ in 0..20 {
+            match Randomkey.handle(vec![], &store, &mut state).await {
+                crate::resp::RespType::BulkString(Some(key)) => {
+                    assert!(keys.iter().any(|expected| key == *expected))
+                }
+                response => panic!("Unexpected response: {:?}.", response),
+            }
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_empty(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let response = Randomkey.handle(vec![], &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Null(), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_all_expired(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        tokio::time::pause();
+        let duration = 10u64;
+        for i in 0..5 {
+            store.write().await.insert(
+                format!("key {i}"),
+                crate::store::Entry::new_string("value").with_deletion(duration),
+            );
+        }
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+
+        let response = Randomkey.handle(vec![], &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Null(), response);
+    }
+}
@@ -0,0 +1,355 @@
+//! This module contains the RENAME and RENAMENX commands.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The RENAME and RENAMENX options.
+#[derive(Debug, PartialEq)]
+struct RenameOptions {
+    source: String,
+    destination: String,
+}
+
+/// Parses the RENAME and RENAMENX options.
+fn parse_rename_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<RenameOptions> {
+    let mut iter = iter.into_iter();
+
+    let source = crate::resp::extract_string(&iter.next().context("Missing source")?)
+        .context("Failed to extract source")?;
+    let destination = crate::resp::extract_string(&iter.next().context("Missing destination")?)
+        .context("Failed to extract destination")?;
+
+    Ok(RenameOptions {
+        source,
+        destination,
+    })
+}
+
+/// Renames the source key to the destination key, keeping its value and time to live.
+///
+/// Returns whether the key was renamed, which it is not if `nx` and the destination exists.
+async fn rename(
+    args: Vec<crate::resp::RespType>,
+    store: &crate::store::SharedStore,
+    name: &str,
+    nx: bool,
+) -> std::result::Result<bool, crate::resp::RespType> {
+    let options = match parse_rename_options(args) {
+        Ok(result) => result,
+        Err(err) => {
+            log::error!("{err}");
+            return Err(crate::resp::RespType::SimpleError(format!(
+                "ERR {err} for '{name}' command"
+            )));
+        }
+    };
+
+    let mut store = store.write().await;
+    if store.peek(&options.source).is_none() {
+        return Err(crate::resp::RespType::SimpleError("ERR no such key".into()));
+    }
+    if options.source == options.destination {
+        return Ok(!nx);
+    }
+    if nx && store.peek(&options.destination).is_some() {
+        return Ok(false);
+    }
+    let entry = store.remove(&options.source).expect("Source should exist.");
+    store.insert(options.destination, entry);
+    Ok(true)
+}
+
+pub struct Rename;
+
+#[async_trait::async_trait]
+impl Command for Rename {
+    fn static_name() -> String {
+        "RENAME".into()
+    }
+
+    fn arity(&self) -> i64 {
+        3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 2, 1)
+    }
+
+    /// Handles the RENAME command.
+    ///
+    /// An existing destination is overwritten, along with its time to live.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        match rename(args, store, "RENAME", false).await {
+            Ok(_) => crate::resp::RespType::SimpleString("OK".into()),
+            Err(err) => err,
+        }
+    }
+}
+
+pub struct Renamenx;
+
+#[async_trait::async_trait]
+impl Command for Renamenx {
+    fn static_name() -> String {
+        "RENAMENX".into()
+    }
+
+    fn arity(&self) -> i64 {
+        3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 2, 1)
+    }
+
+    /// Handles the RENAMENX command.
+    ///
+    /// Returns 1 if the key was renamed, or 0 if the destination exists.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        match rename(args, store, "RENAMENX", true).await {
+            Ok(renamed) => crate::resp::RespType::Integer(renamed as i64),
+            Err(err) => err,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    async fn insert(store: &crate::store::SharedStore, key: &str, entry: crate::store::Entry) {
+        store.write().await.insert(key.into(), entry);
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("RENAME", Rename::static_name());
+        assert_eq!("RENAMENX", Renamenx::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_rename(store: crate::store::SharedStore, mut state: crate::state::State) {
+        tokio::time::pause();
+        let entry = crate::store::Entry::new_string("value").with_deletion(100_u64);
+        insert(&store, "source", entry.clone()).await;
+
+        let response = Rename
+            .handle(make_args(&["source", "destination"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        let store = store.read().await;
+        assert!(store.peek("source").is_none());
+        assert_eq!(Some(&entry), store.peek("destination"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_rename_existing_destination(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        tokio::time::pause();
+        let entry = crate::store::Entry::new_string("value");
+        insert(&store, "source", entry.clone()).await;
+        insert(
+            &store,
+            "destination",
+            crate::store::Entry::new_list().with_deletion(100_u64),
+        )
+        .await;
+
+        let response = Rename
+            .handle(make_args(&["source", "destination"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        let store = store.read().await;
+        assert!(store.peek("source").is_none());
+        assert_eq!(Some(&entry), store.peek("destination"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_rename_same_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let entry = crate::store::Entry::new_string("value");
+        insert(&store, "key", entry.clone()).await;
+
+        let response = Rename
+            .handle(make_args(&["key", "key"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(Some(&entry), store.read().await.peek("key"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_renamenx(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        tokio::time::pause();
+        let entry = crate::store::Entry::new_string("value").with_deletion(100_u64);
+        insert(&store, "source", entry.clone()).await;
+
+        let response = Renamenx
+            .handle(make_args(&["source", "destination"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+        let store = store.read().await;
+        assert!(store.peek("source").is_none());
+        assert_eq!(Some(&entry), store.peek("destination"));
+    }
+
+    #[rstest]
+    #[case::existing_destination(&["source", "destination"])]
+    #[case::same_key(&["source", "source"])]
+    #[tokio::test]
+    async fn test_handle_renamenx_not_renamed(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+    ) {
+        let source = crate::store::Entry::new_string("source");
+        let destination = crate::store::Entry::new_string("destination");
+        insert(&store, "source", source.clone()).await;
+        insert(&store, "destination", destination.clone()).await;
+
+        let response = Renamenx.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+        let store = store.read().await;
+        assert_eq!(Some(&source), store.peek("source"));
+        assert_eq!(Some(&destination), store.peek("destination"));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_renamenx_expired_destination(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        tokio::time::pause();
+        let duration = 10u64;
+        let entry = crate::store::Entry::new_string("value");
+        insert(&store, "source", entry.clone()).await;
+        insert(
+            &store,
+            "destination",
+            crate::store::Entry::new_string("expired").with_deletion(duration),
+        )
+        .await;
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+
+        let response = Renamenx
+            .handle(make_args(&["source", "destination"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+        assert_eq!(Some(&entry), store.read().await.peek("destination"));
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_source(vec![], "ERR Missing source for 'RENAME' command")]
+    #[case::invalid_source(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract source for 'RENAME' command"
+    )]
+    #[case::missing_destination(make_args(&["source"]), "ERR Missing destination for 'RENAME' command")]
+    #[case::no_such_key(make_args(&["missing", "destination"]), "ERR no such key")]
+    #[tokio::test]
+    async fn test_handle_rename_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Rename.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[case::missing_source(vec![], "ERR Missing source for 'RENAMENX' command")]
+    #[case::no_such_key(make_args(&["missing", "destination"]), "ERR no such key")]
+    #[tokio::test]
+    async fn test_handle_renamenx_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Renamenx.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_expired_source(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        tokio::time::pause();
+        let duration = 10u64;
+        insert(
+            &store,
+            "source",
+            crate::store::Entry::new_string("value").with_deletion(duration),
+        )
+        .await;
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+
+        let response = Rename
+            .handle(make_args(&["source", "destination"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR no such key".into()),
+            response
+        );
+        assert!(store.read().await.peek("destination").is_none());
+    }
+}
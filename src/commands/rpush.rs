@@ -12,7 +12,7 @@ fn parse_options<I: IntoIterator<Item = crate::resp::RespType>>(
         .context("Failed to extract key")?;
 
     let mut result = vec![];
-    while let Some(token) = iter.next() {
+    for token in iter {
         let value = crate::resp::extract_string(&token).context("Failed to extract value")?;
         result.push(value);
     }
@@ -27,10 +27,22 @@ pub struct Rpush;
 
 #[async_trait::async_trait]
 impl Command for Rpush {
-    fn name(&self) -> String {
+    fn static_name() -> String {
         "RPUSH".into()
     }
 
+    fn arity(&self) -> i64 {
+        -3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "denyoom", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
     /// Handles the RPUSH command.
     async fn handle(
         &self,
@@ -48,21 +60,17 @@ impl Command for Rpush {
             }
         };
 
-        let mut store = store.lock().await;
-        let entry_ref = store
-            .entry(key.clone())
-            .or_insert(crate::store::Entry::new_list());
+        let mut store = store.write().await;
+        let taken = values.iter().map(String::len).sum();
+        let entry_ref = store.get_or_insert(key.clone(), crate::store::Entry::new_list());
         let length = match &mut entry_ref.value {
             crate::store::EntryValue::List(list) => {
-                list.extend(values.into_iter());
+                list.extend(values);
                 list.len()
             }
-            _ => {
-                return crate::resp::RespType::SimpleError(format!(
-                    "WRONGTYPE Entry at key {key} is not a list"
-                ))
-            }
+            _ => return crate::commands::wrong_type_error(),
         };
+        store.track_resize(0, taken);
 
         crate::resp::RespType::Integer(length as i64)
     }
@@ -102,8 +110,8 @@ mod test {
         (0..10).map(|i| format!("existing {i}")).collect()
     }
 
-    fn make_args(key: &String, values: &Vec<String>) -> Vec<crate::resp::RespType> {
-        vec![crate::resp::RespType::SimpleString(key.clone())]
+    fn make_args(key: &str, values: &[String]) -> Vec<crate::resp::RespType> {
+        vec![crate::resp::RespType::SimpleString(key.into())]
             .into_iter()
             .chain(
                 values
@@ -116,7 +124,7 @@ mod test {
     // --- Tests ---
     #[rstest]
     fn test_name() {
-        assert_eq!("RPUSH", Rpush.name());
+        assert_eq!("RPUSH", Rpush::static_name());
     }
 
     #[rstest]
@@ -135,14 +143,14 @@ mod test {
         let expected = crate::resp::RespType::Integer(expected_length as i64);
         assert_eq!(expected, response);
 
-        let mut store = store.lock().await;
+        let store = store.read().await;
         let list = match &store.get(&key).unwrap().value {
             crate::store::EntryValue::List(list) => list,
             _ => panic!("Unexpected type"),
         };
 
         assert_eq!(expected_length, list.len());
-        for (expected, value) in values.into_iter().zip(list.into_iter()) {
+        for (expected, value) in values.into_iter().zip(list.iter()) {
             assert_eq!(expected, *value);
         }
     }
@@ -164,7 +172,7 @@ mod test {
             _ => unreachable!(),
         };
         list.extend(existing_values.clone());
-        store.lock().await.insert(key.clone(), entry);
+        store.write().await.insert(key.clone(), entry);
 
         let args = make_args(&key, &values);
         let mut expected = existing_values;
@@ -174,15 +182,16 @@ mod test {
         let expected_response = crate::resp::RespType::Integer(expected.len() as i64);
         assert_eq!(expected_response, response);
 
-        let mut store = store.lock().await;
+        let store = store.read().await;
         let list = match &store.get(&key).unwrap().value {
             crate::store::EntryValue::List(list) => list,
             _ => panic!("Unexpected type"),
         };
         assert_eq!(expected.len(), list.len());
-        for (expected, value) in expected.into_iter().zip(list.into_iter()) {
+        for (expected, value) in expected.into_iter().zip(list.iter()) {
             assert_eq!(expected, *value);
         }
+        assert_eq!(store.recount_memory(), store.used_memory());
     }
 
     // --- Errors ---
@@ -250,15 +259,15 @@ mod test {
         key: String,
         #[case] values: Vec<String>,
     ) {
-        store.lock().await.insert(
+        store.write().await.insert(
             key.clone(),
             crate::store::Entry::new_string("existing value"),
         );
 
         let args = make_args(&key, &values);
-        let expected = crate::resp::RespType::SimpleError(format!(
-            "WRONGTYPE Entry at key {key} is not a list"
-        ));
+        let expected = crate::resp::RespType::SimpleError(
+            "WRONGTYPE Operation against a key holding the wrong kind of value".into(),
+        );
         let response = Rpush.handle(args, &store, &mut state).await;
         assert_eq!(expected, response);
     }
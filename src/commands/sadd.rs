@@ -0,0 +1,179 @@
+//! This module contains the SADD command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the SADD options.
+fn parse_sadd_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, Vec<String>)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let members = iter
+        .map(|member| crate::resp::extract_string(&member).context("Failed to extract member"))
+        .collect::<Result<Vec<_>>>()?;
+    if members.is_empty() {
+        return Err(anyhow::anyhow!("Missing member"));
+    }
+
+    Ok((key, members))
+}
+
+pub struct Sadd;
+
+#[async_trait::async_trait]
+impl Command for Sadd {
+    fn static_name() -> String {
+        "SADD".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "denyoom", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the SADD command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, members) = match parse_sadd_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'SADD' command"));
+            }
+        };
+
+        let mut store = store.write().await;
+        let entry_ref = store.get_or_insert(key.clone(), crate::store::Entry::new_set());
+        let added = match &mut entry_ref.value {
+            crate::store::EntryValue::Set(set) => members
+                .into_iter()
+                .filter(|member| set.insert(member.clone()))
+                .collect::<Vec<_>>(),
+            _ => return crate::commands::wrong_type_error(),
+        };
+        store.track_resize(0, added.iter().map(String::len).sum());
+
+        crate::resp::RespType::Integer(added.len() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str, members: &[&str]) -> Vec<crate::resp::RespType> {
+        std::iter::once(key)
+            .chain(members.iter().copied())
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SADD", Sadd::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Sadd
+            .handle(make_args(&key, &["a", "b", "a"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(2), response);
+
+        let response = Sadd
+            .handle(make_args(&key, &["b", "c"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+
+        let expected = std::collections::HashSet::from(["a".into(), "b".into(), "c".into()]);
+        assert_eq!(
+            crate::store::EntryValue::Set(expected),
+            store.write().await.get(&key).unwrap().value
+        );
+        let store = store.read().await;
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Sadd
+            .handle(make_args(&key, &["a"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'SADD' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'SADD' command"
+    )]
+    #[case::missing_member(make_args("key", &[]), "ERR Missing member for 'SADD' command")]
+    #[case::invalid_member(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract member for 'SADD' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Sadd.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
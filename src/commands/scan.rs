@@ -0,0 +1,330 @@
+//! This module contains the SCAN command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The number of keys to scan when no COUNT is given.
+const DEFAULT_COUNT: usize = 10;
+
+/// The SCAN options.
+#[derive(Debug, PartialEq)]
+struct ScanOptions {
+    cursor: u64,
+    pattern: Option<crate::pattern::Pattern>,
+    count: usize,
+    type_name: Option<String>,
+}
+
+/// Parses the SCAN options.
+fn parse_scan_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<ScanOptions> {
+    let mut iter = iter.into_iter();
+
+    let cursor = crate::resp::extract_string(&iter.next().context("Missing cursor")?)
+        .context("Failed to extract cursor")?
+        .parse::<u64>()
+        .map_err(|_| anyhow::anyhow!("invalid cursor"))?;
+
+    let mut options = ScanOptions {
+        cursor,
+        pattern: None,
+        count: DEFAULT_COUNT,
+        type_name: None,
+    };
+    while let Some(token) = iter.next() {
+        let option = crate::resp::extract_string(&token).context("Failed to extract option")?;
+        match option.to_lowercase().as_str() {
+            "match" => {
+                options.pattern = Some(crate::pattern::Pattern::new(
+                    crate::resp::extract_string(
+                        &iter.next().context("Missing pattern for MATCH option")?,
+                    )
+                    .context("Failed to extract pattern")?,
+                ));
+            }
+            "count" => {
+                options.count = crate::resp::extract_string(
+                    &iter.next().context("Missing value for COUNT option")?,
+                )
+                .context("Failed to extract count")?
+                .parse::<usize>()
+                .context("Failed to convert COUNT value to a number")?;
+                if options.count == 0 {
+                    return Err(anyhow::anyhow!("COUNT must be positive"));
+                }
+            }
+            "type" => {
+                options.type_name = Some(
+                    crate::resp::extract_string(
+                        &iter.next().context("Missing type for TYPE option")?,
+                    )
+                    .context("Failed to extract type")?
+                    .to_lowercase(),
+                );
+            }
+            _ => {
+                return Err(anyhow::anyhow!("{option} is not a valid option"));
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+pub struct Scan;
+
+#[async_trait::async_trait]
+impl Command for Scan {
+    fn static_name() -> String {
+        "SCAN".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    /// Handles the SCAN command.
+    ///
+    /// MATCH and TYPE filter the keys of each batch, so a batch may be empty before the end of the
+    /// iteration.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let options = match parse_scan_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'SCAN' command"));
+            }
+        };
+
+        let store = store.read().await;
+        let (next_cursor, entries) = store.scan(options.cursor, options.count);
+        let keys = entries
+            .into_iter()
+            .filter(|(key, _)| match &options.pattern {
+                Some(pattern) => pattern.matches(key),
+                None => true,
+            })
+            .filter(|(_, entry)| match &options.type_name {
+                Some(type_name) => entry.value.type_name() == type_name,
+                None => true,
+            })
+            .map(|(key, _)| crate::resp::RespType::BulkString(Some(key.clone().into())))
+            .collect();
+
+        crate::resp::RespType::Array(vec![
+            crate::resp::RespType::BulkString(Some(next_cursor.to_string().into())),
+            crate::resp::RespType::Array(keys),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    async fn populate(store: &crate::store::SharedStore) {
+        let mut store = store.write().await;
+        for i in 0..15 {
+            store.insert(
+                format!("string:{i}"),
+                crate::store::Entry::new_string("value"),
+            );
+        }
+        for i in 0..5 {
+            store.insert(format!("list:{i}"), crate::store::Entry::new_list());
+        }
+    }
+
+    /// Scans until the cursor returns to 0, returning the keys and the number of calls.
+    async fn scan_all(
+        store: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+        options: &[&str],
+    ) -> (Vec<String>, usize) {
+        let mut cursor = "0".to_string();
+        let mut keys = vec![];
+        let mut calls = 0;
+        loop {
+            let args = make_args(&[&[cursor.as_str()], options].concat());
+            let response = Scan.handle(args, store, state).await;
+            calls += 1;
+            let (next_cursor, batch) = match response {
+                crate::resp::RespType::Array(mut result) if result.len() == 2 => {
+                    let batch = result.pop().unwrap();
+                    (result.pop().unwrap(), batch)
+                }
+                _ => panic!("Unexpected response: {:?}.", response),
+            };
+            match batch {
+                crate::resp::RespType::Array(batch) => keys.extend(
+                    batch
+                        .iter()
+                        .map(|key| crate::resp::extract_string(key).unwrap()),
+                ),
+                _ => panic!("Unexpected batch: {:?}.", batch),
+            }
+            cursor = crate::resp::extract_string(&next_cursor).unwrap();
+            if cursor == "0" {
+                break;
+            }
+        }
+        keys.sort_unstable();
+        (keys, calls)
+    }
+
+    fn expected_keys(prefix: &str, count: usize) -> Vec<String> {
+        let mut keys = (0..count)
+            .map(|i| format!("{prefix}:{i}"))
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SCAN", Scan::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_empty(store: crate::store::SharedStore, mut state: crate::state::State) {
+        let response = Scan.handle(make_args(&["0"]), &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some("0".into())),
+                crate::resp::RespType::Array(vec![]),
+            ]),
+            response
+        );
+    }
+
+    #[rstest]
+    #[case::default_count(&[], 2)]
+    #[case::small_count(&["COUNT", "3"], 7)]
+    #[case::large_count(&["count", "100"], 1)]
+    #[tokio::test]
+    async fn test_handle_iterate_all(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] options: &[&str],
+        #[case] expected_calls: usize,
+    ) {
+        populate(&store).await;
+        let (keys, calls) = scan_all(&store, &mut state, options).await;
+
+        let mut expected = [expected_keys("list", 5), expected_keys("string", 15)].concat();
+        expected.sort_unstable();
+        assert_eq!(expected, keys);
+        assert_eq!(expected_calls, calls);
+    }
+
+    #[rstest]
+    #[case::match_strings(&["MATCH", "string:*"], expected_keys("string", 15))]
+    #[case::match_single(&["MATCH", "list:?"], expected_keys("list", 5))]
+    #[case::match_none(&["MATCH", "none*"], vec![])]
+    #[case::type_list(&["TYPE", "list"], expected_keys("list", 5))]
+    #[case::type_string(&["TYPE", "STRING"], expected_keys("string", 15))]
+    #[case::match_and_type(&["MATCH", "list:*", "TYPE", "string"], vec![])]
+    #[tokio::test]
+    async fn test_handle_filter(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] options: &[&str],
+        #[case] expected: Vec<String>,
+    ) {
+        populate(&store).await;
+        let (keys, _) = scan_all(&store, &mut state, options).await;
+        assert_eq!(expected, keys);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_skips_expired(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        tokio::time::pause();
+        populate(&store).await;
+        store.write().await.insert(
+            "expired".into(),
+            crate::store::Entry::new_string("value").with_deletion(10u64),
+        );
+        tokio::time::advance(tokio::time::Duration::from_millis(10)).await;
+
+        let (keys, _) = scan_all(&store, &mut state, &[]).await;
+        assert!(!keys.contains(&"expired".to_string()));
+        assert_eq!(20, keys.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_cursor_past_end(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        populate(&store).await;
+        let response = Scan
+            .handle(make_args(&[&u64::MAX.to_string()]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some("0".into())),
+                crate::resp::RespType::Array(vec![]),
+            ]),
+            response
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_cursor(&[], "ERR Missing cursor for 'SCAN' command")]
+    #[case::invalid_cursor(&["abc"], "ERR invalid cursor for 'SCAN' command")]
+    #[case::negative_cursor(&["-1"], "ERR invalid cursor for 'SCAN' command")]
+    #[case::invalid_option(&["0", "invalid"], "ERR invalid is not a valid option for 'SCAN' command")]
+    #[case::missing_pattern(&["0", "MATCH"], "ERR Missing pattern for MATCH option for 'SCAN' command")]
+    #[case::missing_count(&["0", "COUNT"], "ERR Missing value for COUNT option for 'SCAN' command")]
+    #[case::invalid_count(&["0", "COUNT", "abc"], "ERR Failed to convert COUNT value to a number for 'SCAN' command")]
+    #[case::zero_count(&["0", "COUNT", "0"], "ERR COUNT must be positive for 'SCAN' command")]
+    #[case::missing_type(&["0", "TYPE"], "ERR Missing type for TYPE option for 'SCAN' command")]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected: &str,
+    ) {
+        let response = Scan.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! This module contains the SCARD command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the SCARD options.
+fn parse_scard_options<I: IntoIterator<Item = crate::resp::RespType>>(iter: I) -> Result<String> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    Ok(key)
+}
+
+pub struct Scard;
+
+#[async_trait::async_trait]
+impl Command for Scard {
+    fn static_name() -> String {
+        "SCARD".into()
+    }
+
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the SCARD command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let key = match parse_scard_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'SCARD' command"
+                ));
+            }
+        };
+
+        let store = store.read().await;
+        match store.get(&key) {
+            Some(crate::store::Entry {
+                value: crate::store::EntryValue::Set(set),
+                ..
+            }) => crate::resp::RespType::Integer(set.len() as i64),
+            Some(_) => crate::commands::wrong_type_error(),
+            None => crate::resp::RespType::Integer(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str) -> Vec<crate::resp::RespType> {
+        vec![crate::resp::RespType::BulkString(Some(
+            key.to_string().into(),
+        ))]
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SCARD", Scard::static_name());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let mut entry = crate::store::Entry::new_set();
+        if let crate::store::EntryValue::Set(set) = &mut entry.value {
+            set.insert("a".into());
+            set.insert("b".into());
+        }
+        store.write().await.insert(key.clone(), entry);
+        let response = Scard.handle(make_args(&key), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(2), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_after_mutations(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let args = |members: &[&str]| {
+            std::iter::once(key.as_str())
+                .chain(members.iter().copied())
+                .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+                .collect::<Vec<_>>()
+        };
+        crate::commands::sadd::Sadd
+            .handle(args(&["a", "b", "c"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Integer(3),
+            Scard.handle(make_args(&key), &store, &mut state).await
+        );
+
+        crate::commands::srem::Srem
+            .handle(args(&["a", "d"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Integer(2),
+            Scard.handle(make_args(&key), &store, &mut state).await
+        );
+
+        crate::commands::srem::Srem
+            .handle(args(&["b", "c"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Integer(0),
+            Scard.handle(make_args(&key), &store, &mut state).await
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Scard.handle(make_args(&key), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_list());
+        let response = Scard.handle(make_args(&key), &store, &mut state).await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'SCARD' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'SCARD' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Scard.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! This module contains the SELECT command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the SELECT options.
+fn parse_select_options<I: IntoIterator<Item = crate::resp::RespType>>(iter: I) -> Result<usize> {
+    let mut iter = iter.into_iter();
+
+    let index = crate::resp::extract_string(&iter.next().context("Missing index")?)
+        .context("Failed to extract index")?
+        .parse::<usize>()
+        .context("Failed to convert index to a number")?;
+
+    Ok(index)
+}
+
+pub struct Select {
+    databases: usize,
+}
+
+impl Select {
+    /// Creates the SELECT command for the number of databases.
+    pub fn new(databases: usize) -> Self {
+        Self { databases }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Select {
+    fn static_name() -> String {
+        "SELECT".into()
+    }
+
+    fn arity(&self) -> i64 {
+        2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["loading", "stale", "fast"]
+    }
+
+    /// Handles the SELECT command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let index = match parse_select_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'SELECT' command"
+                ));
+            }
+        };
+
+        if index >= self.databases {
+            return crate::resp::RespType::SimpleError("ERR DB index is out of range".into());
+        }
+        state.db = index;
+        crate::resp::RespType::SimpleString("OK".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    /// Sends a command over the stream and reads the serialized response.
+    async fn send(stream: &mut tokio::io::DuplexStream, args: &[&str]) -> anyhow::Result<String> {
+        let message = crate::resp::RespType::Array(make_args(args));
+        stream.write_all(&message.serialize()).await?;
+        let mut buffer = bytes::BytesMut::with_capacity(512);
+        stream.read_buf(&mut buffer).await?;
+        Ok(String::from_utf8(buffer.to_vec())?)
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SELECT", Select::static_name());
+    }
+
+    #[rstest]
+    #[case::first("0", 0)]
+    #[case::last("15", 15)]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] index: &str,
+        #[case] expected: usize,
+    ) {
+        let response = Select::new(16)
+            .handle(make_args(&[index]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(expected, state.db);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_isolates_databases() -> anyhow::Result<()> {
+        let databases = crate::store::new_databases(2);
+        let mut register = crate::commands::Register::new();
+        register.register_multiple(vec![
+            Box::new(Select::new(databases.len())),
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::set::Set),
+        ]);
+        let register = std::sync::Arc::new(tokio::sync::RwLock::new(register));
+        let (mut stream, server_stream) = tokio::io::duplex(512);
+        let mut handler = crate::handler::RespHandler::new(server_stream, 0);
+        tokio::spawn(async move { handler.run(databases, register).await });
+
+        let ok = crate::resp::RespType::SimpleString("OK".into()).serialize();
+        let value = crate::resp::RespType::BulkString(Some("value".into())).serialize();
+        assert_eq!(ok, send(&mut stream, &["SET", "key", "value"]).await?);
+        assert_eq!(value, send(&mut stream, &["GET", "key"]).await?);
+        assert_eq!(ok, send(&mut stream, &["SELECT", "1"]).await?);
+        assert_eq!("$-1\r\n", send(&mut stream, &["GET", "key"]).await?);
+        assert_eq!(ok, send(&mut stream, &["SELECT", "0"]).await?);
+        assert_eq!(value, send(&mut stream, &["GET", "key"]).await?);
+        Ok(())
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_index(vec![], "ERR Missing index for 'SELECT' command")]
+    #[case::invalid_index(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract index for 'SELECT' command"
+    )]
+    #[case::non_numeric_index(
+        make_args(&["abc"]),
+        "ERR Failed to convert index to a number for 'SELECT' command"
+    )]
+    #[case::negative_index(
+        make_args(&["-1"]),
+        "ERR Failed to convert index to a number for 'SELECT' command"
+    )]
+    #[case::out_of_range(make_args(&["16"]), "ERR DB index is out of range")]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        state.db = 3;
+        let response = Select::new(16).handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        assert_eq!(3, state.db);
+    }
+}
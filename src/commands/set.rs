@@ -2,6 +2,11 @@
 use crate::commands::Command;
 use anyhow::{Context, Result};
 
+/// The error for an expiry that is not a positive duration.
+#[derive(Debug, thiserror::Error)]
+#[error("ERR invalid expire time in 'set' command")]
+struct InvalidExpireTime;
+
 /// Parses the SET options.
 fn parse_set_options<I: IntoIterator<Item = crate::resp::RespType>>(
     iter: I,
@@ -11,7 +16,7 @@ fn parse_set_options<I: IntoIterator<Item = crate::resp::RespType>>(
     let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
         .context("Failed to extract key")?;
 
-    let value = crate::resp::extract_string(&iter.next().ok_or(anyhow::anyhow!("Missing value"))?)
+    let value = crate::resp::extract_bytes(&iter.next().ok_or(anyhow::anyhow!("Missing value"))?)
         .context("Failed to extract value")?;
     let mut entry = crate::store::Entry::new_string(value);
     while let Some(token) = &iter.next() {
@@ -25,9 +30,12 @@ fn parse_set_options<I: IntoIterator<Item = crate::resp::RespType>>(
                         .ok_or(anyhow::anyhow!("Missing milliseconds for PX option"))?,
                 )
                 .context("Failed to extract duration string")?
-                .parse::<u64>()
+                .parse::<i64>()
                 .context("Failed to convert PX duration string to a number")?;
-                entry = entry.with_deletion(duration);
+                if duration <= 0 {
+                    return Err(InvalidExpireTime.into());
+                }
+                entry = entry.with_deletion(duration as u64);
             }
             _ => {
                 return Err(anyhow::anyhow!("{option} is not a valid option"));
@@ -42,10 +50,22 @@ pub struct Set;
 
 #[async_trait::async_trait]
 impl Command for Set {
-    fn name(&self) -> String {
+    fn static_name() -> String {
         "SET".into()
     }
 
+    fn arity(&self) -> i64 {
+        -3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "denyoom"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
     /// Handles the SET command.
     async fn handle(
         &self,
@@ -55,13 +75,18 @@ impl Command for Set {
     ) -> crate::resp::RespType {
         let (key, entry) = match parse_set_options(args) {
             Ok(result) => result,
+            Err(err) if err.is::<InvalidExpireTime>() => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(err.to_string());
+            }
             Err(err) => {
                 log::error!("{err}");
                 return crate::resp::RespType::SimpleError(format!("ERR {err} for 'SET' command"));
             }
         };
 
-        store.lock().await.insert(key, entry);
+        let mut store = store.write().await;
+        store.insert(key, entry);
         crate::resp::RespType::SimpleString("OK".into())
     }
 }
@@ -95,7 +120,7 @@ mod tests {
     // --- Tests ---
     #[rstest]
     fn test_name() {
-        assert_eq!("SET", Set.name());
+        assert_eq!("SET", Set::static_name());
     }
 
     #[rstest]
@@ -113,12 +138,32 @@ mod tests {
         let response = Set.handle(args, &store, &mut state).await;
         assert_eq!(response, crate::resp::RespType::SimpleString("OK".into()));
 
-        let mut store = store.lock().await;
+        let store = store.read().await;
         let entry = store.get(&key).unwrap();
         let expected = crate::store::Entry::new_string(value.clone());
         assert_eq!(expected, *entry);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_binary(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let value = bytes::Bytes::from_static(b"\x08\x96\x01\xff");
+        let args = vec![
+            crate::resp::RespType::BulkString(Some(key.clone().into())),
+            crate::resp::RespType::BulkString(Some(value.clone())),
+        ];
+        let response = Set.handle(args, &store, &mut state).await;
+        assert_eq!(response, crate::resp::RespType::SimpleString("OK".into()));
+
+        let store = store.read().await;
+        let entry = store.get(&key).unwrap();
+        assert_eq!(crate::store::Entry::new_string(value), *entry);
+    }
+
     #[rstest]
     #[case::px_upper("PX")]
     #[case::px_lower("px")]
@@ -141,7 +186,7 @@ mod tests {
         let response = Set.handle(args, &store, &mut state).await;
         assert_eq!(response, crate::resp::RespType::SimpleString("OK".into()));
 
-        let mut store = store.lock().await;
+        let store = store.read().await;
         let entry = store.get(&key).unwrap();
         let expected =
             crate::store::Entry::new_string(value.clone()).with_deletion(duration as u64);
@@ -159,7 +204,7 @@ mod tests {
         value: String,
         #[case] old_entry: crate::store::Entry,
     ) {
-        store.lock().await.insert(key.clone(), old_entry);
+        store.write().await.insert(key.clone(), old_entry);
 
         let args = vec![
             crate::resp::RespType::SimpleString(key.clone()),
@@ -168,7 +213,7 @@ mod tests {
         let response = Set.handle(args, &store, &mut state).await;
         assert_eq!(response, crate::resp::RespType::SimpleString("OK".into()));
 
-        let mut store = store.lock().await;
+        let store = store.read().await;
         let entry = store.get(&key).unwrap();
         let expected = crate::store::Entry::new_string(value.clone());
         assert_eq!(expected, *entry);
@@ -212,7 +257,7 @@ mod tests {
         mut state: crate::state::State,
         key: String,
     ) {
-        let args = vec![crate::resp::RespType::BulkString(Some(key))];
+        let args = vec![crate::resp::RespType::BulkString(Some(key.into()))];
         let response = Set.handle(args, &store, &mut state).await;
         assert_eq!(
             crate::resp::RespType::SimpleError("ERR Missing value for 'SET' command".into()),
@@ -228,7 +273,7 @@ mod tests {
         key: String,
     ) {
         let args = vec![
-            crate::resp::RespType::BulkString(Some(key)),
+            crate::resp::RespType::BulkString(Some(key.into())),
             crate::resp::RespType::Array(vec![]),
         ];
         let response = Set.handle(args, &store, &mut state).await;
@@ -249,8 +294,8 @@ mod tests {
         value: String,
     ) {
         let args = vec![
-            crate::resp::RespType::BulkString(Some(key)),
-            crate::resp::RespType::BulkString(Some(value)),
+            crate::resp::RespType::BulkString(Some(key.into())),
+            crate::resp::RespType::BulkString(Some(value.into())),
             crate::resp::RespType::BulkString(Some("invalid option".into())),
         ];
         let response = Set.handle(args, &store, &mut state).await;
@@ -271,8 +316,8 @@ mod tests {
         value: String,
     ) {
         let args = vec![
-            crate::resp::RespType::BulkString(Some(key)),
-            crate::resp::RespType::BulkString(Some(value)),
+            crate::resp::RespType::BulkString(Some(key.into())),
+            crate::resp::RespType::BulkString(Some(value.into())),
             crate::resp::RespType::Array(vec![]),
         ];
         let response = Set.handle(args, &store, &mut state).await;
@@ -293,8 +338,8 @@ mod tests {
         value: String,
     ) {
         let args = vec![
-            crate::resp::RespType::BulkString(Some(key)),
-            crate::resp::RespType::BulkString(Some(value)),
+            crate::resp::RespType::BulkString(Some(key.into())),
+            crate::resp::RespType::BulkString(Some(value.into())),
             crate::resp::RespType::BulkString(Some("px".into())),
         ];
         let response = Set.handle(args, &store, &mut state).await;
@@ -315,8 +360,8 @@ mod tests {
         value: String,
     ) {
         let args = vec![
-            crate::resp::RespType::BulkString(Some(key)),
-            crate::resp::RespType::BulkString(Some(value)),
+            crate::resp::RespType::BulkString(Some(key.into())),
+            crate::resp::RespType::BulkString(Some(value.into())),
             crate::resp::RespType::BulkString(Some("px".into())),
             crate::resp::RespType::BulkString(Some("abc".into())),
         ];
@@ -328,4 +373,29 @@ mod tests {
             response
         );
     }
+
+    #[rstest]
+    #[case::zero("0")]
+    #[case::negative("-1")]
+    #[tokio::test]
+    async fn test_handle_non_positive_px_value(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        value: String,
+        #[case] duration: &str,
+    ) {
+        let args = vec![
+            crate::resp::RespType::BulkString(Some(key.clone().into())),
+            crate::resp::RespType::BulkString(Some(value.into())),
+            crate::resp::RespType::BulkString(Some("px".into())),
+            crate::resp::RespType::BulkString(Some(duration.to_string().into())),
+        ];
+        let response = Set.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError("ERR invalid expire time in 'set' command".into()),
+            response
+        );
+        assert!(store.read().await.get(&key).is_none());
+    }
 }
@@ -0,0 +1,179 @@
+//! This module contains the SHUTDOWN command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Whether the dataset is saved before shutting down.
+#[derive(Debug, PartialEq)]
+enum SaveMode {
+    Default,
+    NoSave,
+    Save,
+}
+
+/// Parses the SHUTDOWN options.
+fn parse_shutdown_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<SaveMode> {
+    let mut save_mode = SaveMode::Default;
+    for token in iter {
+        let option = crate::resp::extract_string(&token).context("Failed to extract option")?;
+        let mode = match option.to_lowercase().as_str() {
+            "nosave" => SaveMode::NoSave,
+            "save" => SaveMode::Save,
+            _ => return Err(anyhow::anyhow!("{option} is not a valid option")),
+        };
+        if save_mode != SaveMode::Default && save_mode != mode {
+            return Err(anyhow::anyhow!("NOSAVE and SAVE cannot be used together"));
+        }
+        save_mode = mode;
+    }
+
+    Ok(save_mode)
+}
+
+pub struct Shutdown {
+    shutdown: tokio_util::sync::CancellationToken,
+}
+
+impl Shutdown {
+    /// Creates the SHUTDOWN command, cancelling the token to shut the server down.
+    pub fn new(shutdown: tokio_util::sync::CancellationToken) -> Self {
+        Self { shutdown }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Shutdown {
+    fn static_name() -> String {
+        "SHUTDOWN".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["admin", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the SHUTDOWN command.
+    ///
+    /// The server stops accepting connections and closes every client, including this one, which
+    /// is closed without a reply. The dataset is not persisted, so SHUTDOWN SAVE is refused.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let save_mode = match parse_shutdown_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'SHUTDOWN' command"
+                ));
+            }
+        };
+
+        if save_mode == SaveMode::Save {
+            log::error!("Refusing to shut down with SAVE, as persistence is not supported.");
+            return crate::resp::RespType::SimpleError(
+                "ERR Errors trying to SHUTDOWN. Check logs.".into(),
+            );
+        }
+
+        log::info!("Client {} asked to shut down.", state.client_id);
+        self.shutdown.cancel();
+        state.close_after_reply = true;
+        crate::resp::RespType::Replies(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn token() -> tokio_util::sync::CancellationToken {
+        tokio_util::sync::CancellationToken::new()
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SHUTDOWN", Shutdown::static_name());
+    }
+
+    #[rstest]
+    #[case::default(&[])]
+    #[case::nosave(&["NOSAVE"])]
+    #[case::nosave_lower(&["nosave"])]
+    #[case::nosave_repeated(&["NOSAVE", "nosave"])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        token: tokio_util::sync::CancellationToken,
+        #[case] args: &[&str],
+    ) {
+        let response = Shutdown::new(token.clone())
+            .handle(make_args(args), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Replies(vec![]), response);
+        assert!(token.is_cancelled());
+        assert!(state.close_after_reply);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::save(make_args(&["SAVE"]), "ERR Errors trying to SHUTDOWN. Check logs.")]
+    #[case::invalid_option(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract option for 'SHUTDOWN' command"
+    )]
+    #[case::unknown_option(
+        make_args(&["invalid"]),
+        "ERR invalid is not a valid option for 'SHUTDOWN' command"
+    )]
+    #[case::nosave_and_save(
+        make_args(&["NOSAVE", "SAVE"]),
+        "ERR NOSAVE and SAVE cannot be used together for 'SHUTDOWN' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        token: tokio_util::sync::CancellationToken,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Shutdown::new(token.clone())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        assert!(!token.is_cancelled());
+        assert!(!state.close_after_reply);
+    }
+}
@@ -0,0 +1,364 @@
+//! This module contains the SINTER, SUNION and SDIFF commands.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+
+/// Parses the set operation options.
+fn parse_set_operation_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Vec<String>> {
+    let keys = iter
+        .into_iter()
+        .map(|key| crate::resp::extract_string(&key).context("Failed to extract key"))
+        .collect::<Result<Vec<_>>>()?;
+    if keys.is_empty() {
+        return Err(anyhow::anyhow!("Missing key"));
+    }
+
+    Ok(keys)
+}
+
+/// Reads the sets at the keys under a single lock.
+///
+/// Missing keys are read as empty sets.
+async fn get_sets(
+    keys: &[String],
+    store: &crate::store::SharedStore,
+) -> std::result::Result<Vec<HashSet<String>>, crate::resp::RespType> {
+    let store = store.read().await;
+    keys.iter()
+        .map(|key| match store.get(key) {
+            Some(crate::store::Entry {
+                value: crate::store::EntryValue::Set(set),
+                ..
+            }) => Ok(set.clone()),
+            Some(_) => Err(crate::commands::wrong_type_error()),
+            None => Ok(HashSet::new()),
+        })
+        .collect()
+}
+
+/// Intersects the sets.
+fn intersect(sets: Vec<HashSet<String>>) -> HashSet<String> {
+    let mut sets = sets.into_iter();
+    let first = sets.next().unwrap_or_default();
+    sets.fold(first, |result, set| {
+        result
+            .into_iter()
+            .filter(|member| set.contains(member))
+            .collect()
+    })
+}
+
+/// Unions the sets.
+fn union(sets: Vec<HashSet<String>>) -> HashSet<String> {
+    sets.into_iter().flatten().collect()
+}
+
+/// Subtracts the later sets from the first set.
+fn difference(sets: Vec<HashSet<String>>) -> HashSet<String> {
+    let mut sets = sets.into_iter();
+    let first = sets.next().unwrap_or_default();
+    sets.fold(first, |result, set| {
+        result
+            .into_iter()
+            .filter(|member| !set.contains(member))
+            .collect()
+    })
+}
+
+/// Handles a set operation command.
+///
+/// The members are replied as a set, which RESP2 clients receive as an array.
+/// The members are sorted so that the reply is deterministic.
+async fn handle_set_operation(
+    args: Vec<crate::resp::RespType>,
+    store: &crate::store::SharedStore,
+    name: &str,
+    operation: fn(Vec<HashSet<String>>) -> HashSet<String>,
+) -> crate::resp::RespType {
+    let keys = match parse_set_operation_options(args) {
+        Ok(result) => result,
+        Err(err) => {
+            log::error!("{err}");
+            return crate::resp::RespType::SimpleError(format!("ERR {err} for '{name}' command"));
+        }
+    };
+
+    let sets = match get_sets(&keys, store).await {
+        Ok(sets) => sets,
+        Err(err) => return err,
+    };
+
+    let mut members = operation(sets).into_iter().collect::<Vec<_>>();
+    members.sort_unstable();
+    crate::resp::RespType::Set(
+        members
+            .into_iter()
+            .map(|member| crate::resp::RespType::BulkString(Some(member.into())))
+            .collect(),
+    )
+}
+
+pub struct Sinter;
+
+#[async_trait::async_trait]
+impl Command for Sinter {
+    fn static_name() -> String {
+        "SINTER".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, -1, 1)
+    }
+
+    /// Handles the SINTER command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        handle_set_operation(args, store, "SINTER", intersect).await
+    }
+}
+
+pub struct Sunion;
+
+#[async_trait::async_trait]
+impl Command for Sunion {
+    fn static_name() -> String {
+        "SUNION".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, -1, 1)
+    }
+
+    /// Handles the SUNION command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        handle_set_operation(args, store, "SUNION", union).await
+    }
+}
+
+pub struct Sdiff;
+
+#[async_trait::async_trait]
+impl Command for Sdiff {
+    fn static_name() -> String {
+        "SDIFF".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, -1, 1)
+    }
+
+    /// Handles the SDIFF command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        handle_set_operation(args, store, "SDIFF", difference).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    async fn store() -> crate::store::SharedStore {
+        let store = crate::store::new();
+        for (key, members) in [
+            ("a", &["1", "2", "3"][..]),
+            ("b", &["2", "3", "4"][..]),
+            ("c", &["3", "5"][..]),
+        ] {
+            let mut entry = crate::store::Entry::new_set();
+            if let crate::store::EntryValue::Set(set) = &mut entry.value {
+                set.extend(members.iter().map(|member| member.to_string()));
+            }
+            store.write().await.insert(key.into(), entry);
+        }
+        store
+            .write()
+            .await
+            .insert("string".into(), crate::store::Entry::new_string("value"));
+        store
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(keys: &[&str]) -> Vec<crate::resp::RespType> {
+        keys.iter()
+            .map(|key| crate::resp::RespType::BulkString(Some(key.to_string().into())))
+            .collect()
+    }
+
+    fn make_members(members: &[&str]) -> crate::resp::RespType {
+        crate::resp::RespType::Set(
+            members
+                .iter()
+                .map(|member| crate::resp::RespType::BulkString(Some(member.to_string().into())))
+                .collect(),
+        )
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SINTER", Sinter::static_name());
+        assert_eq!("SUNION", Sunion::static_name());
+        assert_eq!("SDIFF", Sdiff::static_name());
+    }
+
+    #[rstest]
+    #[case::single(&["a"], &["1", "2", "3"])]
+    #[case::two(&["a", "b"], &["2", "3"])]
+    #[case::three(&["a", "b", "c"], &["3"])]
+    #[case::missing(&["a", "missing"], &[])]
+    #[tokio::test]
+    async fn test_handle_sinter(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] keys: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let store = store.await;
+        let response = Sinter.handle(make_args(keys), &store, &mut state).await;
+        assert_eq!(make_members(expected), response);
+    }
+
+    #[rstest]
+    #[case::v2(crate::state::ProtocolVersion::V2, "*2\r\n$1\r\n2\r\n$1\r\n3\r\n")]
+    #[case::v3(crate::state::ProtocolVersion::V3, "~2\r\n$1\r\n2\r\n$1\r\n3\r\n")]
+    #[tokio::test]
+    async fn test_handle_sinter_serialization(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] version: crate::state::ProtocolVersion,
+        #[case] expected: &str,
+    ) {
+        let store = store.await;
+        let response = Sinter
+            .handle(make_args(&["a", "b"]), &store, &mut state)
+            .await;
+        assert_eq!(expected, response.serialize_with_version(&version));
+    }
+
+    #[rstest]
+    #[case::two(&["a", "b"], &["1", "2", "3", "4"])]
+    #[case::three(&["a", "b", "c"], &["1", "2", "3", "4", "5"])]
+    #[case::missing(&["c", "missing"], &["3", "5"])]
+    #[case::all_missing(&["missing"], &[])]
+    #[tokio::test]
+    async fn test_handle_sunion(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] keys: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let store = store.await;
+        let response = Sunion.handle(make_args(keys), &store, &mut state).await;
+        assert_eq!(make_members(expected), response);
+    }
+
+    #[rstest]
+    #[case::a_minus_b(&["a", "b"], &["1"])]
+    #[case::b_minus_a(&["b", "a"], &["4"])]
+    #[case::three(&["b", "a", "c"], &["4"])]
+    #[case::missing_later(&["c", "missing"], &["3", "5"])]
+    #[case::missing_first(&["missing", "a"], &[])]
+    #[tokio::test]
+    async fn test_handle_sdiff(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] keys: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let store = store.await;
+        let response = Sdiff.handle(make_args(keys), &store, &mut state).await;
+        assert_eq!(make_members(expected), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let store = store.await;
+        let args = make_args(&["a", "string"]);
+        let expected = crate::commands::wrong_type_error();
+        assert_eq!(
+            expected,
+            Sinter.handle(args.clone(), &store, &mut state).await
+        );
+        assert_eq!(
+            expected,
+            Sunion.handle(args.clone(), &store, &mut state).await
+        );
+        assert_eq!(expected, Sdiff.handle(args, &store, &mut state).await);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'SINTER' command")]
+    #[case::invalid_key(
+        vec![
+            crate::resp::RespType::BulkString(Some("a".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract key for 'SINTER' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let store = store.await;
+        let response = Sinter.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
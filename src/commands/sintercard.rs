@@ -0,0 +1,238 @@
+//! This module contains the SINTERCARD command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The SINTERCARD options.
+#[derive(Debug, PartialEq)]
+struct SintercardOptions {
+    keys: Vec<String>,
+    /// The number of members to stop counting at, 0 for no limit.
+    limit: usize,
+}
+
+/// Parses the SINTERCARD options.
+fn parse_sintercard_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<SintercardOptions> {
+    let mut iter = iter.into_iter();
+
+    let numkeys = crate::resp::extract_string(&iter.next().context("Missing numkeys")?)
+        .context("Failed to extract numkeys")?
+        .parse::<usize>()
+        .ok()
+        .filter(|numkeys| *numkeys > 0)
+        .context("Failed to convert numkeys to a positive number")?;
+
+    let keys = (0..numkeys)
+        .map(|_| {
+            crate::resp::extract_string(&iter.next().context("Missing key")?)
+                .context("Failed to extract key")
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut options = SintercardOptions { keys, limit: 0 };
+    while let Some(token) = iter.next() {
+        let option = crate::resp::extract_string(&token).context("Failed to extract option")?;
+        match option.to_lowercase().as_str() {
+            "limit" => {
+                options.limit = crate::resp::extract_string(&iter.next().context("Missing limit")?)
+                    .context("Failed to extract limit")?
+                    .parse::<usize>()
+                    .context("Failed to convert limit to a non-negative number")?;
+            }
+            _ => return Err(anyhow::anyhow!("{option} is not a valid option")),
+        }
+    }
+
+    Ok(options)
+}
+
+pub struct Sintercard;
+
+#[async_trait::async_trait]
+impl Command for Sintercard {
+    fn static_name() -> String {
+        "SINTERCARD".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "movablekeys"]
+    }
+
+    fn keys(&self, args: &[crate::resp::RespType]) -> Vec<String> {
+        crate::commands::numkeys_keys(args)
+    }
+
+    /// Handles the SINTERCARD command.
+    ///
+    /// The sets are read under one lock and the intersection is counted without being built,
+    /// stopping once the limit is reached.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let options = match parse_sintercard_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'SINTERCARD' command"
+                ));
+            }
+        };
+
+        let store = store.read().await;
+        let mut sets = vec![];
+        for key in &options.keys {
+            match store.get(key) {
+                Some(crate::store::Entry {
+                    value: crate::store::EntryValue::Set(set),
+                    ..
+                }) => sets.push(Some(set)),
+                Some(_) => return crate::commands::wrong_type_error(),
+                None => sets.push(None),
+            }
+        }
+        // A missing key is an empty set, so the intersection is empty.
+        let Some(mut sets) = sets.into_iter().collect::<Option<Vec<_>>>() else {
+            return crate::resp::RespType::Integer(0);
+        };
+
+        sets.sort_unstable_by_key(|set| set.len());
+        let (smallest, others) = sets.split_first().expect("There is at least one key.");
+        let members = smallest
+            .iter()
+            .filter(|member| others.iter().all(|set| set.contains(*member)));
+        let count = match options.limit {
+            0 => members.count(),
+            limit => members.take(limit).count(),
+        };
+        crate::resp::RespType::Integer(count as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    async fn store() -> crate::store::SharedStore {
+        let store = crate::store::new();
+        {
+            let mut store = store.write().await;
+            store.insert("a".into(), make_set(&["1", "2", "3", "4"]));
+            store.insert("b".into(), make_set(&["2", "3", "4", "5"]));
+            store.insert("c".into(), make_set(&["3", "4", "5", "6"]));
+            store.insert("string".into(), crate::store::Entry::new_string("value"));
+        }
+        store
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_set(members: &[&str]) -> crate::store::Entry {
+        let mut entry = crate::store::Entry::new_set();
+        if let crate::store::EntryValue::Set(set) = &mut entry.value {
+            set.extend(members.iter().map(|member| member.to_string()));
+        }
+        entry
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SINTERCARD", Sintercard::static_name());
+    }
+
+    #[rstest]
+    #[case::single(&["1", "a"], 4)]
+    #[case::two(&["2", "a", "b"], 3)]
+    #[case::three(&["3", "a", "b", "c"], 2)]
+    #[case::same_key(&["2", "a", "a"], 4)]
+    #[case::limit(&["2", "a", "b", "LIMIT", "2"], 2)]
+    #[case::limit_above_count(&["3", "a", "b", "c", "limit", "5"], 2)]
+    #[case::no_limit(&["2", "a", "b", "LIMIT", "0"], 3)]
+    #[case::missing_key(&["2", "a", "missing"], 0)]
+    #[tokio::test]
+    async fn test_handle(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+        #[case] expected: i64,
+    ) {
+        let store = store.await;
+        let response = Sintercard.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(expected), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::first(&["2", "string", "a"])]
+    #[case::last(&["2", "a", "string"])]
+    #[case::after_missing_key(&["2", "missing", "string"])]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: &[&str],
+    ) {
+        let store = store.await;
+        let response = Sintercard.handle(make_args(args), &store, &mut state).await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_numkeys(vec![], "ERR Missing numkeys for 'SINTERCARD' command")]
+    #[case::invalid_numkeys(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract numkeys for 'SINTERCARD' command"
+    )]
+    #[case::zero_numkeys(
+        make_args(&["0", "a"]),
+        "ERR Failed to convert numkeys to a positive number for 'SINTERCARD' command"
+    )]
+    #[case::missing_key(make_args(&["2", "a"]), "ERR Missing key for 'SINTERCARD' command")]
+    #[case::missing_limit(
+        make_args(&["1", "a", "LIMIT"]),
+        "ERR Missing limit for 'SINTERCARD' command"
+    )]
+    #[case::negative_limit(
+        make_args(&["1", "a", "LIMIT", "-1"]),
+        "ERR Failed to convert limit to a non-negative number for 'SINTERCARD' command"
+    )]
+    #[case::unknown_option(
+        make_args(&["1", "a", "invalid"]),
+        "ERR invalid is not a valid option for 'SINTERCARD' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        #[future] store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let store = store.await;
+        let response = Sintercard.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
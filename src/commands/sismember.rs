@@ -0,0 +1,219 @@
+//! This module contains the SISMEMBER command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the SISMEMBER options.
+fn parse_sismember_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, String)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let member = crate::resp::extract_string(&iter.next().context("Missing member")?)
+        .context("Failed to extract member")?;
+
+    Ok((key, member))
+}
+
+pub struct Sismember;
+
+#[async_trait::async_trait]
+impl Command for Sismember {
+    fn static_name() -> String {
+        "SISMEMBER".into()
+    }
+
+    fn arity(&self) -> i64 {
+        3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the SISMEMBER command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, member) = match parse_sismember_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'SISMEMBER' command"
+                ));
+            }
+        };
+
+        let store = store.read().await;
+        match store.get(&key) {
+            Some(crate::store::Entry {
+                value: crate::store::EntryValue::Set(set),
+                ..
+            }) => crate::resp::RespType::Integer(set.contains(&member) as i64),
+            Some(_) => crate::commands::wrong_type_error(),
+            None => crate::resp::RespType::Integer(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str, member: &str) -> Vec<crate::resp::RespType> {
+        vec![
+            crate::resp::RespType::BulkString(Some(key.to_string().into())),
+            crate::resp::RespType::BulkString(Some(member.to_string().into())),
+        ]
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SISMEMBER", Sismember::static_name());
+    }
+
+    #[rstest]
+    #[case::member("a", 1)]
+    #[case::not_member("c", 0)]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] member: &str,
+        #[case] expected: i64,
+    ) {
+        let mut entry = crate::store::Entry::new_set();
+        if let crate::store::EntryValue::Set(set) = &mut entry.value {
+            set.extend(["a".to_string(), "b".to_string()]);
+        }
+        store.write().await.insert(key.clone(), entry);
+        let response = Sismember
+            .handle(make_args(&key, member), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(expected), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_after_mutations(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let args = |members: &[&str]| {
+            std::iter::once(key.as_str())
+                .chain(members.iter().copied())
+                .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+                .collect::<Vec<_>>()
+        };
+        crate::commands::sadd::Sadd
+            .handle(args(&["a", "b"]), &store, &mut state)
+            .await;
+        crate::commands::srem::Srem
+            .handle(args(&["a"]), &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::Integer(0),
+            Sismember
+                .handle(make_args(&key, "a"), &store, &mut state)
+                .await
+        );
+        assert_eq!(
+            crate::resp::RespType::Integer(1),
+            Sismember
+                .handle(make_args(&key, "b"), &store, &mut state)
+                .await
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Sismember
+            .handle(make_args(&key, "a"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_string("value"));
+        let response = Sismember
+            .handle(make_args(&key, "a"), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'SISMEMBER' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'SISMEMBER' command"
+    )]
+    #[case::missing_member(
+        vec![crate::resp::RespType::BulkString(Some("key".into()))],
+        "ERR Missing member for 'SISMEMBER' command"
+    )]
+    #[case::invalid_member(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract member for 'SISMEMBER' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Sismember.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! This module contains the SREM command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the SREM options.
+fn parse_srem_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, Vec<String>)> {
+    let mut iter = iter.into_iter();
+
+    let key = crate::resp::extract_string(&iter.next().context("Missing key")?)
+        .context("Failed to extract key")?;
+
+    let members = iter
+        .map(|member| crate::resp::extract_string(&member).context("Failed to extract member"))
+        .collect::<Result<Vec<_>>>()?;
+    if members.is_empty() {
+        return Err(anyhow::anyhow!("Missing member"));
+    }
+
+    Ok((key, members))
+}
+
+pub struct Srem;
+
+#[async_trait::async_trait]
+impl Command for Srem {
+    fn static_name() -> String {
+        "SREM".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, 1, 1)
+    }
+
+    /// Handles the SREM command.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (key, members) = match parse_srem_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'SREM' command"));
+            }
+        };
+
+        let mut store = store.write().await;
+        let Some(entry) = store.get_entry(&key) else {
+            return crate::resp::RespType::Integer(0);
+        };
+        let set = match &mut entry.value {
+            crate::store::EntryValue::Set(set) => set,
+            _ => return crate::commands::wrong_type_error(),
+        };
+
+        let removed = members
+            .iter()
+            .filter(|member| set.remove(*member))
+            .collect::<Vec<_>>();
+        let is_empty = set.is_empty();
+        store.track_resize(removed.iter().map(|member| member.len()).sum(), 0);
+        if is_empty {
+            store.remove(&key);
+        }
+
+        crate::resp::RespType::Integer(removed.len() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    fn key() -> String {
+        "key".into()
+    }
+
+    fn make_args(key: &str, members: &[&str]) -> Vec<crate::resp::RespType> {
+        std::iter::once(key)
+            .chain(members.iter().copied())
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    async fn insert_set(store: &crate::store::SharedStore, key: &str, members: &[&str]) {
+        let mut entry = crate::store::Entry::new_set();
+        if let crate::store::EntryValue::Set(set) = &mut entry.value {
+            set.extend(members.iter().map(|member| member.to_string()));
+        }
+        store.write().await.insert(key.into(), entry);
+    }
+
+    async fn get_members(store: &crate::store::SharedStore, key: &str) -> Vec<String> {
+        match &store.write().await.get(key).unwrap().value {
+            crate::store::EntryValue::Set(set) => {
+                let mut members = set.iter().cloned().collect::<Vec<_>>();
+                members.sort_unstable();
+                members
+            }
+            _ => panic!("Unexpected type"),
+        }
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SREM", Srem::static_name());
+    }
+
+    #[rstest]
+    #[case::single(&["a"], 1, &["b", "c"])]
+    #[case::multiple(&["a", "c"], 2, &["b"])]
+    #[case::absent(&["d"], 0, &["a", "b", "c"])]
+    #[case::mixed(&["a", "d", "a"], 1, &["b", "c"])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+        #[case] members: &[&str],
+        #[case] expected_removed: i64,
+        #[case] expected: &[&str],
+    ) {
+        insert_set(&store, &key, &["a", "b", "c"]).await;
+        let response = Srem
+            .handle(make_args(&key, members), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(expected_removed), response);
+        assert_eq!(expected, get_members(&store, &key).await);
+        let store = store.read().await;
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_removes_empty_set(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        insert_set(&store, &key, &["a", "b"]).await;
+        let response = Srem
+            .handle(make_args(&key, &["a", "b"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(2), response);
+        assert!(store.write().await.get(&key).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_missing_key(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        let response = Srem
+            .handle(make_args(&key, &["a"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_wrong_type(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        key: String,
+    ) {
+        store
+            .write()
+            .await
+            .insert(key.clone(), crate::store::Entry::new_list());
+        let response = Srem
+            .handle(make_args(&key, &["a"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::commands::wrong_type_error(), response);
+    }
+
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'SREM' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'SREM' command"
+    )]
+    #[case::missing_member(make_args("key", &[]), "ERR Missing member for 'SREM' command")]
+    #[case::invalid_member(
+        vec![
+            crate::resp::RespType::BulkString(Some("key".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract member for 'SREM' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Srem.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
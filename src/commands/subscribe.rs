@@ -0,0 +1,296 @@
+//! This module contains the SUBSCRIBE and UNSUBSCRIBE commands.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the SUBSCRIBE and UNSUBSCRIBE channels.
+fn parse_channels<I: IntoIterator<Item = crate::resp::RespType>>(iter: I) -> Result<Vec<String>> {
+    iter.into_iter()
+        .map(|channel| crate::resp::extract_string(&channel).context("Failed to extract channel"))
+        .collect()
+}
+
+/// Makes the reply to a subscription change, including the connection's subscription count.
+/// The reply is a push, which RESP2 clients receive as an array.
+fn make_reply(kind: &str, channel: Option<String>, count: usize) -> crate::resp::RespType {
+    crate::resp::RespType::Push(vec![
+        crate::resp::RespType::BulkString(Some(kind.to_string().into())),
+        crate::resp::RespType::BulkString(channel.map(Into::into)),
+        crate::resp::RespType::Integer(count as i64),
+    ])
+}
+
+pub struct Subscribe;
+
+#[async_trait::async_trait]
+impl Command for Subscribe {
+    fn static_name() -> String {
+        "SUBSCRIBE".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["pubsub", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the SUBSCRIBE command.
+    ///
+    /// Each channel gets its own reply.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let channels = match parse_channels(args) {
+            Ok(channels) if channels.is_empty() => {
+                log::error!("Missing channel");
+                return crate::resp::RespType::SimpleError(
+                    "ERR Missing channel for 'SUBSCRIBE' command".into(),
+                );
+            }
+            Ok(channels) => channels,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'SUBSCRIBE' command"
+                ));
+            }
+        };
+
+        crate::resp::RespType::Replies(
+            channels
+                .into_iter()
+                .map(|channel| {
+                    state.channels.insert(channel.clone());
+                    make_reply("subscribe", Some(channel), state.channels.len())
+                })
+                .collect(),
+        )
+    }
+}
+
+pub struct Unsubscribe;
+
+#[async_trait::async_trait]
+impl Command for Unsubscribe {
+    fn static_name() -> String {
+        "UNSUBSCRIBE".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -1
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["pubsub", "noscript", "loading", "stale"]
+    }
+
+    /// Handles the UNSUBSCRIBE command.
+    ///
+    /// Without any channels, every channel is unsubscribed from. Each channel gets its own reply.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        state: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let channels = match parse_channels(args) {
+            Ok(channels) if channels.is_empty() => state.channels.iter().cloned().collect(),
+            Ok(channels) => channels,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'UNSUBSCRIBE' command"
+                ));
+            }
+        };
+
+        if channels.is_empty() {
+            return crate::resp::RespType::Replies(vec![make_reply("unsubscribe", None, 0)]);
+        }
+        crate::resp::RespType::Replies(
+            channels
+                .into_iter()
+                .map(|channel| {
+                    state.channels.remove(&channel);
+                    make_reply("unsubscribe", Some(channel), state.channels.len())
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(channels: &[&str]) -> Vec<crate::resp::RespType> {
+        channels
+            .iter()
+            .map(|channel| crate::resp::RespType::BulkString(Some(channel.to_string().into())))
+            .collect()
+    }
+
+    fn make_replies(kind: &str, replies: &[(&str, i64)]) -> crate::resp::RespType {
+        crate::resp::RespType::Replies(
+            replies
+                .iter()
+                .map(|(channel, count)| {
+                    make_reply(kind, Some(channel.to_string()), *count as usize)
+                })
+                .collect(),
+        )
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SUBSCRIBE", Subscribe::static_name());
+        assert_eq!("UNSUBSCRIBE", Unsubscribe::static_name());
+    }
+
+    #[rstest]
+    fn test_make_reply() {
+        assert_eq!(
+            crate::resp::RespType::Push(vec![
+                crate::resp::RespType::BulkString(Some("subscribe".into())),
+                crate::resp::RespType::BulkString(Some("channel".into())),
+                crate::resp::RespType::Integer(1),
+            ]),
+            make_reply("subscribe", Some("channel".into()), 1)
+        );
+    }
+
+    #[rstest]
+    #[case::v2(
+        crate::state::ProtocolVersion::V2,
+        "*3\r\n$9\r\nsubscribe\r\n$7\r\nchannel\r\n:1\r\n"
+    )]
+    #[case::v3(
+        crate::state::ProtocolVersion::V3,
+        ">3\r\n$9\r\nsubscribe\r\n$7\r\nchannel\r\n:1\r\n"
+    )]
+    fn test_make_reply_serialization(
+        #[case] version: crate::state::ProtocolVersion,
+        #[case] expected: &str,
+    ) {
+        let reply = make_reply("subscribe", Some("channel".into()), 1);
+        assert_eq!(expected, reply.serialize_with_version(&version));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_subscribe(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = Subscribe
+            .handle(make_args(&["a", "b"]), &store, &mut state)
+            .await;
+        assert_eq!(make_replies("subscribe", &[("a", 1), ("b", 2)]), response);
+
+        let response = Subscribe
+            .handle(make_args(&["b", "c"]), &store, &mut state)
+            .await;
+        assert_eq!(make_replies("subscribe", &[("b", 2), ("c", 3)]), response);
+        assert!(state.is_subscribed());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_unsubscribe(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        Subscribe
+            .handle(make_args(&["a", "b"]), &store, &mut state)
+            .await;
+        let response = Unsubscribe
+            .handle(make_args(&["a", "c"]), &store, &mut state)
+            .await;
+        assert_eq!(make_replies("unsubscribe", &[("a", 1), ("c", 1)]), response);
+        assert!(state.is_subscribed());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_unsubscribe_all(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        Subscribe
+            .handle(make_args(&["b", "a", "c"]), &store, &mut state)
+            .await;
+        let response = Unsubscribe.handle(vec![], &store, &mut state).await;
+        assert_eq!(
+            make_replies("unsubscribe", &[("a", 2), ("b", 1), ("c", 0)]),
+            response
+        );
+        assert!(!state.is_subscribed());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_unsubscribe_all_without_subscriptions(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = Unsubscribe.handle(vec![], &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::Replies(vec![make_reply("unsubscribe", None, 0)]),
+            response
+        );
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_subscribe_missing_channel(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let response = Subscribe.handle(vec![], &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR Missing channel for 'SUBSCRIBE' command".into()
+            ),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_invalid_channel(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let args = vec![crate::resp::RespType::Array(vec![])];
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR Failed to extract channel for 'SUBSCRIBE' command".into()
+            ),
+            Subscribe.handle(args.clone(), &store, &mut state).await
+        );
+        assert_eq!(
+            crate::resp::RespType::SimpleError(
+                "ERR Failed to extract channel for 'UNSUBSCRIBE' command".into()
+            ),
+            Unsubscribe.handle(args, &store, &mut state).await
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! This module contains the SWAPDB command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the SWAPDB options.
+fn parse_swapdb_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(usize, usize)> {
+    let mut iter = iter.into_iter();
+
+    let first = crate::resp::extract_string(&iter.next().context("Missing index")?)
+        .context("Failed to extract index")?
+        .parse::<usize>()
+        .context("Failed to convert index to a number")?;
+
+    let second = crate::resp::extract_string(&iter.next().context("Missing index")?)
+        .context("Failed to extract index")?
+        .parse::<usize>()
+        .context("Failed to convert index to a number")?;
+
+    Ok((first, second))
+}
+
+pub struct Swapdb {
+    databases: crate::store::SharedDatabases,
+}
+
+impl Swapdb {
+    /// Creates the SWAPDB command for the databases.
+    pub fn new(databases: crate::store::SharedDatabases) -> Self {
+        Self { databases }
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for Swapdb {
+    fn static_name() -> String {
+        "SWAPDB".into()
+    }
+
+    fn arity(&self) -> i64 {
+        3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "fast", "dangerous"]
+    }
+
+    /// Handles the SWAPDB command.
+    ///
+    /// Both databases are locked in index order so that concurrent swaps cannot deadlock.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (first, second) = match parse_swapdb_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'SWAPDB' command"
+                ));
+            }
+        };
+
+        if first >= self.databases.len() || second >= self.databases.len() {
+            return crate::resp::RespType::SimpleError("ERR DB index is out of range".into());
+        }
+        if first != second {
+            let (low, high) = (first.min(second), first.max(second));
+            let mut low = self.databases[low].write().await;
+            let mut high = self.databases[high].write().await;
+            std::mem::swap(&mut *low, &mut *high);
+        }
+
+        crate::resp::RespType::SimpleString("OK".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    #[fixture]
+    async fn databases() -> crate::store::SharedDatabases {
+        let databases = crate::store::new_databases(3);
+        for (i, store) in databases.iter().enumerate() {
+            store.write().await.insert(
+                format!("key {i}"),
+                crate::store::Entry::new_string(format!("value {i}")),
+            );
+        }
+        databases
+    }
+
+    fn make_args(first: &str, second: &str) -> Vec<crate::resp::RespType> {
+        vec![
+            crate::resp::RespType::BulkString(Some(first.to_string().into())),
+            crate::resp::RespType::BulkString(Some(second.to_string().into())),
+        ]
+    }
+
+    async fn get_keys(store: &crate::store::SharedStore) -> Vec<String> {
+        let mut keys = store
+            .write()
+            .await
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("SWAPDB", Swapdb::static_name());
+    }
+
+    #[rstest]
+    #[case::ascending("0", "2")]
+    #[case::descending("2", "0")]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[future] databases: crate::store::SharedDatabases,
+        #[case] first: &str,
+        #[case] second: &str,
+    ) {
+        let databases = databases.await;
+        let response = Swapdb::new(databases.clone())
+            .handle(make_args(first, second), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(vec!["key 2"], get_keys(&databases[0]).await);
+        assert_eq!(vec!["key 1"], get_keys(&databases[1]).await);
+        assert_eq!(vec!["key 0"], get_keys(&databases[2]).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_same_index(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[future] databases: crate::store::SharedDatabases,
+    ) {
+        let databases = databases.await;
+        let response = Swapdb::new(databases.clone())
+            .handle(make_args("1", "1"), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::SimpleString("OK".into()), response);
+        assert_eq!(vec!["key 1"], get_keys(&databases[1]).await);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_index(vec![], "ERR Missing index for 'SWAPDB' command")]
+    #[case::invalid_index(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract index for 'SWAPDB' command"
+    )]
+    #[case::missing_second_index(
+        vec![crate::resp::RespType::BulkString(Some("0".into()))],
+        "ERR Missing index for 'SWAPDB' command"
+    )]
+    #[case::non_numeric_index(
+        make_args("0", "abc"),
+        "ERR Failed to convert index to a number for 'SWAPDB' command"
+    )]
+    #[case::first_out_of_range(make_args("3", "0"), "ERR DB index is out of range")]
+    #[case::second_out_of_range(make_args("0", "3"), "ERR DB index is out of range")]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[future] databases: crate::store::SharedDatabases,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let databases = databases.await;
+        let response = Swapdb::new(databases.clone())
+            .handle(args, &store, &mut state)
+            .await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+        for (i, store) in databases.iter().enumerate() {
+            assert_eq!(vec![format!("key {i}")], get_keys(store).await);
+        }
+    }
+}
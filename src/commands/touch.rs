@@ -0,0 +1,176 @@
+//! This module contains the TOUCH command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the TOUCH options.
+fn parse_touch_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Vec<String>> {
+    let keys = iter
+        .into_iter()
+        .map(|key| crate::resp::extract_string(&key).context("Failed to extract key"))
+        .collect::<Result<Vec<_>>>()?;
+    if keys.is_empty() {
+        return Err(anyhow::anyhow!("Missing key"));
+    }
+
+    Ok(keys)
+}
+
+pub struct Touch;
+
+#[async_trait::async_trait]
+impl Command for Touch {
+    fn static_name() -> String {
+        "TOUCH".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["readonly", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, -1, 1)
+    }
+
+    /// Handles the TOUCH command.
+    ///
+    /// Looking up a key marks it as the most recently accessed.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let keys = match parse_touch_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'TOUCH' command"
+                ));
+            }
+        };
+
+        let store = store.read().await;
+        let count = keys.iter().filter(|key| store.get(*key).is_some()).count();
+
+        crate::resp::RespType::Integer(count as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(keys: &[&str]) -> Vec<crate::resp::RespType> {
+        keys.iter()
+            .map(|key| crate::resp::RespType::BulkString(Some(key.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("TOUCH", Touch::static_name());
+    }
+
+    #[rstest]
+    #[case::present(&["a", "b"], 2)]
+    #[case::missing(&["c"], 0)]
+    #[case::mixed(&["a", "c"], 1)]
+    #[case::repeated(&["a", "a"], 2)]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] keys: &[&str],
+        #[case] expected: i64,
+    ) {
+        for key in ["a", "b"] {
+            store
+                .write()
+                .await
+                .insert(key.into(), crate::store::Entry::new_string("value"));
+        }
+        let response = Touch.handle(make_args(keys), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(expected), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_expired(store: crate::store::SharedStore, mut state: crate::state::State) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.write().await.insert(
+            "key".into(),
+            crate::store::Entry::new_string("value").with_deletion(duration),
+        );
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        let response = Touch.handle(make_args(&["key"]), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_updates_last_access(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        for key in ["a", "b"] {
+            store
+                .write()
+                .await
+                .insert(key.into(), crate::store::Entry::new_string("value"));
+        }
+        Touch.handle(make_args(&["a"]), &store, &mut state).await;
+
+        store
+            .read()
+            .await
+            .set_eviction(6, crate::store::EvictionPolicy::AllkeysLru);
+        crate::store::free_memory(&std::sync::Arc::new(vec![store.clone()]))
+            .await
+            .unwrap();
+        let store = store.read().await;
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'TOUCH' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'TOUCH' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Touch.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
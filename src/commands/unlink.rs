@@ -0,0 +1,217 @@
+//! This module contains the UNLINK command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// The free effort above which a value is dropped on a separate task rather than immediately.
+const LAZYFREE_THRESHOLD: usize = 64;
+
+/// Parses the UNLINK options.
+fn parse_unlink_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<Vec<String>> {
+    let keys = iter
+        .into_iter()
+        .map(|key| crate::resp::extract_string(&key).context("Failed to extract key"))
+        .collect::<Result<Vec<_>>>()?;
+    if keys.is_empty() {
+        return Err(anyhow::anyhow!("Missing key"));
+    }
+
+    Ok(keys)
+}
+
+pub struct Unlink;
+
+#[async_trait::async_trait]
+impl Command for Unlink {
+    fn static_name() -> String {
+        "UNLINK".into()
+    }
+
+    fn arity(&self) -> i64 {
+        -2
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["write", "fast"]
+    }
+
+    fn key_positions(&self) -> (i64, i64, i64) {
+        (1, -1, 1)
+    }
+
+    /// Handles the UNLINK command.
+    ///
+    /// The keys are removed immediately. The values with many elements are dropped on a separate
+    /// task so that freeing them does not stall the connection, while the others are dropped
+    /// immediately.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        store: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let keys = match parse_unlink_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!(
+                    "ERR {err} for 'UNLINK' command"
+                ));
+            }
+        };
+
+        let removed = {
+            let mut store = store.write().await;
+            keys.iter()
+                .filter_map(|key| store.remove(key))
+                .collect::<Vec<_>>()
+        };
+        let count = removed.len();
+        let (large, small): (Vec<_>, Vec<_>) = removed
+            .into_iter()
+            .partition(|entry| entry.value.free_effort() > LAZYFREE_THRESHOLD);
+        drop(small);
+        if !large.is_empty() {
+            tokio::spawn(async move { drop(large) });
+        }
+
+        crate::resp::RespType::Integer(count as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(keys: &[&str]) -> Vec<crate::resp::RespType> {
+        keys.iter()
+            .map(|key| crate::resp::RespType::BulkString(Some(key.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("UNLINK", Unlink::static_name());
+    }
+
+    #[rstest]
+    #[case::single(&["a"], 1, &["b", "c"])]
+    #[case::multiple(&["a", "c"], 2, &["b"])]
+    #[case::missing(&["d"], 0, &["a", "b", "c"])]
+    #[case::mixed(&["a", "d", "a"], 1, &["b", "c"])]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] keys: &[&str],
+        #[case] expected_removed: i64,
+        #[case] expected: &[&str],
+    ) {
+        for key in ["a", "b", "c"] {
+            store
+                .write()
+                .await
+                .insert(key.into(), crate::store::Entry::new_string("value"));
+        }
+        let response = Unlink.handle(make_args(keys), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(expected_removed), response);
+
+        let mut remaining = store
+            .write()
+            .await
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        remaining.sort_unstable();
+        assert_eq!(expected, remaining);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_expired(store: crate::store::SharedStore, mut state: crate::state::State) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.write().await.insert(
+            "key".into(),
+            crate::store::Entry::new_string("value").with_deletion(duration),
+        );
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        let response = Unlink.handle(make_args(&["key"]), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_large_list(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let mut entry = crate::store::Entry::new_list();
+        if let crate::store::EntryValue::List(list) = &mut entry.value {
+            list.extend((0..100_000).map(|i| i.to_string()));
+        }
+        store.write().await.insert("key".into(), entry);
+
+        let response = Unlink.handle(make_args(&["key"]), &store, &mut state).await;
+        assert_eq!(crate::resp::RespType::Integer(1), response);
+        assert!(store.write().await.get("key").is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_large_and_small(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+    ) {
+        let mut entry = crate::store::Entry::new_hash();
+        if let crate::store::EntryValue::Hash(hash) = &mut entry.value {
+            hash.extend((0..=LAZYFREE_THRESHOLD).map(|i| (i.to_string(), i.to_string())));
+        }
+        store.write().await.insert("large".into(), entry);
+        store
+            .write()
+            .await
+            .insert("small".into(), crate::store::Entry::new_string("value"));
+
+        let response = Unlink
+            .handle(make_args(&["large", "small"]), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(2), response);
+        assert_eq!(0, store.write().await.len());
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_key(vec![], "ERR Missing key for 'UNLINK' command")]
+    #[case::invalid_key(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract key for 'UNLINK' command"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Unlink.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! This module contains the WAIT command.
+use crate::commands::Command;
+use anyhow::{Context, Result};
+
+/// Parses the WAIT options.
+///
+/// The arguments are returned unconverted so that a non-integer argument can be reported with
+/// the standard integer error.
+fn parse_wait_options<I: IntoIterator<Item = crate::resp::RespType>>(
+    iter: I,
+) -> Result<(String, String)> {
+    let mut iter = iter.into_iter();
+
+    let numreplicas = crate::resp::extract_string(&iter.next().context("Missing numreplicas")?)
+        .context("Failed to extract numreplicas")?;
+
+    let timeout = crate::resp::extract_string(&iter.next().context("Missing timeout")?)
+        .context("Failed to extract timeout")?;
+
+    Ok((numreplicas, timeout))
+}
+
+pub struct Wait;
+
+#[async_trait::async_trait]
+impl Command for Wait {
+    fn static_name() -> String {
+        "WAIT".into()
+    }
+
+    fn arity(&self) -> i64 {
+        3
+    }
+
+    fn flags(&self) -> Vec<&'static str> {
+        vec!["noscript"]
+    }
+
+    /// Handles the WAIT command.
+    ///
+    /// There is no replication, so no replicas ever acknowledge the writes.
+    async fn handle(
+        &self,
+        args: Vec<crate::resp::RespType>,
+        _: &crate::store::SharedStore,
+        _: &mut crate::state::State,
+    ) -> crate::resp::RespType {
+        let (numreplicas, timeout) = match parse_wait_options(args) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("{err}");
+                return crate::resp::RespType::SimpleError(format!("ERR {err} for 'WAIT' command"));
+            }
+        };
+
+        if numreplicas.parse::<i64>().is_err() || timeout.parse::<i64>().is_err() {
+            log::error!("Invalid WAIT arguments: {numreplicas} {timeout}");
+            return crate::resp::RespType::SimpleError(
+                "ERR value is not an integer or out of range".into(),
+            );
+        }
+
+        crate::resp::RespType::Integer(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn store() -> crate::store::SharedStore {
+        crate::store::new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        crate::state::State::new(0)
+    }
+
+    fn make_args(numreplicas: &str, timeout: &str) -> Vec<crate::resp::RespType> {
+        vec![
+            crate::resp::RespType::BulkString(Some(numreplicas.to_string().into())),
+            crate::resp::RespType::BulkString(Some(timeout.to_string().into())),
+        ]
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_name() {
+        assert_eq!("WAIT", Wait::static_name());
+    }
+
+    #[rstest]
+    #[case::zero("0", "0")]
+    #[case::replicas("1", "100")]
+    #[case::negative("-1", "-1")]
+    #[tokio::test]
+    async fn test_handle(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] numreplicas: &str,
+        #[case] timeout: &str,
+    ) {
+        let response = Wait
+            .handle(make_args(numreplicas, timeout), &store, &mut state)
+            .await;
+        assert_eq!(crate::resp::RespType::Integer(0), response);
+    }
+
+    // --- Errors ---
+    #[rstest]
+    #[case::missing_numreplicas(vec![], "ERR Missing numreplicas for 'WAIT' command")]
+    #[case::invalid_numreplicas(
+        vec![crate::resp::RespType::Array(vec![])],
+        "ERR Failed to extract numreplicas for 'WAIT' command"
+    )]
+    #[case::missing_timeout(
+        vec![crate::resp::RespType::BulkString(Some("0".into()))],
+        "ERR Missing timeout for 'WAIT' command"
+    )]
+    #[case::invalid_timeout(
+        vec![
+            crate::resp::RespType::BulkString(Some("0".into())),
+            crate::resp::RespType::Array(vec![]),
+        ],
+        "ERR Failed to extract timeout for 'WAIT' command"
+    )]
+    #[case::non_integer_numreplicas(
+        make_args("abc", "0"),
+        "ERR value is not an integer or out of range"
+    )]
+    #[case::non_integer_timeout(
+        make_args("0", "1.5"),
+        "ERR value is not an integer or out of range"
+    )]
+    #[tokio::test]
+    async fn test_handle_errors(
+        store: crate::store::SharedStore,
+        mut state: crate::state::State,
+        #[case] args: Vec<crate::resp::RespType>,
+        #[case] expected: &str,
+    ) {
+        let response = Wait.handle(args, &store, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+}
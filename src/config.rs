@@ -0,0 +1,979 @@
+//! This module contains the server configuration.
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// The names of the configuration parameters.
+const PARAMETERS: [&str; 27] = [
+    "appendfsync",
+    "appendonly",
+    "bind",
+    "client-query-buffer-limit",
+    "connection-rate-limit",
+    "databases",
+    "dbfilename",
+    "dir",
+    "latency-monitor-threshold",
+    "list-max-listpack-size",
+    "maxclients",
+    "maxmemory",
+    "maxmemory-policy",
+    "port",
+    "protected-mode",
+    "proto-max-bulk-len",
+    "replica-read-only",
+    "replicaof",
+    "requirepass",
+    "save",
+    "tcp-backlog",
+    "tcp-keepalive",
+    "tcp-nodelay",
+    "timeout",
+    "tls-cert-file",
+    "tls-key-file",
+    "tls-port",
+];
+
+/// The parameters that are only read on startup, so cannot be changed at runtime.
+const IMMUTABLE_PARAMETERS: [&str; 8] = [
+    "bind",
+    "databases",
+    "maxclients",
+    "port",
+    "tcp-backlog",
+    "tls-cert-file",
+    "tls-key-file",
+    "tls-port",
+];
+
+/// The values of the `appendfsync` parameter.
+const APPENDFSYNC_VALUES: [&str; 3] = ["always", "everysec", "no"];
+
+/// Whether the parameter can only be set on startup.
+pub fn is_immutable<T: AsRef<str>>(name: T) -> bool {
+    IMMUTABLE_PARAMETERS.contains(&name.as_ref().to_lowercase().as_str())
+}
+
+/// The deepest a configuration file can include other files, to stop include cycles.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Splits a configuration file line into words.
+/// Words are separated by whitespace unless quoted. Double quoted words can contain the `\"`, `\\`,
+/// `\n`, `\r` and `\t` escapes, and single quoted words can contain `\'`.
+fn split_line(line: &str) -> Result<Vec<String>> {
+    let mut words = vec![];
+    let mut chars = line.chars().peekable();
+    loop {
+        while chars.next_if(|char| char.is_whitespace()).is_some() {}
+        let Some(first) = chars.next() else {
+            return Ok(words);
+        };
+
+        let mut word = String::new();
+        match first {
+            '"' => loop {
+                match chars.next().context("Unbalanced quotes")? {
+                    '"' => break,
+                    '\\' => word.push(match chars.next().context("Unbalanced quotes")? {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        char => char,
+                    }),
+                    char => word.push(char),
+                }
+            },
+            '\'' => loop {
+                match chars.next().context("Unbalanced quotes")? {
+                    '\'' => break,
+                    '\\' if chars.peek() == Some(&'\'') => word.push(chars.next().unwrap()),
+                    char => word.push(char),
+                }
+            },
+            char => {
+                word.push(char);
+                while let Some(char) = chars.next_if(|char| !char.is_whitespace()) {
+                    word.push(char);
+                }
+            }
+        }
+        if chars.peek().is_some_and(|char| !char.is_whitespace()) {
+            return Err(anyhow::anyhow!("Closing quote must be followed by a space"));
+        }
+        words.push(word);
+    }
+}
+
+/// The server configuration.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Config {
+    /// How often the append only file is synced to disk: `always`, `everysec` or `no`.
+    pub appendfsync: String,
+    pub appendonly: bool,
+    /// The address to listen on, only read on startup.
+    pub bind: String,
+    /// The most bytes buffered for a client's incomplete message, read when a client connects.
+    pub client_query_buffer_limit: usize,
+    /// The most connections accepted from an IP address each second, 0 for no limit.
+    pub connection_rate_limit: u64,
+    /// The number of logical databases, only read on startup.
+    pub databases: usize,
+    pub dbfilename: String,
+    pub dir: String,
+    /// The number of milliseconds from which events are recorded by the latency monitor, 0 to
+    /// disable it.
+    pub latency_monitor_threshold: u64,
+    /// The number of elements from which a list is reported with the quicklist encoding.
+    pub list_max_listpack_size: usize,
+    /// The maximum number of concurrent client connections, only read on startup.
+    pub maxclients: usize,
+    pub maxmemory: u64,
+    pub maxmemory_policy: crate::store::EvictionPolicy,
+    /// The port to listen on, 0 to only accept TLS connections, only read on startup.
+    pub port: u16,
+    /// Whether clients outside the loopback interface are refused while the server listens on
+    /// every interface and the default user needs no password.
+    pub protected_mode: bool,
+    /// The largest bulk string a client can send, read when a client connects.
+    pub proto_max_bulk_len: usize,
+    /// The commands renamed by `rename-command`, with their new names, where an empty new name
+    /// disables the command. Only read on startup.
+    pub renamed_commands: Vec<(String, String)>,
+    /// Whether writes are refused while replicating a primary.
+    pub replica_read_only: bool,
+    /// The host and port of the primary to replicate, or an empty string for none.
+    pub replicaof: String,
+    /// The password of the default user, or an empty string for none.
+    pub requirepass: String,
+    pub save: String,
+    /// The length of the queue of pending connections, only read on startup.
+    pub tcp_backlog: u32,
+    /// The number of seconds between keepalive probes of idle clients, 0 to disable.
+    /// Read when a client connects.
+    pub tcp_keepalive: u64,
+    /// Whether replies are sent without waiting to be coalesced, read when a client connects.
+    pub tcp_nodelay: bool,
+    /// The number of seconds a client can be idle before being disconnected, 0 to disable.
+    pub timeout: u64,
+    /// The PEM file of the certificate chain presented to TLS clients, only read on startup.
+    pub tls_cert_file: String,
+    /// The PEM file of the private key of the certificate, only read on startup.
+    pub tls_key_file: String,
+    /// The port to accept TLS connections on, 0 to disable, only read on startup.
+    pub tls_port: u16,
+    /// The configuration file loaded on startup, which CONFIG REWRITE writes to.
+    pub config_file: Option<std::path::PathBuf>,
+}
+
+impl Config {
+    /// Creates the default configuration.
+    pub fn new() -> Self {
+        Self {
+            appendfsync: "everysec".into(),
+            appendonly: false,
+            bind: "127.0.0.1".into(),
+            client_query_buffer_limit: crate::resp::ProtocolLimits::default()
+                .max_query_buffer_length,
+            connection_rate_limit: 0,
+            databases: 16,
+            dbfilename: "dump.rdb".into(),
+            dir: ".".into(),
+            latency_monitor_threshold: 0,
+            list_max_listpack_size: 128,
+            maxclients: 10000,
+            maxmemory: 0,
+            maxmemory_policy: crate::store::EvictionPolicy::NoEviction,
+            port: 6379,
+            protected_mode: true,
+            proto_max_bulk_len: crate::resp::ProtocolLimits::default().max_bulk_length,
+            renamed_commands: vec![],
+            replica_read_only: true,
+            replicaof: "".into(),
+            requirepass: "".into(),
+            save: "3600 1 300 100 60 10000".into(),
+            tcp_backlog: 511,
+            tcp_keepalive: 300,
+            tcp_nodelay: true,
+            timeout: 0,
+            tls_cert_file: "".into(),
+            tls_key_file: "".into(),
+            tls_port: 0,
+            config_file: None,
+        }
+    }
+
+    /// Creates the configuration from command line arguments, such as `--port 6380`.
+    /// The values following an option up to the next option are joined by spaces, so values of
+    /// several words, such as `--save 60 1`, need no quotes.
+    /// A first argument that is not an option is a configuration file to load, with the options
+    /// overriding it.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Self> {
+        let mut config = Self::new();
+        let mut args = args.into_iter().peekable();
+        if let Some(path) = args.next_if(|arg| !arg.starts_with("--")) {
+            config.load(&path)?;
+            config.config_file =
+                Some(std::path::absolute(&path).context(format!("Failed to resolve '{path}'"))?);
+        }
+        while let Some(arg) = args.next() {
+            let name = arg
+                .strip_prefix("--")
+                .context(format!("Invalid argument '{arg}'"))?;
+            let mut values = vec![];
+            while let Some(value) = args.next_if(|value| !value.starts_with("--")) {
+                values.push(value);
+            }
+            config.set_words(name, &values)?;
+        }
+        Ok(config)
+    }
+
+    /// Sets a parameter from the words of its value, as given on startup.
+    ///
+    /// The words are joined by spaces, except for `rename-command`, which takes the command and its
+    /// new name, possibly empty, and can be given several times.
+    fn set_words(&mut self, name: &str, values: &[String]) -> Result<()> {
+        if !name.eq_ignore_ascii_case("rename-command") {
+            return self.set(name, values.join(" "));
+        }
+        let [command, new_name] = values else {
+            return Err(anyhow::anyhow!(
+                "Rename-command takes a command and its new name"
+            ));
+        };
+        self.renamed_commands
+            .push((command.clone(), new_name.clone()));
+        Ok(())
+    }
+
+    /// Sets the parameters of a Redis configuration file.
+    ///
+    /// Each line is a parameter followed by its value, where the words of the value are joined by
+    /// spaces. Empty lines and lines starting with `#` are skipped, and `include <path>` loads
+    /// another file in place.
+    pub fn load<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<()> {
+        self.load_with_depth(path.as_ref(), 0)
+    }
+
+    /// Sets the parameters of a configuration file included at the depth.
+    fn load_with_depth(&mut self, path: &std::path::Path, depth: usize) -> Result<()> {
+        if depth > MAX_INCLUDE_DEPTH {
+            return Err(anyhow::anyhow!(
+                "Too many nested includes at '{}'",
+                path.display()
+            ));
+        }
+        let contents = std::fs::read_to_string(path)
+            .context(format!("Failed to read '{}'", path.display()))?;
+
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let context = || format!("Invalid line {} of '{}'", index + 1, path.display());
+            let words = split_line(line).with_context(context)?;
+            let (name, values) = words.split_first().expect("The line is not empty.");
+            if name.eq_ignore_ascii_case("include") {
+                let [include] = values else {
+                    return Err(anyhow::anyhow!("Include takes one path")).with_context(context);
+                };
+                self.load_with_depth(std::path::Path::new(include), depth + 1)?;
+            } else {
+                self.set_words(name, values).with_context(context)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the configuration back to the configuration file loaded on startup.
+    pub fn rewrite(&self) -> Result<()> {
+        let path = self
+            .config_file
+            .as_ref()
+            .context("The server is running without a config file")?;
+        let contents = std::fs::read_to_string(path)
+            .context(format!("Failed to read '{}'", path.display()))?;
+
+        // The new file replaces the old one at once, so a failed write leaves the old one intact.
+        let mut temporary_path = path.clone().into_os_string();
+        temporary_path.push(".tmp");
+        std::fs::write(&temporary_path, self.rewrite_contents(&contents))
+            .context(format!("Failed to write '{}'", path.display()))?;
+        std::fs::rename(&temporary_path, path)
+            .context(format!("Failed to write '{}'", path.display()))
+    }
+
+    /// Rewrites the contents of a configuration file with the current values.
+    ///
+    /// The first line of each parameter is replaced with its value and any later lines of it are
+    /// dropped. Comments, includes and lines that cannot be parsed are kept. Parameters missing
+    /// from the file are appended when their value is not the default.
+    fn rewrite_contents(&self, contents: &str) -> String {
+        let mut written = std::collections::HashSet::new();
+        let mut lines = vec![];
+        for line in contents.lines() {
+            let name = split_line(line.trim())
+                .ok()
+                .and_then(|words| words.into_iter().next())
+                .map(|name| name.to_lowercase())
+                .filter(|name| !name.starts_with('#'));
+            match name.and_then(|name| Some((self.get(&name)?, name))) {
+                Some((value, name)) => {
+                    if written.insert(name.clone()) {
+                        lines.push(format!("{name} {}", format_value(&value)));
+                    }
+                }
+                None => lines.push(line.to_string()),
+            }
+        }
+
+        let defaults = Self::new();
+        for name in PARAMETERS {
+            let value = self.get(name).expect("Every parameter has a value.");
+            if !written.contains(name) && defaults.get(name).as_ref() != Some(&value) {
+                lines.push(format!("{name} {}", format_value(&value)));
+            }
+        }
+
+        let mut contents = lines.join("\n");
+        contents.push('\n');
+        contents
+    }
+
+    /// Gets the value of a parameter.
+    pub fn get<T: AsRef<str>>(&self, name: T) -> Option<String> {
+        match name.as_ref().to_lowercase().as_str() {
+            "appendfsync" => Some(self.appendfsync.clone()),
+            "appendonly" => Some(format_bool(self.appendonly)),
+            "bind" => Some(self.bind.clone()),
+            "client-query-buffer-limit" => Some(self.client_query_buffer_limit.to_string()),
+            "connection-rate-limit" => Some(self.connection_rate_limit.to_string()),
+            "databases" => Some(self.databases.to_string()),
+            "dbfilename" => Some(self.dbfilename.clone()),
+            "dir" => Some(self.dir.clone()),
+            "latency-monitor-threshold" => Some(self.latency_monitor_threshold.to_string()),
+            "list-max-listpack-size" => Some(self.list_max_listpack_size.to_string()),
+            "maxclients" => Some(self.maxclients.to_string()),
+            "maxmemory" => Some(self.maxmemory.to_string()),
+            "maxmemory-policy" => Some(self.maxmemory_policy.as_str().into()),
+            "port" => Some(self.port.to_string()),
+            "protected-mode" => Some(format_bool(self.protected_mode)),
+            "proto-max-bulk-len" => Some(self.proto_max_bulk_len.to_string()),
+            "replica-read-only" => Some(format_bool(self.replica_read_only)),
+            "replicaof" => Some(self.replicaof.clone()),
+            "requirepass" => Some(self.requirepass.clone()),
+            "save" => Some(self.save.clone()),
+            "tcp-backlog" => Some(self.tcp_backlog.to_string()),
+            "tcp-keepalive" => Some(self.tcp_keepalive.to_string()),
+            "tcp-nodelay" => Some(format_bool(self.tcp_nodelay)),
+            "timeout" => Some(self.timeout.to_string()),
+            "tls-cert-file" => Some(self.tls_cert_file.clone()),
+            "tls-key-file" => Some(self.tls_key_file.clone()),
+            "tls-port" => Some(self.tls_port.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Gets the parameters and their values where the name matches the glob pattern, ignoring
+    /// case.
+    pub fn get_matching<T: AsRef<str>>(&self, pattern: T) -> Vec<(String, String)> {
+        let pattern = crate::pattern::Pattern::new(pattern).nocase();
+        PARAMETERS
+            .iter()
+            .filter(|name| pattern.matches(name))
+            .filter_map(|name| Some((name.to_string(), self.get(name)?)))
+            .collect()
+    }
+
+    /// Sets the value of a parameter.
+    pub fn set<T: AsRef<str>, U: Into<String>>(&mut self, name: T, value: U) -> Result<()> {
+        let name = name.as_ref().to_lowercase();
+        let value = value.into();
+        match name.as_str() {
+            "appendfsync" => {
+                let value = value.to_lowercase();
+                if !APPENDFSYNC_VALUES.contains(&value.as_str()) {
+                    return Err(anyhow::anyhow!("Invalid value for '{name}': {value}"));
+                }
+                self.appendfsync = value;
+            }
+            "appendonly" => {
+                self.appendonly =
+                    parse_bool(&value).context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "bind" => {
+                value
+                    .parse::<std::net::IpAddr>()
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+                self.bind = value;
+            }
+            "client-query-buffer-limit" => {
+                self.client_query_buffer_limit = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|client_query_buffer_limit| *client_query_buffer_limit > 0)
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "connection-rate-limit" => {
+                self.connection_rate_limit = value
+                    .parse::<u64>()
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "databases" => {
+                self.databases = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|databases| *databases > 0)
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "dbfilename" => {
+                if value.is_empty() || value.contains(std::path::is_separator) {
+                    return Err(anyhow::anyhow!("Invalid value for '{name}': {value}"));
+                }
+                self.dbfilename = value;
+            }
+            "dir" => {
+                if value.is_empty() {
+                    return Err(anyhow::anyhow!("Invalid value for '{name}': {value}"));
+                }
+                self.dir = value;
+            }
+            "latency-monitor-threshold" => {
+                self.latency_monitor_threshold = value
+                    .parse::<u64>()
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "list-max-listpack-size" => {
+                self.list_max_listpack_size = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|list_max_listpack_size| *list_max_listpack_size > 0)
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "maxclients" => {
+                self.maxclients = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|maxclients| *maxclients > 0)
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "maxmemory" => {
+                self.maxmemory = value
+                    .parse::<u64>()
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "maxmemory-policy" => {
+                self.maxmemory_policy = crate::store::EvictionPolicy::from_string(&value)
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "port" => {
+                self.port = value
+                    .parse::<u16>()
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "protected-mode" => {
+                self.protected_mode =
+                    parse_bool(&value).context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "proto-max-bulk-len" => {
+                self.proto_max_bulk_len = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|proto_max_bulk_len| *proto_max_bulk_len > 0)
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "replica-read-only" => {
+                self.replica_read_only =
+                    parse_bool(&value).context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "replicaof" => {
+                let is_valid = match value.split_whitespace().collect::<Vec<_>>()[..] {
+                    [] => true,
+                    [host, port] => {
+                        host.eq_ignore_ascii_case("no") && port.eq_ignore_ascii_case("one")
+                            || port.parse::<u16>().is_ok()
+                    }
+                    _ => false,
+                };
+                if !is_valid {
+                    return Err(anyhow::anyhow!("Invalid value for '{name}': {value}"));
+                }
+                // Replicating no one is the same as not replicating.
+                self.replicaof = if value.eq_ignore_ascii_case("no one") {
+                    "".into()
+                } else {
+                    value
+                };
+            }
+            "requirepass" => self.requirepass = value,
+            "save" => self.save = value,
+            "tcp-backlog" => {
+                self.tcp_backlog = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|tcp_backlog| *tcp_backlog > 0)
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "tcp-keepalive" => {
+                self.tcp_keepalive = value
+                    .parse::<u64>()
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "tcp-nodelay" => {
+                self.tcp_nodelay =
+                    parse_bool(&value).context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "timeout" => {
+                self.timeout = value
+                    .parse::<u64>()
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            "tls-cert-file" => self.tls_cert_file = value,
+            "tls-key-file" => self.tls_key_file = value,
+            "tls-port" => {
+                self.tls_port = value
+                    .parse::<u16>()
+                    .context(format!("Invalid value for '{name}': {value}"))?;
+            }
+            _ => return Err(anyhow::anyhow!("Unknown parameter '{name}'")),
+        }
+        Ok(())
+    }
+}
+
+/// Parses a `yes` or `no` parameter value.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Formats a parameter value for a configuration file, quoting it unless it is read back as is.
+fn format_value(value: &str) -> String {
+    if !value.is_empty() && split_line(value).is_ok_and(|words| words.join(" ") == value) {
+        return value.into();
+    }
+    let mut quoted = String::from('"');
+    for char in value.chars() {
+        match char {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            char => quoted.push(char),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Formats a boolean parameter value as `yes` or `no`.
+fn format_bool(value: bool) -> String {
+    if value { "yes" } else { "no" }.into()
+}
+
+pub type SharedConfig = Arc<RwLock<Config>>;
+
+/// Creates a new shared configuration with the default values.
+#[cfg(test)]
+pub fn new() -> SharedConfig {
+    share(Config::new())
+}
+
+/// Shares the configuration.
+pub fn share(config: Config) -> SharedConfig {
+    Arc::new(RwLock::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // --- Tests ---
+    // ---- Config ----
+    #[rstest]
+    #[case::client_query_buffer_limit("client-query-buffer-limit", Some("1073741824"))]
+    #[case::appendfsync("appendfsync", Some("everysec"))]
+    #[case::appendonly("appendonly", Some("no"))]
+    #[case::bind("bind", Some("127.0.0.1"))]
+    #[case::connection_rate_limit("connection-rate-limit", Some("0"))]
+    #[case::databases("databases", Some("16"))]
+    #[case::dbfilename("dbfilename", Some("dump.rdb"))]
+    #[case::dir("dir", Some("."))]
+    #[case::latency_monitor_threshold("latency-monitor-threshold", Some("0"))]
+    #[case::list_max_listpack_size("list-max-listpack-size", Some("128"))]
+    #[case::maxclients("maxclients", Some("10000"))]
+    #[case::maxmemory("maxmemory", Some("0"))]
+    #[case::maxmemory_upper("MAXMEMORY", Some("0"))]
+    #[case::maxmemory_policy("maxmemory-policy", Some("noeviction"))]
+    #[case::port("port", Some("6379"))]
+    #[case::protected_mode("protected-mode", Some("yes"))]
+    #[case::proto_max_bulk_len("proto-max-bulk-len", Some("536870912"))]
+    #[case::replica_read_only("replica-read-only", Some("yes"))]
+    #[case::replicaof("replicaof", Some(""))]
+    #[case::requirepass("requirepass", Some(""))]
+    #[case::save("save", Some("3600 1 300 100 60 10000"))]
+    #[case::tcp_backlog("tcp-backlog", Some("511"))]
+    #[case::tcp_keepalive("tcp-keepalive", Some("300"))]
+    #[case::tcp_nodelay("tcp-nodelay", Some("yes"))]
+    #[case::timeout("timeout", Some("0"))]
+    #[case::tls_cert_file("tls-cert-file", Some(""))]
+    #[case::tls_key_file("tls-key-file", Some(""))]
+    #[case::tls_port("tls-port", Some("0"))]
+    #[case::unknown("unknown", None)]
+    fn test_get(#[case] name: &str, #[case] expected: Option<&str>) {
+        assert_eq!(expected.map(String::from), Config::new().get(name));
+    }
+
+    #[rstest]
+    #[case::exact("save", vec![("save", "3600 1 300 100 60 10000")])]
+    #[case::glob("maxmemory*", vec![("maxmemory", "0"), ("maxmemory-policy", "noeviction")])]
+    #[case::none("unknown*", vec![])]
+    fn test_get_matching(#[case] pattern: &str, #[case] expected: Vec<(&str, &str)>) {
+        let expected = expected
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, Config::new().get_matching(pattern));
+    }
+
+    #[rstest]
+    #[case::client_query_buffer_limit("client-query-buffer-limit", "1024")]
+    #[case::connection_rate_limit("connection-rate-limit", "100")]
+    #[case::appendfsync("appendfsync", "always")]
+    #[case::appendonly("appendonly", "yes")]
+    #[case::bind("bind", "::1")]
+    #[case::databases("databases", "1")]
+    #[case::dbfilename("dbfilename", "backup.rdb")]
+    #[case::dir("dir", "/tmp")]
+    #[case::latency_monitor_threshold("latency-monitor-threshold", "100")]
+    #[case::list_max_listpack_size("list-max-listpack-size", "4")]
+    #[case::maxclients("maxclients", "1")]
+    #[case::maxmemory("maxmemory", "100")]
+    #[case::maxmemory_policy("maxmemory-policy", "allkeys-lru")]
+    #[case::port("port", "6380")]
+    #[case::protected_mode("protected-mode", "no")]
+    #[case::proto_max_bulk_len("proto-max-bulk-len", "1024")]
+    #[case::replica_read_only("replica-read-only", "no")]
+    #[case::replicaof("replicaof", "localhost 6380")]
+    #[case::requirepass("requirepass", "password")]
+    #[case::save("SAVE", "")]
+    #[case::tcp_backlog("tcp-backlog", "1024")]
+    #[case::tcp_keepalive("tcp-keepalive", "0")]
+    #[case::tcp_nodelay("tcp-nodelay", "no")]
+    #[case::timeout("timeout", "300")]
+    #[case::tls_cert_file("tls-cert-file", "redis.crt")]
+    #[case::tls_key_file("tls-key-file", "redis.key")]
+    #[case::tls_port("tls-port", "6380")]
+    fn test_set(#[case] name: &str, #[case] value: &str) {
+        let mut config = Config::new();
+        config.set(name, value).unwrap();
+        assert_eq!(Some(value.to_string()), config.get(name));
+    }
+
+    #[rstest]
+    #[case::invalid_appendfsync(
+        "appendfsync",
+        "sometimes",
+        "Invalid value for 'appendfsync': sometimes"
+    )]
+    #[case::invalid_appendonly("appendonly", "maybe", "Invalid value for 'appendonly': maybe")]
+    #[case::invalid_bind("bind", "localhost", "Invalid value for 'bind': localhost")]
+    #[case::invalid_connection_rate_limit(
+        "connection-rate-limit",
+        "-1",
+        "Invalid value for 'connection-rate-limit': -1"
+    )]
+    #[case::invalid_dbfilename(
+        "dbfilename",
+        "dir/dump.rdb",
+        "Invalid value for 'dbfilename': dir/dump.rdb"
+    )]
+    #[case::empty_dir("dir", "", "Invalid value for 'dir': ")]
+    #[case::invalid_port("port", "65536", "Invalid value for 'port': 65536")]
+    #[case::invalid_protected_mode(
+        "protected-mode",
+        "maybe",
+        "Invalid value for 'protected-mode': maybe"
+    )]
+    #[case::invalid_replica_read_only(
+        "replica-read-only",
+        "maybe",
+        "Invalid value for 'replica-read-only': maybe"
+    )]
+    #[case::invalid_replicaof_port(
+        "replicaof",
+        "localhost port",
+        "Invalid value for 'replicaof': localhost port"
+    )]
+    #[case::missing_replicaof_port(
+        "replicaof",
+        "localhost",
+        "Invalid value for 'replicaof': localhost"
+    )]
+    #[case::zero_client_query_buffer_limit(
+        "client-query-buffer-limit",
+        "0",
+        "Invalid value for 'client-query-buffer-limit': 0"
+    )]
+    #[case::invalid_databases("databases", "abc", "Invalid value for 'databases': abc")]
+    #[case::zero_databases("databases", "0", "Invalid value for 'databases': 0")]
+    #[case::zero_list_max_listpack_size(
+        "list-max-listpack-size",
+        "0",
+        "Invalid value for 'list-max-listpack-size': 0"
+    )]
+    #[case::invalid_maxclients("maxclients", "abc", "Invalid value for 'maxclients': abc")]
+    #[case::zero_maxclients("maxclients", "0", "Invalid value for 'maxclients': 0")]
+    #[case::invalid_latency_monitor_threshold(
+        "latency-monitor-threshold",
+        "-1",
+        "Invalid value for 'latency-monitor-threshold': -1"
+    )]
+    #[case::invalid_maxmemory("maxmemory", "abc", "Invalid value for 'maxmemory': abc")]
+    #[case::invalid_maxmemory_policy(
+        "maxmemory-policy",
+        "invalid",
+        "Invalid value for 'maxmemory-policy': invalid"
+    )]
+    #[case::zero_proto_max_bulk_len(
+        "proto-max-bulk-len",
+        "0",
+        "Invalid value for 'proto-max-bulk-len': 0"
+    )]
+    #[case::zero_tcp_backlog("tcp-backlog", "0", "Invalid value for 'tcp-backlog': 0")]
+    #[case::invalid_tcp_keepalive("tcp-keepalive", "-1", "Invalid value for 'tcp-keepalive': -1")]
+    #[case::invalid_tcp_nodelay("tcp-nodelay", "maybe", "Invalid value for 'tcp-nodelay': maybe")]
+    #[case::invalid_timeout("timeout", "-1", "Invalid value for 'timeout': -1")]
+    #[case::invalid_tls_port("tls-port", "65536", "Invalid value for 'tls-port': 65536")]
+    #[case::unknown("unknown", "value", "Unknown parameter 'unknown'")]
+    fn test_set_invalid(#[case] name: &str, #[case] value: &str, #[case] expected: &str) {
+        let mut config = Config::new();
+        let result = config.set(name, value);
+        assert_eq!(expected, result.unwrap_err().to_string());
+        assert_eq!(Config::new(), config);
+    }
+
+    #[rstest]
+    #[case::immutable("databases", true)]
+    #[case::immutable_upper("PORT", true)]
+    #[case::mutable("maxmemory", false)]
+    fn test_is_immutable(#[case] name: &str, #[case] expected: bool) {
+        assert_eq!(expected, is_immutable(name));
+    }
+
+    #[rstest]
+    #[case::replicaof_no_one("no one")]
+    #[case::replicaof_none("")]
+    fn test_set_replicaof_none(#[case] value: &str) {
+        let mut config = Config::new();
+        config.replicaof = "localhost 6380".into();
+        config.set("replicaof", value).unwrap();
+        assert_eq!("", config.replicaof);
+    }
+
+    // ---- Arguments ----
+    #[rstest]
+    fn test_from_args() {
+        let args = ["--port", "6380", "--save", "60", "1", "--appendonly", "yes"];
+        let config = Config::from_args(args.map(String::from)).unwrap();
+        assert_eq!(
+            Config {
+                port: 6380,
+                save: "60 1".into(),
+                appendonly: true,
+                ..Config::new()
+            },
+            config
+        );
+    }
+
+    #[rstest]
+    fn test_from_args_rename_command() {
+        let args = [
+            "--rename-command",
+            "FLUSHALL",
+            "",
+            "--rename-command",
+            "config",
+            "hidden-config",
+        ];
+        let config = Config::from_args(args.map(String::from)).unwrap();
+        assert_eq!(
+            vec![
+                ("FLUSHALL".to_string(), "".to_string()),
+                ("config".to_string(), "hidden-config".to_string()),
+            ],
+            config.renamed_commands
+        );
+    }
+
+    #[rstest]
+    fn test_from_args_empty() {
+        assert_eq!(Config::new(), Config::from_args(vec![]).unwrap());
+    }
+
+    #[rstest]
+    #[case::missing_file(&["/missing/redis.conf"], "Failed to read '/missing/redis.conf'")]
+    #[case::invalid_value(&["--port", "abc"], "Invalid value for 'port': abc")]
+    #[case::unknown_option(&["--unknown", "value"], "Unknown parameter 'unknown'")]
+    #[case::positional_after_file(&["/dev/null", "foo"], "Invalid argument 'foo'")]
+    fn test_from_args_invalid(#[case] args: &[&str], #[case] expected: &str) {
+        let result = Config::from_args(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(expected, result.unwrap_err().to_string());
+    }
+
+    // ---- File ----
+    /// Writes the contents to a new configuration file, returning its path.
+    fn write_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "redis-rs-config-{}-{name}.conf",
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[rstest]
+    #[case::words("a b  c", &["a", "b", "c"])]
+    #[case::surrounding_whitespace("  a\tb  ", &["a", "b"])]
+    #[case::double_quotes(r#"save "60 1" "" x"#, &["save", "60 1", "", "x"])]
+    #[case::double_quote_escapes(r#""a\"b\\c\nd""#, &["a\"b\\c\nd"])]
+    #[case::single_quotes(r"'a b' 'it\'s'", &["a b", "it's"])]
+    fn test_split_line(#[case] line: &str, #[case] expected: &[&str]) {
+        assert_eq!(expected, split_line(line).unwrap());
+    }
+
+    #[rstest]
+    #[case::unbalanced_double(r#"save "60 1"#, "Unbalanced quotes")]
+    #[case::unbalanced_single("save '60 1", "Unbalanced quotes")]
+    #[case::text_after_quote(r#"save "60"1"#, "Closing quote must be followed by a space")]
+    fn test_split_line_invalid(#[case] line: &str, #[case] expected: &str) {
+        assert_eq!(expected, split_line(line).unwrap_err().to_string());
+    }
+
+    #[rstest]
+    fn test_load() {
+        let include = write_file("include", "maxclients 10\ntimeout 30\n");
+        let path = write_file(
+            "load",
+            &format!(
+                "# A comment\n\nPORT 6380\nsave 60 1\n  requirepass \"a password\"\ninclude {}\ntimeout 60\nrename-command flushall \"\"\n",
+                include.display()
+            ),
+        );
+
+        let mut config = Config::new();
+        config.load(&path).unwrap();
+        assert_eq!(
+            Config {
+                port: 6380,
+                save: "60 1".into(),
+                requirepass: "a password".into(),
+                maxclients: 10,
+                timeout: 60,
+                renamed_commands: vec![("flushall".into(), "".into())],
+                ..Config::new()
+            },
+            config
+        );
+    }
+
+    #[rstest]
+    fn test_from_args_file() {
+        let path = write_file("args", "port 6380\ntimeout 60\n");
+        let args = [path.display().to_string(), "--port".into(), "6381".into()];
+        let config = Config::from_args(args).unwrap();
+        assert_eq!(6381, config.port);
+        assert_eq!(60, config.timeout);
+    }
+
+    #[rstest]
+    #[case::unknown_parameter("unknown", "unknown 1", "Invalid line 1 of")]
+    #[case::invalid_value("value", "\nport abc", "Invalid line 2 of")]
+    #[case::unbalanced_quotes("quotes", "save \"60 1", "Invalid line 1 of")]
+    #[case::include_arguments("include", "include a b", "Invalid line 1 of")]
+    #[case::rename_command_arguments("rename", "rename-command get", "Invalid line 1 of")]
+    #[case::missing_include(
+        "missing",
+        "include /missing/redis.conf",
+        "Failed to read '/missing/redis.conf'"
+    )]
+    fn test_load_invalid(#[case] name: &str, #[case] contents: &str, #[case] expected: &str) {
+        let path = write_file(name, contents);
+        let mut config = Config::new();
+        let result = config.load(&path);
+        assert!(result.unwrap_err().to_string().starts_with(expected));
+    }
+
+    #[rstest]
+    fn test_load_include_cycle() {
+        let path =
+            std::env::temp_dir().join(format!("redis-rs-config-{}-cycle.conf", std::process::id()));
+        std::fs::write(&path, format!("include {}\n", path.display())).unwrap();
+        let mut config = Config::new();
+        let result = config.load(&path);
+        assert!(format!("{:#}", result.unwrap_err()).contains("Too many nested includes"));
+    }
+
+    // ---- Rewrite ----
+    #[rstest]
+    #[case::value("42", "42")]
+    #[case::words("60 1", "60 1")]
+    #[case::empty("", r#""""#)]
+    #[case::spaces(" a  b", r#"" a  b""#)]
+    #[case::quotes(r#"a "b""#, r#""a \"b\"""#)]
+    #[case::escapes("a\\b\n", r#""a\\b\n""#)]
+    fn test_format_value(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(expected, format_value(value));
+        assert_eq!(value, split_line(&format_value(value)).unwrap().join(" "));
+    }
+
+    #[rstest]
+    #[case::unchanged("requirepass \"a  password\"\n", "requirepass \"a  password\"\n")]
+    #[case::replaced(
+        "# Comment\nPORT 6380\n\nrequirepass old\n",
+        "# Comment\nport 6379\n\nrequirepass \"a  password\"\n"
+    )]
+    #[case::duplicates("timeout 1\ntimeout 2\n", "timeout 0\nrequirepass \"a  password\"\n")]
+    #[case::kept(
+        "include other.conf\nunknown value\n# port 6380\n",
+        "include other.conf\nunknown value\n# port 6380\nrequirepass \"a  password\"\n"
+    )]
+    #[case::appended("", "requirepass \"a  password\"\n")]
+    fn test_rewrite_contents(#[case] contents: &str, #[case] expected: &str) {
+        let mut config = Config::new();
+        config.requirepass = "a  password".into();
+        assert_eq!(expected, config.rewrite_contents(contents));
+    }
+
+    #[rstest]
+    fn test_rewrite() {
+        let path = write_file("rewrite", "# Comment\nport 6380\n");
+        let mut config = Config::from_args([path.display().to_string()]).unwrap();
+        config.port = 6381;
+        config.rewrite().unwrap();
+        assert_eq!(
+            "# Comment\nport 6381\n",
+            std::fs::read_to_string(&path).unwrap()
+        );
+        assert_eq!(
+            config,
+            Config::from_args([path.display().to_string()]).unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_rewrite_without_file() {
+        let result = Config::new().rewrite();
+        assert_eq!(
+            "The server is running without a config file",
+            result.unwrap_err().to_string()
+        );
+    }
+}
@@ -1,7 +1,17 @@
 //! This module contains the handler.
 use anyhow::Result;
-use bytes::BytesMut;
-use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use futures_util::{FutureExt, SinkExt, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+
+/// The length from which bulk string replies are written straight from the value instead of being
+/// copied into the write buffer.
+const STREAMING_THRESHOLD: usize = 16 * 1024;
+/// The size of the chunks streamed bulk strings are written in.
+const STREAMING_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The commands allowed while a RESP2 connection is in subscribe mode.
+/// RESP3 connections can run any command, as pub/sub replies are pushes.
+const SUBSCRIBE_MODE_COMMANDS: [&str; 5] = ["SUBSCRIBE", "UNSUBSCRIBE", "PING", "RESET", "QUIT"];
 
 async fn get_response(
     message: crate::resp::RespType,
@@ -9,19 +19,58 @@ async fn get_response(
     register: &crate::commands::SharedRegister,
     state: &mut crate::state::State,
 ) -> crate::resp::RespType {
-    let (command, args) = crate::resp::extract_command(message).unwrap();
+    let (command, args) = match crate::resp::extract_command(message) {
+        Ok(result) => result,
+        Err(err) => {
+            log::error!("{err}");
+            return crate::resp::RespType::SimpleError(format!("ERR {err}"));
+        }
+    };
+    state.last_command = Some(command.to_lowercase());
+    if state.is_subscribed()
+        && state.protocol_version == crate::state::ProtocolVersion::V2
+        && !SUBSCRIBE_MODE_COMMANDS.contains(&command.to_uppercase().as_str())
+    {
+        return crate::resp::RespType::SimpleError(format!(
+            "ERR Can't execute '{}': only SUBSCRIBE / UNSUBSCRIBE / PING / QUIT / RESET are allowed in this context",
+            command.to_lowercase()
+        ));
+    }
     register
         .read()
         .await
-        .handle(command, args, &store, state)
+        .handle(command, args, store, state)
         .await
 }
 
+/// Receives the next line of the monitor feed, never resolving while the connection is not
+/// monitoring. Lines dropped because the connection fell behind are skipped.
+async fn next_monitor_line(
+    monitor: &mut Option<tokio::sync::broadcast::Receiver<String>>,
+) -> String {
+    let Some(receiver) = monitor else {
+        return std::future::pending().await;
+    };
+    loop {
+        match receiver.recv().await {
+            Ok(line) => return line,
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                return std::future::pending().await
+            }
+        }
+    }
+}
+
 /// Handles reading and writing RESP messages over a TCP stream.
 pub struct RespHandler<T> {
-    stream: T,
-    buffer: BytesMut,
+    framed: tokio_util::codec::Framed<T, crate::codec::RespCodec>,
     state: crate::state::State,
+    idle_timeout: Option<tokio::time::Duration>,
+    /// Cancelled when the server shuts down, closing the connection between commands.
+    shutdown: tokio_util::sync::CancellationToken,
+    /// The connection's entry in the registry of connected clients, updated after each command.
+    registration: Option<crate::clients::Registration>,
 }
 
 impl<T> RespHandler<T>
@@ -29,39 +78,172 @@ where
     T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
 {
     /// Creates a new RESP handler.
-    pub fn new(stream: T, client_id: usize) -> Self {
+    pub fn new(stream: T, client_id: u64) -> Self {
         Self {
-            stream,
-            buffer: BytesMut::with_capacity(512),
+            framed: tokio_util::codec::Framed::with_capacity(
+                stream,
+                crate::codec::RespCodec::new(),
+                512,
+            ),
             state: crate::state::State::new(client_id),
+            idle_timeout: None,
+            shutdown: tokio_util::sync::CancellationToken::new(),
+            registration: None,
+        }
+    }
+
+    /// Closes the connection if no message is received within the timeout.
+    pub fn with_idle_timeout(mut self, idle_timeout: tokio::time::Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Closes the connection once the token is cancelled, letting a running command finish first.
+    pub fn with_shutdown(mut self, shutdown: tokio_util::sync::CancellationToken) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Keeps the connection's entry in the registry of connected clients up to date.
+    pub fn with_registration(mut self, registration: crate::clients::Registration) -> Self {
+        self.state.addr = Some(registration.addr().into());
+        self.registration = Some(registration);
+        self
+    }
+
+    /// Refuses the commands other than those authenticating the connection until it authenticates,
+    /// if authentication is required.
+    pub fn with_authentication_required(mut self, required: bool) -> Self {
+        self.state.authenticated = !required;
+        self
+    }
+
+    /// Rejects messages declaring lengths outside the protocol limits.
+    pub fn with_protocol_limits(mut self, protocol_limits: crate::resp::ProtocolLimits) -> Self {
+        self.framed.codec_mut().protocol_limits = protocol_limits;
+        self
+    }
+
+    /// Reads a RESP message from the TCP stream, giving up once the idle timeout elapses.
+    /// Subscribed and monitoring connections never time out, as they wait for messages rather than
+    /// send commands.
+    async fn read_stream_with_timeout(&mut self) -> Result<Option<crate::resp::RespType>> {
+        match self.idle_timeout {
+            Some(idle_timeout) if !self.state.is_subscribed() && !self.state.monitoring => {
+                tokio::time::timeout(idle_timeout, self.read_stream())
+                    .await
+                    .unwrap_or_else(|_| {
+                        log::info!("Client {} timed out.", self.state.client_id);
+                        Ok(None)
+                    })
+            }
+            _ => self.read_stream().await,
         }
     }
 
     /// Reads a RESP message from the TCP stream.
+    /// Messages already in the buffer, such as pipelined commands, are returned before reading
+    /// again. Reading continues until the whole message is buffered, as it may be split across
+    /// reads.
+    /// The buffered replies are flushed once no message is ready, so the replies to a pipeline are
+    /// written together.
     pub async fn read_stream(&mut self) -> Result<Option<crate::resp::RespType>> {
-        let bytes = self.stream.read_buf(&mut self.buffer).await?;
-        if bytes == 0 {
-            Ok(None)
-        } else {
-            Ok(Some(crate::resp::RespType::from_bytes(&mut self.buffer)?))
+        if let Some(Some(message)) = self.framed.next().now_or_never() {
+            return message.map(Some);
         }
+        self.flush_stream().await?;
+        self.framed.next().await.transpose()
     }
 
-    /// Writes a RESP message to the TCP stream.
+    /// Buffers a RESP message for the TCP stream, encoded for the connection's protocol version.
+    /// The message is written once the buffer is flushed, or once it fills up.
+    ///
+    /// Large bulk strings are streamed in chunks after their header, bypassing the codec so that
+    /// they are never copied into its write buffer.
     pub async fn write_stream(&mut self, value: crate::resp::RespType) -> Result<()> {
-        self.stream.write_all(value.serialize().as_bytes()).await?;
+        self.framed.codec_mut().protocol_version = self.state.protocol_version.clone();
+        match value {
+            crate::resp::RespType::BulkString(Some(value))
+                if value.len() >= STREAMING_THRESHOLD =>
+            {
+                self.framed.flush().await?;
+                let stream = self.framed.get_mut();
+                stream
+                    .write_all(format!("${}\r\n", value.len()).as_bytes())
+                    .await?;
+                for chunk in value.chunks(STREAMING_CHUNK_SIZE) {
+                    stream.write_all(chunk).await?;
+                }
+                stream.write_all(b"\r\n").await?;
+            }
+            value => self.framed.feed(value).await?,
+        }
         Ok(())
     }
 
-    /// Runs the handler.
+    /// Writes the buffered messages to the TCP stream.
+    pub async fn flush_stream(&mut self) -> Result<()> {
+        self.framed.flush().await
+    }
+
+    /// Runs the handler, using the selected database for each command.
+    /// The connection is closed once a command asks for it to be closed after its reply, or after
+    /// a message breaks the protocol, which is replied to with the protocol error.
+    /// Once MONITOR is run, the commands run by every connection are streamed between replies.
     pub async fn run(
         &mut self,
-        store: crate::store::SharedStore,
+        databases: crate::store::SharedDatabases,
         register: crate::commands::SharedRegister,
     ) {
-        while let Ok(Some(message)) = self.read_stream().await {
-            let response = get_response(message, &store, &register, &mut self.state).await;
-            self.write_stream(response).await.unwrap();
+        let shutdown = self.shutdown.clone();
+        let mut monitor = None;
+        loop {
+            let message = tokio::select! {
+                message = self.read_stream_with_timeout() => message,
+                line = next_monitor_line(&mut monitor) => {
+                    let written = self.write_stream(crate::resp::RespType::SimpleString(line)).await;
+                    if written.is_err() || self.flush_stream().await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                _ = shutdown.cancelled() => {
+                    log::info!("Closing client {} for shutdown.", self.state.client_id);
+                    Ok(None)
+                }
+            };
+            let message = match message {
+                Ok(Some(message)) => message,
+                Ok(None) => break,
+                Err(err) => {
+                    // Protocol errors are reported to the client before closing the connection.
+                    if let Some(err) = err.downcast_ref::<crate::resp::ProtocolError>() {
+                        log::error!("Client {}: {err}", self.state.client_id);
+                        let _ = self
+                            .write_stream(crate::resp::RespType::SimpleError(err.to_string()))
+                            .await;
+                        let _ = self.framed.close().await;
+                    }
+                    break;
+                }
+            };
+            let store = &databases[self.state.db];
+            let response = get_response(message, store, &register, &mut self.state).await;
+            if let Some(registration) = &self.registration {
+                registration.update(&self.state);
+            }
+            if let Err(err) = self.write_stream(response).await {
+                log::debug!("Client {}: {err}", self.state.client_id);
+                break;
+            }
+            if self.state.monitoring && monitor.is_none() {
+                monitor = Some(register.read().await.monitor().subscribe());
+            }
+            if self.state.close_after_reply {
+                log::info!("Client {} closed the connection.", self.state.client_id);
+                let _ = self.framed.close().await;
+                break;
+            }
         }
     }
 }
@@ -70,7 +252,9 @@ where
 mod tests {
     use super::*;
     use crate::commands::Command;
+    use bytes::BytesMut;
     use rstest::{fixture, rstest};
+    use tokio::io::AsyncReadExt;
 
     // --- Fixtures ---
     #[fixture]
@@ -78,6 +262,11 @@ mod tests {
         crate::store::new()
     }
 
+    #[fixture]
+    fn databases() -> crate::store::SharedDatabases {
+        crate::store::new_databases(1)
+    }
+
     #[fixture]
     fn register() -> crate::commands::SharedRegister {
         std::sync::Arc::new(tokio::sync::RwLock::new(crate::commands::Register::new()))
@@ -107,8 +296,8 @@ mod tests {
         (client_stream, RespHandler::new(server_stream, 0))
     }
 
-    fn make_handle_args(args: &Vec<crate::resp::RespType>) -> Vec<crate::resp::RespType> {
-        args.clone().into_iter().skip(1).collect()
+    fn make_handle_args(args: &[crate::resp::RespType]) -> Vec<crate::resp::RespType> {
+        args.iter().skip(1).cloned().collect()
     }
 
     // --- Tests ---
@@ -136,6 +325,7 @@ mod tests {
             .await;
         let response = get_response(message, &store, &register, &mut state).await;
         assert_eq!(expected, response);
+        assert_eq!(Some("ping".into()), state.last_command);
     }
 
     #[rstest]
@@ -185,7 +375,7 @@ mod tests {
             .await
             .register(Box::new(crate::commands::get::Get));
         store
-            .lock()
+            .write()
             .await
             .insert(key.clone(), crate::store::Entry::new_string(value.clone()));
         let args = vec![
@@ -231,7 +421,7 @@ mod tests {
         let set_message = crate::resp::RespType::Array(args);
         let response = get_response(set_message, &store, &register, &mut state).await;
         assert_eq!(expected, response);
-        assert_eq!(*expected_store.lock().await, *store.lock().await);
+        assert_eq!(*expected_store.write().await, *store.write().await);
     }
 
     #[rstest]
@@ -264,7 +454,7 @@ mod tests {
         let set_message = crate::resp::RespType::Array(args);
         let response = get_response(set_message, &store, &register, &mut state).await;
         assert_eq!(expected, response);
-        assert_eq!(*expected_store.lock().await, *store.lock().await);
+        assert_eq!(*expected_store.write().await, *store.write().await);
     }
 
     #[rstest]
@@ -283,15 +473,172 @@ mod tests {
         assert_eq!(expected, response);
     }
 
+    #[rstest]
+    #[case::not_array(
+        crate::resp::RespType::SimpleString("PING".into()),
+        "ERR Invalid command: SimpleString(\"PING\")"
+    )]
+    #[case::empty_array(crate::resp::RespType::Array(vec![]), "ERR Command is empty.")]
+    #[tokio::test]
+    async fn test_get_response_malformed_command(
+        store: crate::store::SharedStore,
+        register: crate::commands::SharedRegister,
+        mut state: crate::state::State,
+        #[case] message: crate::resp::RespType,
+        #[case] expected: &str,
+    ) {
+        let response = get_response(message, &store, &register, &mut state).await;
+        assert_eq!(
+            crate::resp::RespType::SimpleError(expected.into()),
+            response
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_get_response_subscribe_mode(
+        store: crate::store::SharedStore,
+        register: crate::commands::SharedRegister,
+        mut state: crate::state::State,
+    ) {
+        register.write().await.register_multiple(vec![
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::subscribe::Subscribe),
+            Box::new(crate::commands::subscribe::Unsubscribe),
+        ]);
+        let message = |args: &[&str]| {
+            crate::resp::RespType::Array(
+                args.iter()
+                    .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+                    .collect(),
+            )
+        };
+
+        get_response(
+            message(&["SUBSCRIBE", "channel"]),
+            &store,
+            &register,
+            &mut state,
+        )
+        .await;
+        let response = get_response(message(&["get", "key"]), &store, &register, &mut state).await;
+        let expected = crate::resp::RespType::SimpleError(
+            "ERR Can't execute 'get': only SUBSCRIBE / UNSUBSCRIBE / PING / QUIT / RESET are allowed in this context".into(),
+        );
+        assert_eq!(expected, response);
+
+        get_response(message(&["UNSUBSCRIBE"]), &store, &register, &mut state).await;
+        let response = get_response(message(&["get", "key"]), &store, &register, &mut state).await;
+        assert_eq!(crate::resp::RespType::BulkString(None), response);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_get_response_subscribe_mode_v3(
+        store: crate::store::SharedStore,
+        register: crate::commands::SharedRegister,
+        mut state: crate::state::State,
+    ) {
+        register.write().await.register_multiple(vec![
+            Box::new(crate::commands::get::Get),
+            Box::new(crate::commands::subscribe::Subscribe),
+        ]);
+        let message = |args: &[&str]| {
+            crate::resp::RespType::Array(
+                args.iter()
+                    .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+                    .collect(),
+            )
+        };
+        state.protocol_version = crate::state::ProtocolVersion::V3;
+
+        get_response(
+            message(&["SUBSCRIBE", "channel"]),
+            &store,
+            &register,
+            &mut state,
+        )
+        .await;
+        let response = get_response(message(&["get", "key"]), &store, &register, &mut state).await;
+        assert_eq!(crate::resp::RespType::Null(), response);
+        assert!(state.is_subscribed());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_get_response_unauthenticated(
+        store: crate::store::SharedStore,
+        register: crate::commands::SharedRegister,
+        mut state: crate::state::State,
+    ) {
+        register.write().await.register_multiple(vec![
+            Box::new(crate::commands::ping::Ping),
+            Box::new(crate::commands::hello::Hello::new(crate::acl::new())),
+        ]);
+        let message = |args: &[&str]| {
+            crate::resp::RespType::Array(
+                args.iter()
+                    .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+                    .collect(),
+            )
+        };
+        state.authenticated = false;
+
+        let response = get_response(message(&["ping"]), &store, &register, &mut state).await;
+        let expected = crate::resp::RespType::SimpleError("NOAUTH Authentication required.".into());
+        assert_eq!(expected, response);
+
+        let response = get_response(message(&["hello"]), &store, &register, &mut state).await;
+        assert!(matches!(response, crate::resp::RespType::Map(_)));
+    }
+
     mod handler {
         use super::*;
         #[rstest]
         fn test_handler_new() {
             let (_, server_stream) = tokio::io::duplex(512);
             let handler = RespHandler::new(server_stream, 0);
-            assert_eq!(handler.buffer.capacity(), 512);
-            assert!(handler.buffer.is_empty());
+            assert_eq!(handler.framed.read_buffer().capacity(), 512);
+            assert!(handler.framed.read_buffer().is_empty());
+            assert!(handler.framed.write_buffer().is_empty());
             assert_eq!(handler.state, crate::state::State::new(0));
+            assert_eq!(handler.idle_timeout, None);
+            assert_eq!(
+                handler.framed.codec().protocol_limits,
+                crate::resp::ProtocolLimits::default()
+            );
+        }
+
+        #[rstest]
+        fn test_handler_with_protocol_limits() {
+            let (_, server_stream) = tokio::io::duplex(512);
+            let protocol_limits = crate::resp::ProtocolLimits {
+                max_bulk_length: 1,
+                max_multibulk_length: 2,
+                ..Default::default()
+            };
+            let handler = RespHandler::new(server_stream, 0).with_protocol_limits(protocol_limits);
+            assert_eq!(handler.framed.codec().protocol_limits, protocol_limits);
+        }
+
+        #[rstest]
+        #[case::required(true, false)]
+        #[case::not_required(false, true)]
+        fn test_handler_with_authentication_required(
+            #[case] required: bool,
+            #[case] expected: bool,
+        ) {
+            let (_, server_stream) = tokio::io::duplex(512);
+            let handler = RespHandler::new(server_stream, 0).with_authentication_required(required);
+            assert_eq!(expected, handler.state.authenticated);
+        }
+
+        #[rstest]
+        fn test_handler_with_idle_timeout() {
+            let (_, server_stream) = tokio::io::duplex(512);
+            let idle_timeout = tokio::time::Duration::from_secs(5);
+            let handler = RespHandler::new(server_stream, 0).with_idle_timeout(idle_timeout);
+            assert_eq!(handler.idle_timeout, Some(idle_timeout));
         }
 
         #[rstest]
@@ -303,7 +650,7 @@ mod tests {
             ),
         ) -> Result<()> {
             let (mut client_stream, mut handler) = stream_and_handler;
-            client_stream.write(b"").await?;
+            client_stream.write_all(b"").await?;
             client_stream.shutdown().await?;
 
             match handler.read_stream().await {
@@ -326,7 +673,7 @@ mod tests {
             let (mut client_stream, mut handler) = stream_and_handler;
 
             let expected = crate::resp::RespType::SimpleString(value);
-            client_stream.write(expected.serialize().as_bytes()).await?;
+            client_stream.write_all(&expected.serialize()).await?;
             client_stream.shutdown().await?;
 
             match handler.read_stream().await {
@@ -337,6 +684,32 @@ mod tests {
             Ok(())
         }
 
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_read_split(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+        ) -> Result<()> {
+            let (mut client_stream, mut handler) = stream_and_handler;
+            let reader = tokio::spawn(async move { handler.read_stream().await });
+
+            let message = b"*2\r\n$4\r\nECHO\r\n$5\r\nhello\r\n";
+            for chunk in message.chunks(3) {
+                client_stream.write_all(chunk).await?;
+                tokio::task::yield_now().await;
+            }
+
+            let expected = crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some("ECHO".into())),
+                crate::resp::RespType::BulkString(Some("hello".into())),
+            ]);
+            assert_eq!(Some(expected), reader.await??);
+
+            Ok(())
+        }
+
         #[rstest]
         #[tokio::test]
         async fn test_handler_write(
@@ -350,6 +723,7 @@ mod tests {
 
             let expected = crate::resp::RespType::SimpleString(value);
             handler.write_stream(expected.clone()).await?;
+            handler.flush_stream().await?;
 
             let mut buffer = BytesMut::with_capacity(512);
             client_stream.read_buf(&mut buffer).await?;
@@ -358,6 +732,145 @@ mod tests {
             Ok(())
         }
 
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_write_batched(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+        ) -> Result<()> {
+            let (mut client_stream, mut handler) = stream_and_handler;
+
+            let messages = [
+                crate::resp::RespType::SimpleString("first".into()),
+                crate::resp::RespType::SimpleString("second".into()),
+            ];
+            for message in messages.clone() {
+                handler.write_stream(message).await?;
+            }
+            let mut buffer = BytesMut::with_capacity(512);
+            assert!(client_stream.read_buf(&mut buffer).now_or_never().is_none());
+
+            handler.flush_stream().await?;
+            let expected = messages
+                .iter()
+                .map(|message| message.serialize())
+                .collect::<Vec<_>>()
+                .concat();
+            while buffer.len() < expected.len() {
+                client_stream.read_buf(&mut buffer).await?;
+            }
+            assert_eq!(expected, buffer);
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[case::below_threshold(STREAMING_THRESHOLD - 1)]
+        #[case::threshold(STREAMING_THRESHOLD)]
+        #[case::multiple_chunks(STREAMING_CHUNK_SIZE * 2 + 1)]
+        #[tokio::test]
+        async fn test_handler_write_bulk_string(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            #[case] length: usize,
+        ) -> Result<()> {
+            let (mut client_stream, mut handler) = stream_and_handler;
+
+            let expected = crate::resp::RespType::BulkString(Some("a".repeat(length).into()));
+            let writer = tokio::spawn({
+                let expected = expected.clone();
+                async move {
+                    handler.write_stream(expected).await.unwrap();
+                    handler.flush_stream().await.unwrap();
+                    handler.framed.write_buffer().capacity()
+                }
+            });
+
+            let expected = expected.serialize();
+            let mut buffer = BytesMut::with_capacity(expected.len());
+            while buffer.len() < expected.len() {
+                client_stream.read_buf(&mut buffer).await?;
+            }
+            assert_eq!(expected, buffer);
+            // Only the header of a streamed bulk string goes through the write buffer.
+            assert_eq!(length >= STREAMING_THRESHOLD, writer.await? < length);
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_get_large_value(
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+            key: String,
+        ) -> Result<()> {
+            register
+                .write()
+                .await
+                .register(Box::new(crate::commands::get::Get));
+            let value = "abcdefgh".repeat(128 * 1024);
+            databases[0]
+                .write()
+                .await
+                .insert(key.clone(), crate::store::Entry::new_string(value.clone()));
+            let (mut client_stream, server_stream) = tokio::io::duplex(4096);
+            let mut handler = RespHandler::new(server_stream, 0);
+            let server = tokio::spawn(async move { handler.run(databases, register).await });
+
+            let message = crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some("GET".into())),
+                crate::resp::RespType::BulkString(Some(key.into())),
+            ]);
+            client_stream.write_all(&message.serialize()).await?;
+            client_stream.shutdown().await?;
+
+            let mut buffer = BytesMut::new();
+            while client_stream.read_buf(&mut buffer).await? > 0 {}
+            server.await?;
+            assert_eq!(
+                crate::resp::RespType::BulkString(Some(value.into())),
+                crate::resp::RespType::from_bytes(&mut buffer)?
+            );
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_get_large_value_disconnected(
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+            key: String,
+        ) -> Result<()> {
+            register
+                .write()
+                .await
+                .register(Box::new(crate::commands::get::Get));
+            let value = "abcdefgh".repeat(128 * 1024);
+            databases[0]
+                .write()
+                .await
+                .insert(key.clone(), crate::store::Entry::new_string(value));
+            let (mut client_stream, server_stream) = tokio::io::duplex(4096);
+            let mut handler = RespHandler::new(server_stream, 0);
+
+            let message = crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some("GET".into())),
+                crate::resp::RespType::BulkString(Some(key.into())),
+            ]);
+            client_stream.write_all(&message.serialize()).await?;
+            drop(client_stream);
+
+            handler.run(databases, register).await;
+
+            Ok(())
+        }
+
         #[rstest]
         #[tokio::test]
         async fn test_handler_run(
@@ -365,7 +878,7 @@ mod tests {
                 tokio::io::DuplexStream,
                 RespHandler<tokio::io::DuplexStream>,
             ),
-            store: crate::store::SharedStore,
+            databases: crate::store::SharedDatabases,
             register: crate::commands::SharedRegister,
         ) -> Result<()> {
             register
@@ -377,12 +890,10 @@ mod tests {
             let message = crate::resp::RespType::Array(vec![crate::resp::RespType::SimpleString(
                 "PING".into(),
             )]);
-            client_stream
-                .write_all(message.serialize().as_bytes())
-                .await?;
+            client_stream.write_all(&message.serialize()).await?;
             client_stream.shutdown().await?;
 
-            handler.run(store, register).await;
+            handler.run(databases, register).await;
 
             let mut buffer = BytesMut::with_capacity(512);
             client_stream.read_buf(&mut buffer).await?;
@@ -391,5 +902,479 @@ mod tests {
 
             Ok(())
         }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_pipelined(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            {
+                let mut register = register.write().await;
+                register.register(Box::new(crate::commands::ping::Ping));
+                register.register(Box::new(crate::commands::echo::Echo));
+            }
+            let (mut client_stream, mut handler) = stream_and_handler;
+            let server = tokio::spawn(async move { handler.run(databases, register).await });
+
+            client_stream
+                .write_all(b"PING\r\n*2\r\n$4\r\nECHO\r\n$5\r\nhello\r\n*1\r\n$4\r\nPING\r\n")
+                .await?;
+
+            let expected = "+PONG\r\n$5\r\nhello\r\n+PONG\r\n";
+            let mut buffer = BytesMut::with_capacity(512);
+            while buffer.len() < expected.len() {
+                client_stream.read_buf(&mut buffer).await?;
+            }
+            assert_eq!(expected, buffer);
+
+            client_stream.shutdown().await?;
+            server.await?;
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_binary_value(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            {
+                let mut register = register.write().await;
+                register.register(Box::new(crate::commands::set::Set));
+                register.register(Box::new(crate::commands::get::Get));
+            }
+            let (mut client_stream, mut handler) = stream_and_handler;
+
+            client_stream
+                .write_all(b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$4\r\n\xff\x00\r\n\r\n")
+                .await?;
+            client_stream
+                .write_all(b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n")
+                .await?;
+            client_stream.shutdown().await?;
+            handler.run(databases, register).await;
+
+            let expected = b"+OK\r\n$4\r\n\xff\x00\r\n\r\n".as_slice();
+            let mut buffer = BytesMut::with_capacity(512);
+            while buffer.len() < expected.len() {
+                client_stream.read_buf(&mut buffer).await?;
+            }
+            assert_eq!(expected, buffer);
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_quit(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            register.write().await.register_multiple(vec![
+                Box::new(crate::commands::ping::Ping),
+                Box::new(crate::commands::quit::Quit),
+            ]);
+            let (mut client_stream, mut handler) = stream_and_handler;
+
+            let command = |name: &str| {
+                crate::resp::RespType::Array(vec![crate::resp::RespType::BulkString(Some(
+                    name.to_string().into(),
+                ))])
+                .serialize()
+            };
+            client_stream.write_all(&command("QUIT")).await?;
+            handler.run(databases, register).await;
+            assert!(client_stream.write_all(&command("PING")).await.is_ok());
+
+            let mut buffer = BytesMut::with_capacity(512);
+            while client_stream.read_buf(&mut buffer).await? > 0 {}
+            let expected = crate::resp::RespType::SimpleString("OK".into());
+            assert_eq!(expected.serialize(), buffer);
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_quit_pipelined(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            register.write().await.register_multiple(vec![
+                Box::new(crate::commands::quit::Quit),
+                Box::new(crate::commands::set::Set),
+            ]);
+            let (mut client_stream, mut handler) = stream_and_handler;
+
+            let command = |args: &[&str]| {
+                crate::resp::RespType::Array(
+                    args.iter()
+                        .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+                        .collect(),
+                )
+                .serialize()
+            };
+            let message = [command(&["QUIT"]), command(&["SET", "key", "value"])].concat();
+            client_stream.write_all(&message).await?;
+            handler.run(databases.clone(), register).await;
+
+            let mut buffer = BytesMut::with_capacity(512);
+            while client_stream.read_buf(&mut buffer).await? > 0 {}
+            let expected = crate::resp::RespType::SimpleString("OK".into());
+            assert_eq!(expected.serialize(), buffer);
+            // Commands sent after QUIT are never run.
+            assert!(databases[0].read().await.peek("key").is_none());
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[case::bulk_length(b"*1\r\n$5\r\nhello\r\n", "ERR Protocol error: invalid bulk length")]
+        #[case::multibulk_length(
+            b"*3\r\n$1\r\na\r\n$1\r\nb\r\n$1\r\nc\r\n",
+            "ERR Protocol error: invalid multibulk length"
+        )]
+        #[case::malformed_length(b"*a\r\n", "ERR Protocol error: Failed to parse array length.")]
+        #[case::malformed_boolean(b"#x\r\n", "ERR Protocol error: Invalid boolean: b\"x\".")]
+        #[tokio::test]
+        async fn test_handler_run_protocol_errors(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+            #[case] message: &[u8],
+            #[case] expected: &str,
+        ) -> Result<()> {
+            let (mut client_stream, handler) = stream_and_handler;
+            let mut handler = handler.with_protocol_limits(crate::resp::ProtocolLimits {
+                max_bulk_length: 4,
+                max_multibulk_length: 2,
+                ..Default::default()
+            });
+            client_stream.write_all(message).await?;
+            handler.run(databases, register).await;
+
+            let mut buffer = BytesMut::with_capacity(512);
+            while client_stream.read_buf(&mut buffer).await? > 0 {}
+            let expected = crate::resp::RespType::SimpleError(expected.into());
+            assert_eq!(expected.serialize(), buffer);
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_query_buffer_limit(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            let (mut client_stream, handler) = stream_and_handler;
+            let mut handler = handler.with_protocol_limits(crate::resp::ProtocolLimits {
+                max_query_buffer_length: 16,
+                ..Default::default()
+            });
+            client_stream
+                .write_all(b"*1\r\n$100\r\nan incomplete value")
+                .await?;
+            handler.run(databases, register).await;
+
+            let mut buffer = BytesMut::with_capacity(512);
+            while client_stream.read_buf(&mut buffer).await? > 0 {}
+            let expected = crate::resp::RespType::SimpleError(
+                "ERR Protocol error: query buffer limit reached".into(),
+            );
+            assert_eq!(expected.serialize(), buffer);
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_idle_timeout(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            tokio::time::pause();
+            let (mut client_stream, handler) = stream_and_handler;
+            let mut handler = handler.with_idle_timeout(tokio::time::Duration::from_secs(5));
+            let task = tokio::spawn(async move { handler.run(databases, register).await });
+
+            tokio::time::advance(tokio::time::Duration::from_secs(5)).await;
+            task.await?;
+
+            let mut buffer = BytesMut::with_capacity(512);
+            assert_eq!(0, client_stream.read_buf(&mut buffer).await?);
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_idle_timeout_active(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            tokio::time::pause();
+            register
+                .write()
+                .await
+                .register(Box::new(crate::commands::ping::Ping));
+            let (mut client_stream, handler) = stream_and_handler;
+            let mut handler = handler.with_idle_timeout(tokio::time::Duration::from_secs(5));
+            let task = tokio::spawn(async move { handler.run(databases, register).await });
+
+            let message = crate::resp::RespType::Array(vec![crate::resp::RespType::SimpleString(
+                "PING".into(),
+            )]);
+            let expected = crate::resp::RespType::SimpleString("PONG".into());
+            for _ in 0..3 {
+                tokio::time::advance(tokio::time::Duration::from_secs(4)).await;
+                client_stream.write_all(&message.serialize()).await?;
+                let mut buffer = BytesMut::with_capacity(512);
+                client_stream.read_buf(&mut buffer).await?;
+                assert_eq!(expected.serialize(), buffer);
+            }
+            assert!(!task.is_finished());
+
+            client_stream.shutdown().await?;
+            task.await?;
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_idle_timeout_subscribed(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            tokio::time::pause();
+            register
+                .write()
+                .await
+                .register(Box::new(crate::commands::subscribe::Subscribe));
+            let (mut client_stream, handler) = stream_and_handler;
+            let mut handler = handler.with_idle_timeout(tokio::time::Duration::from_secs(5));
+            let task = tokio::spawn(async move { handler.run(databases, register).await });
+
+            let message = crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some("SUBSCRIBE".into())),
+                crate::resp::RespType::BulkString(Some("channel".into())),
+            ]);
+            client_stream.write_all(&message.serialize()).await?;
+            let mut buffer = BytesMut::with_capacity(512);
+            client_stream.read_buf(&mut buffer).await?;
+
+            tokio::time::advance(tokio::time::Duration::from_secs(10)).await;
+            tokio::task::yield_now().await;
+            assert!(!task.is_finished());
+
+            client_stream.shutdown().await?;
+            task.await?;
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_shutdown(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            register
+                .write()
+                .await
+                .register(Box::new(crate::commands::ping::Ping));
+            let shutdown = tokio_util::sync::CancellationToken::new();
+            let (mut client_stream, handler) = stream_and_handler;
+            let mut handler = handler.with_shutdown(shutdown.clone());
+            let task = tokio::spawn(async move { handler.run(databases, register).await });
+
+            let message = crate::resp::RespType::Array(vec![crate::resp::RespType::SimpleString(
+                "PING".into(),
+            )]);
+            client_stream.write_all(&message.serialize()).await?;
+            let mut buffer = BytesMut::with_capacity(512);
+            client_stream.read_buf(&mut buffer).await?;
+            assert_eq!(
+                crate::resp::RespType::SimpleString("PONG".into()).serialize(),
+                buffer
+            );
+
+            shutdown.cancel();
+            task.await?;
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_registration(
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            register
+                .write()
+                .await
+                .register(Box::new(crate::commands::ping::Ping));
+            let clients = crate::clients::new();
+            let (mut client_stream, server_stream) = tokio::io::duplex(512);
+            let mut handler = RespHandler::new(server_stream, 7)
+                .with_registration(clients.register(7, "127.0.0.1:5000".into()));
+            let task = tokio::spawn(async move { handler.run(databases, register).await });
+
+            let message = crate::resp::RespType::Array(vec![crate::resp::RespType::SimpleString(
+                "PING".into(),
+            )]);
+            client_stream.write_all(&message.serialize()).await?;
+            let mut buffer = BytesMut::with_capacity(512);
+            client_stream.read_buf(&mut buffer).await?;
+            assert_eq!(
+                Some("ping".into()),
+                clients.get(7).and_then(|client| client.last_command)
+            );
+
+            client_stream.shutdown().await?;
+            task.await?;
+            assert_eq!(None, clients.get(7));
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_monitor(
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            register.write().await.register_multiple(vec![
+                Box::new(crate::commands::monitor::Monitor),
+                Box::new(crate::commands::ping::Ping),
+            ]);
+            let clients = crate::clients::new();
+            let mut streams = vec![];
+            for client_id in 0..2 {
+                let (client_stream, server_stream) = tokio::io::duplex(512);
+                let mut handler = RespHandler::new(server_stream, client_id).with_registration(
+                    clients.register(client_id, format!("127.0.0.1:500{client_id}")),
+                );
+                let (databases, register) = (databases.clone(), register.clone());
+                tokio::spawn(async move { handler.run(databases, register).await });
+                streams.push(client_stream);
+            }
+            let (monitoring, other) = streams.split_at_mut(1);
+            let (monitoring, other) = (&mut monitoring[0], &mut other[0]);
+
+            let message = crate::resp::RespType::Array(vec![crate::resp::RespType::BulkString(
+                Some("MONITOR".into()),
+            )]);
+            monitoring.write_all(&message.serialize()).await?;
+            let mut buffer = BytesMut::with_capacity(512);
+            monitoring.read_buf(&mut buffer).await?;
+            assert_eq!(b"+OK\r\n".as_slice(), buffer);
+
+            let message = crate::resp::RespType::Array(vec![
+                crate::resp::RespType::BulkString(Some("PING".into())),
+                crate::resp::RespType::BulkString(Some("hello".into())),
+            ]);
+            other.write_all(&message.serialize()).await?;
+            let mut buffer = BytesMut::with_capacity(512);
+            other.read_buf(&mut buffer).await?;
+
+            let mut buffer = BytesMut::with_capacity(512);
+            monitoring.read_buf(&mut buffer).await?;
+            let line = String::from_utf8(buffer.to_vec())?;
+            assert!(line.starts_with('+'), "{line}");
+            assert!(
+                line.ends_with(" [0 127.0.0.1:5001] \"PING\" \"hello\"\r\n"),
+                "{line}"
+            );
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn test_handler_run_hello_null_serialization(
+            stream_and_handler: (
+                tokio::io::DuplexStream,
+                RespHandler<tokio::io::DuplexStream>,
+            ),
+            databases: crate::store::SharedDatabases,
+            register: crate::commands::SharedRegister,
+        ) -> Result<()> {
+            register.write().await.register_multiple(vec![
+                Box::new(crate::commands::echo::Echo),
+                Box::new(crate::commands::hello::Hello::new(crate::acl::new())),
+            ]);
+            let (mut client_stream, mut handler) = stream_and_handler;
+            let task = tokio::spawn(async move { handler.run(databases, register).await });
+
+            let hello = |version: &str| {
+                crate::resp::RespType::Array(vec![
+                    crate::resp::RespType::BulkString(Some("HELLO".into())),
+                    crate::resp::RespType::BulkString(Some(version.to_string().into())),
+                ])
+            };
+            let echo = crate::resp::RespType::Array(vec![crate::resp::RespType::BulkString(Some(
+                "ECHO".into(),
+            ))]);
+
+            for (version, expected) in [("3", "_\r\n"), ("2", "$-1\r\n")] {
+                client_stream.write_all(&hello(version).serialize()).await?;
+                let mut buffer = BytesMut::with_capacity(512);
+                client_stream.read_buf(&mut buffer).await?;
+
+                client_stream.write_all(&echo.serialize()).await?;
+                let mut buffer = BytesMut::with_capacity(512);
+                client_stream.read_buf(&mut buffer).await?;
+                assert_eq!(expected.as_bytes(), buffer);
+            }
+
+            client_stream.shutdown().await?;
+            task.await?;
+
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,228 @@
+//! This module contains the latency monitor, recording the events that took too long.
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// The number of samples kept for each event, beyond which the oldest are dropped.
+const HISTORY_LENGTH: usize = 160;
+
+/// A latency spike of an event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// The unix time of the spike, in seconds.
+    pub time: i64,
+    pub latency_ms: u64,
+}
+
+/// The spikes of an event.
+#[derive(Debug, Default)]
+struct Event {
+    history: VecDeque<Sample>,
+    max_latency_ms: u64,
+}
+
+/// Records the events lasting at least the threshold, such as slow commands.
+#[derive(Debug, Default)]
+pub struct Latency {
+    /// The latency from which events are recorded, 0 to disable the monitor.
+    threshold_ms: AtomicU64,
+    /// The spikes of each event, by the event's name.
+    events: std::sync::Mutex<BTreeMap<String, Event>>,
+}
+
+impl Latency {
+    /// Sets the latency from which events are recorded, 0 to disable the monitor.
+    pub fn set_threshold(&self, threshold_ms: u64) {
+        self.threshold_ms.store(threshold_ms, Ordering::Relaxed);
+    }
+
+    /// Records the duration of an event if the monitor is enabled and the duration reaches the
+    /// threshold.
+    pub fn record(&self, event: &str, duration: std::time::Duration) {
+        let threshold_ms = self.threshold_ms.load(Ordering::Relaxed);
+        let latency_ms = duration.as_millis() as u64;
+        if threshold_ms == 0 || latency_ms < threshold_ms {
+            return;
+        }
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+        self.add_sample(event, Sample { time, latency_ms });
+    }
+
+    /// Adds a spike to the event's history.
+    /// Spikes within the same second are merged, keeping the highest latency.
+    fn add_sample(&self, event: &str, sample: Sample) {
+        let mut events = self.events.lock().unwrap();
+        let event = events.entry(event.into()).or_default();
+        event.max_latency_ms = event.max_latency_ms.max(sample.latency_ms);
+        match event.history.back_mut() {
+            Some(last) if last.time == sample.time => {
+                last.latency_ms = last.latency_ms.max(sample.latency_ms);
+            }
+            _ => {
+                if event.history.len() == HISTORY_LENGTH {
+                    event.history.pop_front();
+                }
+                event.history.push_back(sample);
+            }
+        }
+    }
+
+    /// Gets the latest spike and the highest latency of every event, sorted by the event's name.
+    pub fn latest(&self) -> Vec<(String, Sample, u64)> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(name, event)| {
+                let latest = event.history.back()?;
+                Some((name.clone(), *latest, event.max_latency_ms))
+            })
+            .collect()
+    }
+
+    /// Gets the spikes of the event, from the oldest.
+    pub fn history(&self, event: &str) -> Vec<Sample> {
+        self.events
+            .lock()
+            .unwrap()
+            .get(event)
+            .map(|event| event.history.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Clears the spikes of the events, or of every event if none are given.
+    /// Returns the number of events cleared.
+    pub fn reset(&self, events: &[String]) -> usize {
+        let mut recorded = self.events.lock().unwrap();
+        if events.is_empty() {
+            let count = recorded.len();
+            recorded.clear();
+            return count;
+        }
+        events
+            .iter()
+            .filter(|event| recorded.remove(event.as_str()).is_some())
+            .count()
+    }
+}
+
+pub type SharedLatency = Arc<Latency>;
+
+/// Creates a new latency monitor, disabled and without spikes.
+pub fn new() -> SharedLatency {
+    Arc::new(Latency::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn latency() -> SharedLatency {
+        let latency = new();
+        latency.set_threshold(100);
+        latency
+    }
+
+    fn sample(time: i64, latency_ms: u64) -> Sample {
+        Sample { time, latency_ms }
+    }
+
+    // --- Tests ---
+    #[rstest]
+    #[case::below_threshold(99, false)]
+    #[case::at_threshold(100, true)]
+    #[case::above_threshold(250, true)]
+    fn test_record(latency: SharedLatency, #[case] duration_ms: u64, #[case] expected: bool) {
+        latency.record("command", std::time::Duration::from_millis(duration_ms));
+        let history = latency.history("command");
+        assert_eq!(expected, !history.is_empty());
+        if expected {
+            assert_eq!(duration_ms, history[0].latency_ms);
+        }
+    }
+
+    #[rstest]
+    fn test_record_disabled() {
+        let latency = new();
+        latency.record("command", std::time::Duration::from_secs(10));
+        assert_eq!(Vec::<(String, Sample, u64)>::new(), latency.latest());
+    }
+
+    #[rstest]
+    fn test_add_sample(latency: SharedLatency) {
+        latency.add_sample("command", sample(1, 300));
+        latency.add_sample("command", sample(2, 200));
+        latency.add_sample("command", sample(2, 250));
+        latency.add_sample("command", sample(2, 150));
+        assert_eq!(
+            vec![sample(1, 300), sample(2, 250)],
+            latency.history("command")
+        );
+        assert_eq!(
+            vec![("command".to_string(), sample(2, 250), 300)],
+            latency.latest()
+        );
+    }
+
+    #[rstest]
+    fn test_add_sample_history_length(latency: SharedLatency) {
+        for time in 0..HISTORY_LENGTH as i64 + 10 {
+            latency.add_sample("command", sample(time, 100));
+        }
+        let history = latency.history("command");
+        assert_eq!(HISTORY_LENGTH, history.len());
+        assert_eq!(10, history[0].time);
+    }
+
+    #[rstest]
+    fn test_latest(latency: SharedLatency) {
+        latency.add_sample("expire-cycle", sample(2, 100));
+        latency.add_sample("command", sample(1, 200));
+        assert_eq!(
+            vec![
+                ("command".to_string(), sample(1, 200), 200),
+                ("expire-cycle".to_string(), sample(2, 100), 100),
+            ],
+            latency.latest()
+        );
+    }
+
+    #[rstest]
+    fn test_history_unknown_event(latency: SharedLatency) {
+        assert_eq!(Vec::<Sample>::new(), latency.history("unknown"));
+    }
+
+    #[rstest]
+    #[case::all(&[], 2, &[])]
+    #[case::one(&["command"], 1, &["expire-cycle"])]
+    #[case::unknown(&["command", "unknown"], 1, &["expire-cycle"])]
+    fn test_reset(
+        latency: SharedLatency,
+        #[case] events: &[&str],
+        #[case] expected: usize,
+        #[case] remaining: &[&str],
+    ) {
+        latency.add_sample("command", sample(1, 200));
+        latency.add_sample("expire-cycle", sample(1, 200));
+
+        let events = events
+            .iter()
+            .map(|event| event.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(expected, latency.reset(&events));
+        assert_eq!(
+            remaining,
+            latency
+                .latest()
+                .iter()
+                .map(|(name, _, _)| name.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,23 +1,149 @@
+mod acl;
+mod clients;
+mod codec;
 mod commands;
+mod config;
 mod handler;
+mod latency;
+mod monitor;
+mod pattern;
+mod pause;
 mod resp;
 mod state;
+mod stats;
 mod store;
+mod throttle;
+mod tls;
 
 use std::sync::Arc;
 use tokio::{
-    net::{TcpListener, TcpStream},
-    sync::RwLock,
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt},
+    net::TcpListener,
+    sync::{OwnedSemaphorePermit, RwLock, Semaphore},
 };
+use tokio_util::sync::CancellationToken;
 
-async fn handle_stream(
-    stream: TcpStream,
-    store: store::SharedStore,
+/// Handles the stream, holding the connection permit until the client disconnects or the server
+/// shuts down.
+async fn handle_stream<T>(
+    stream: T,
+    databases: store::SharedDatabases,
     register: commands::SharedRegister,
-    client_id: usize,
-) {
-    let mut handler = handler::RespHandler::new(stream, client_id);
-    handler.run(store, register).await;
+    config: config::SharedConfig,
+    registration: clients::Registration,
+    shutdown: CancellationToken,
+    _permit: OwnedSemaphorePermit,
+) where
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (timeout, protocol_limits) = {
+        let config = config.read().await;
+        let protocol_limits = resp::ProtocolLimits {
+            max_bulk_length: config.proto_max_bulk_len,
+            max_query_buffer_length: config.client_query_buffer_limit,
+            ..Default::default()
+        };
+        (config.timeout, protocol_limits)
+    };
+    let authentication_required = register.read().await.acl().requires_authentication();
+    let mut handler = handler::RespHandler::new(stream, registration.id())
+        .with_protocol_limits(protocol_limits)
+        .with_authentication_required(authentication_required)
+        .with_shutdown(shutdown)
+        .with_registration(registration);
+    if timeout > 0 {
+        handler = handler.with_idle_timeout(tokio::time::Duration::from_secs(timeout));
+    }
+    handler.run(databases, register).await;
+}
+
+/// The error for a client refused for exceeding the connection limit.
+const MAX_CLIENTS_ERROR: &[u8] = b"-ERR max number of clients reached\r\n";
+
+/// The error for a client refused for connecting too often from its address.
+const CONNECTION_RATE_ERROR: &[u8] =
+    b"-ERR too many connections from your address, try again later\r\n";
+
+/// The error for a client refused by protected mode.
+const PROTECTED_MODE_ERROR: &[u8] = b"-DENIED Redis is running in protected mode because protected mode is enabled and no password is set for the default user. In this mode connections are only accepted from the loopback interface. If you want to connect from external computers to Redis you may adopt one of the following solutions: 1) Just disable protected mode sending the command 'CONFIG SET protected-mode no' from the loopback interface by connecting to Redis from the same host the server is running, however MAKE SURE Redis is not publicly accessible from internet if you do so. Use CONFIG REWRITE to make this change permanent. 2) Alternatively you can just disable the protected mode by editing the Redis configuration file, and setting the protected mode option to 'no', and then restarting the server. 3) If you started the server manually just for testing, restart it with the '--protected-mode no' option. 4) Set up an authentication password for the default user. NOTE: You only need to do one of the above things in order for the server to start accepting connections from the outside.\r\n";
+
+/// How long a client has to complete the TLS handshake before it is dropped, so that stalled
+/// handshakes do not hold on to a connection slot.
+const TLS_HANDSHAKE_TIMEOUT: tokio::time::Duration = tokio::time::Duration::from_secs(10);
+
+/// Tells the client why it was refused, then closes the stream.
+async fn refuse_stream<T>(mut stream: T, error: &'static [u8])
+where
+    T: AsyncWrite + Unpin,
+{
+    let _ = stream.write_all(error).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Whether protected mode refuses a client: protected mode is enabled, the server listens on every
+/// interface, the default user needs no password and the client is not on the loopback interface.
+fn is_protected(config: &config::Config, acl: &acl::Acl, peer: std::net::SocketAddr) -> bool {
+    let binds_every_interface = config
+        .bind
+        .parse::<std::net::IpAddr>()
+        .is_ok_and(|address| address.is_unspecified());
+    config.protected_mode
+        && binds_every_interface
+        && !acl.requires_authentication()
+        && !peer.ip().to_canonical().is_loopback()
+}
+
+/// Waits for the process to be asked to stop, by SIGINT or SIGTERM.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM.")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate => {}
+    }
+}
+
+/// Binds a listener with the backlog as the length of its queue of pending connections.
+fn bind_listener(address: std::net::SocketAddr, backlog: u32) -> std::io::Result<TcpListener> {
+    let socket = match address {
+        std::net::SocketAddr::V4(_) => tokio::net::TcpSocket::new_v4()?,
+        std::net::SocketAddr::V6(_) => tokio::net::TcpSocket::new_v6()?,
+    };
+    socket.set_reuseaddr(true)?;
+    socket.bind(address)?;
+    socket.listen(backlog)
+}
+
+/// Accepts a connection on the listener, or waits forever without a listener.
+async fn accept(
+    listener: Option<&TcpListener>,
+) -> std::io::Result<(tokio::net::TcpStream, std::net::SocketAddr)> {
+    match listener {
+        Some(listener) => listener.accept().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Applies the socket options of the configuration to an accepted stream.
+fn configure_stream(
+    stream: &tokio::net::TcpStream,
+    config: &config::Config,
+) -> std::io::Result<()> {
+    stream.set_nodelay(config.tcp_nodelay)?;
+    if config.tcp_keepalive > 0 {
+        let keepalive = socket2::TcpKeepalive::new()
+            .with_time(std::time::Duration::from_secs(config.tcp_keepalive));
+        socket2::SockRef::from(stream).set_tcp_keepalive(&keepalive)?;
+    }
+    Ok(())
 }
 
 #[tokio::main]
@@ -25,37 +151,337 @@ async fn main() {
     // You can use print statements as follows for debugging, they'll be visible when running tests.
     println!("Logs from your program will appear here!");
 
-    let listener = TcpListener::bind("127.0.0.1:6379").await.unwrap();
-    let store = store::new();
+    let config = config::Config::from_args(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+    let port = config.port;
+    let bind = config.bind.parse().unwrap();
+    // Port 0 leaves only the TLS listener.
+    let listener = (port > 0)
+        .then(|| bind_listener(std::net::SocketAddr::new(bind, port), config.tcp_backlog).unwrap());
+    let tls_listener = (config.tls_port > 0).then(|| {
+        let acceptor = tls::new_acceptor(&config.tls_cert_file, &config.tls_key_file)
+            .unwrap_or_else(|err| {
+                eprintln!("{err:#}");
+                std::process::exit(1);
+            });
+        let address = std::net::SocketAddr::new(bind, config.tls_port);
+        (
+            bind_listener(address, config.tcp_backlog).unwrap(),
+            acceptor,
+        )
+    });
+    let config = config::share(config);
+    let (databases, connection_limit, maxclients) = {
+        let config = config.read().await;
+        let databases = store::new_databases(config.databases);
+        // The databases share their budget, so setting it through one sets it for all.
+        databases[0]
+            .read()
+            .await
+            .set_eviction(config.maxmemory, config.maxmemory_policy);
+        (
+            databases,
+            Arc::new(Semaphore::new(config.maxclients)),
+            config.maxclients,
+        )
+    };
+    let register = commands::Register::new()
+        .with_config(config.clone())
+        .with_databases(databases.clone());
+    let stats = register.stats();
+    let latency = register.latency();
+    let acl = register.acl();
+    acl.set_default_password(&config.read().await.requirepass);
+    latency.set_threshold(config.read().await.latency_monitor_threshold);
 
+    let active_expire = store::new_active_expire();
+    store::spawn_active_expire(databases.clone(), active_expire.clone(), latency.clone());
+
+    let clients = clients::new();
+    // The register is shared before being filled so COMMAND can describe every command.
+    let register = Arc::new(RwLock::new(register));
+    // Cancelled by a signal or by SHUTDOWN, closing the listener and every client.
+    let shutdown = CancellationToken::new();
     let commands: Vec<Box<dyn commands::Command>> = vec![
+        Box::new(commands::acl::Acl::new(register.clone())),
+        Box::new(commands::auth::Auth::new(acl.clone())),
+        Box::new(commands::command::Command::new(register.clone())),
+        Box::new(commands::copy::Copy::new(databases.clone())),
+        Box::new(commands::debug::Debug::new(
+            active_expire.clone(),
+            config.clone(),
+        )),
+        Box::new(commands::del::Del),
         Box::new(commands::echo::Echo),
+        Box::new(commands::expiretime::Expiretime),
+        Box::new(commands::flushall::Flushall::new(databases.clone())),
         Box::new(commands::get::Get),
+        Box::new(commands::getex::Getex),
+        Box::new(commands::hdel::Hdel),
+        Box::new(commands::hgetall::Hgetall),
+        Box::new(commands::hlen::Hlen),
+        Box::new(commands::hset::Hset),
+        Box::new(commands::latency::Latency::new(latency.clone())),
+        Box::new(commands::incrbyfloat::Incrbyfloat),
+        Box::new(commands::keys::Keys),
+        Box::new(commands::lindex::Lindex),
+        Box::new(commands::linsert::Linsert),
+        Box::new(commands::lmpop::Lmpop),
+        Box::new(commands::lolwut::Lolwut),
+        Box::new(commands::lpos::Lpos),
+        Box::new(commands::lrem::Lrem),
+        Box::new(commands::lset::Lset),
+        Box::new(commands::memory::Memory::new(databases.clone())),
+        Box::new(commands::monitor::Monitor),
+        Box::new(commands::r#move::Move::new(databases.clone())),
+        Box::new(commands::expiretime::Pexpiretime),
         Box::new(commands::ping::Ping),
+        Box::new(commands::quit::Quit),
+        Box::new(commands::randomkey::Randomkey),
+        Box::new(commands::rename::Rename),
+        Box::new(commands::rename::Renamenx),
         Box::new(commands::rpush::Rpush),
+        Box::new(commands::sadd::Sadd),
+        Box::new(commands::scan::Scan),
+        Box::new(commands::select::Select::new(databases.len())),
+        Box::new(commands::scard::Scard),
         Box::new(commands::set::Set),
-        Box::new(commands::hello::Hello),
+        Box::new(commands::shutdown::Shutdown::new(shutdown.clone())),
+        Box::new(commands::sinter::Sdiff),
+        Box::new(commands::sinter::Sinter),
+        Box::new(commands::sinter::Sunion),
+        Box::new(commands::sintercard::Sintercard),
+        Box::new(commands::sismember::Sismember),
+        Box::new(commands::srem::Srem),
+        Box::new(commands::subscribe::Subscribe),
+        Box::new(commands::swapdb::Swapdb::new(databases.clone())),
+        Box::new(commands::touch::Touch),
+        Box::new(commands::unlink::Unlink),
+        Box::new(commands::subscribe::Unsubscribe),
+        Box::new(commands::wait::Wait),
+        Box::new(commands::hello::Hello::new(acl.clone())),
+        Box::new(commands::info::Info::new(
+            port,
+            stats.clone(),
+            databases.clone(),
+        )),
+        Box::new(commands::object::Object::new(config.clone())),
+        Box::new(commands::config::Config::new(
+            config.clone(),
+            databases.clone(),
+            latency.clone(),
+            stats.clone(),
+            acl.clone(),
+        )),
+        Box::new(commands::client::Client::new(
+            clients.clone(),
+            register.read().await.pause(),
+        )),
     ];
 
-    let mut register = commands::Register::new();
-    register.register_multiple(commands);
-    let register = Arc::new(RwLock::new(register));
-    let mut client_counter = 0;
+    {
+        let mut register = register.write().await;
+        register.register_multiple(commands);
+        for (name, new_name) in &config.read().await.renamed_commands {
+            register.rename(name, new_name).unwrap_or_else(|err| {
+                eprintln!("{err}");
+                std::process::exit(1);
+            });
+        }
+    }
 
+    let mut throttle = throttle::Throttle::new();
+    let signal = shutdown_signal();
+    tokio::pin!(signal);
     loop {
-        match listener.accept().await {
-            Ok((stream, _)) => {
+        let (accepted, acceptor) = tokio::select! {
+            accepted = accept(listener.as_ref()) => (accepted, None),
+            accepted = accept(tls_listener.as_ref().map(|(listener, _)| listener)) => {
+                (accepted, tls_listener.as_ref().map(|(_, acceptor)| acceptor.clone()))
+            }
+            _ = &mut signal => break,
+            _ = shutdown.cancelled() => break,
+        };
+        match accepted {
+            Ok((stream, addr)) => {
+                let connection_rate_limit = config.read().await.connection_rate_limit;
+                if !throttle.allow(addr.ip(), connection_rate_limit, std::time::Instant::now()) {
+                    log::warn!("Refusing connection from {addr}, too many connections.");
+                    tokio::spawn(refuse_stream(stream, CONNECTION_RATE_ERROR));
+                    continue;
+                }
+                if is_protected(&*config.read().await, &acl, addr) {
+                    log::warn!("Refusing connection from {addr}, protected mode is enabled.");
+                    tokio::spawn(refuse_stream(stream, PROTECTED_MODE_ERROR));
+                    continue;
+                }
+                if let Err(err) = configure_stream(&stream, &*config.read().await) {
+                    log::warn!("Failed to set the socket options: {err}");
+                }
+                let Ok(permit) = connection_limit.clone().try_acquire_owned() else {
+                    log::warn!("Refusing connection, max number of clients reached.");
+                    tokio::spawn(refuse_stream(stream, MAX_CLIENTS_ERROR));
+                    continue;
+                };
                 println!("accepted new connection");
-                let store = store.clone();
+                stats.record_connection();
+                let databases = databases.clone();
                 let register = register.clone();
+                let config = config.clone();
+                let registration = clients.register(state::next_client_id(), addr.to_string());
+                let shutdown = shutdown.clone();
                 tokio::spawn(async move {
-                    handle_stream(stream, store, register, client_counter).await;
+                    let Some(acceptor) = acceptor else {
+                        handle_stream(
+                            stream,
+                            databases,
+                            register,
+                            config,
+                            registration,
+                            shutdown,
+                            permit,
+                        )
+                        .await;
+                        return;
+                    };
+                    match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await
+                    {
+                        Ok(Ok(stream)) => {
+                            handle_stream(
+                                stream,
+                                databases,
+                                register,
+                                config,
+                                registration,
+                                shutdown,
+                                permit,
+                            )
+                            .await
+                        }
+                        Ok(Err(err)) => log::warn!("TLS handshake with {addr} failed: {err}"),
+                        Err(_) => log::warn!("TLS handshake with {addr} timed out."),
+                    }
                 });
-                client_counter += 1;
             }
             Err(e) => {
                 println!("error: {}", e);
             }
         }
     }
+
+    // Every connection holds a permit, so all of them are back once every client is closed.
+    println!("Shutting down, waiting for the clients to close.");
+    drop(listener);
+    drop(tls_listener);
+    shutdown.cancel();
+    let _ = connection_limit.acquire_many(maxclients as u32).await;
+    println!("Shut down.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+    use tokio::io::AsyncReadExt;
+
+    // --- Fixtures ---
+    #[fixture]
+    fn register() -> commands::SharedRegister {
+        Arc::new(RwLock::new(commands::Register::new()))
+    }
+
+    // --- Tests ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_handle_stream_connection_limit(register: commands::SharedRegister) {
+        let databases = store::new_databases(1);
+        let config = config::new();
+        let connection_limit = Arc::new(Semaphore::new(2));
+        let registry = clients::new();
+
+        let mut clients = vec![];
+        let mut handlers = vec![];
+        for client_id in 0..2 {
+            let permit = connection_limit.clone().try_acquire_owned().unwrap();
+            let (client, server) = tokio::io::duplex(64);
+            clients.push(client);
+            handlers.push(tokio::spawn(handle_stream(
+                server,
+                databases.clone(),
+                register.clone(),
+                config.clone(),
+                registry.register(client_id, format!("127.0.0.1:{client_id}")),
+                CancellationToken::new(),
+                permit,
+            )));
+        }
+        assert!(connection_limit.clone().try_acquire_owned().is_err());
+
+        let mut client = clients.pop().unwrap();
+        client.shutdown().await.unwrap();
+        drop(client);
+        handlers.pop().unwrap().await.unwrap();
+        assert_eq!(1, registry.list().len());
+        assert_eq!(1, connection_limit.available_permits());
+        assert!(connection_limit.clone().try_acquire_owned().is_ok());
+    }
+
+    #[rstest]
+    #[case::enabled(true, 300)]
+    #[case::disabled(false, 0)]
+    #[tokio::test]
+    async fn test_configure_stream(#[case] tcp_nodelay: bool, #[case] tcp_keepalive: u64) {
+        let listener = bind_listener(std::net::SocketAddr::from(([127, 0, 0, 1], 0)), 16).unwrap();
+        let client = tokio::net::TcpStream::connect(listener.local_addr().unwrap());
+        let (client, accepted) = tokio::join!(client, listener.accept());
+        let (_client, (stream, _)) = (client.unwrap(), accepted.unwrap());
+
+        let mut config = config::Config::new();
+        config.tcp_nodelay = tcp_nodelay;
+        config.tcp_keepalive = tcp_keepalive;
+        configure_stream(&stream, &config).unwrap();
+
+        assert_eq!(tcp_nodelay, stream.nodelay().unwrap());
+        assert_eq!(
+            tcp_keepalive > 0,
+            socket2::SockRef::from(&stream).keepalive().unwrap()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_refuse_stream() {
+        let (mut client, server) = tokio::io::duplex(64);
+        refuse_stream(server, MAX_CLIENTS_ERROR).await;
+
+        let mut buffer = vec![];
+        client.read_to_end(&mut buffer).await.unwrap();
+        assert_eq!(b"-ERR max number of clients reached\r\n".as_slice(), buffer);
+    }
+
+    #[rstest]
+    #[case::loopback("0.0.0.0", true, "", "127.0.0.1:5000", false)]
+    #[case::ipv6_loopback("::", true, "", "[::1]:5000", false)]
+    #[case::mapped_loopback("::", true, "", "[::ffff:127.0.0.1]:5000", false)]
+    #[case::remote("0.0.0.0", true, "", "10.0.0.2:5000", true)]
+    #[case::remote_ipv6("::", true, "", "[2001:db8::2]:5000", true)]
+    #[case::bind_address("10.0.0.1", true, "", "10.0.0.2:5000", false)]
+    #[case::disabled("0.0.0.0", false, "", "10.0.0.2:5000", false)]
+    #[case::password("0.0.0.0", true, "secret", "10.0.0.2:5000", false)]
+    fn test_is_protected(
+        #[case] bind: &str,
+        #[case] protected_mode: bool,
+        #[case] requirepass: &str,
+        #[case] peer: &str,
+        #[case] expected: bool,
+    ) {
+        let mut config = config::Config::new();
+        config.bind = bind.into();
+        config.protected_mode = protected_mode;
+        let acl = acl::new();
+        acl.set_default_password(requirepass);
+        assert_eq!(expected, is_protected(&config, &acl, peer.parse().unwrap()));
+    }
 }
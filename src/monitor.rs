@@ -0,0 +1,178 @@
+//! This module contains the feed of the commands run, streamed to connections running MONITOR.
+use std::sync::Arc;
+
+/// The number of lines buffered for each monitoring connection, beyond which the oldest lines
+/// are dropped.
+const CAPACITY: usize = 1024;
+
+/// Broadcasts a line for each command run to the monitoring connections.
+#[derive(Debug)]
+pub struct Monitor {
+    sender: tokio::sync::broadcast::Sender<String>,
+}
+
+impl Monitor {
+    /// Starts receiving the lines of the commands run from now on.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<String> {
+        self.sender.subscribe()
+    }
+
+    /// Feeds a command run by the connection to the monitoring connections.
+    /// The line is only built while a connection is monitoring.
+    pub fn feed(&self, state: &crate::state::State, command: &str, args: &[crate::resp::RespType]) {
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        let line = format_line(std::time::SystemTime::now(), state, command, args);
+        // The monitoring connections may have closed since they were counted.
+        let _ = self.sender.send(line);
+    }
+}
+
+/// Formats a command as `<unix time> [<db> <addr>] "<command>" "<arg>" ...`.
+fn format_line(
+    time: std::time::SystemTime,
+    state: &crate::state::State,
+    command: &str,
+    args: &[crate::resp::RespType],
+) -> String {
+    let time = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let mut line = format!(
+        "{}.{:06} [{} {}] {}",
+        time.as_secs(),
+        time.subsec_micros(),
+        state.db,
+        state.addr.as_deref().unwrap_or("unknown"),
+        quote(command.as_bytes())
+    );
+    for arg in args {
+        line.push(' ');
+        match crate::resp::extract_bytes(arg) {
+            Ok(bytes) => line.push_str(&quote(&bytes)),
+            Err(_) => line.push_str(&quote(format!("{arg:?}").as_bytes())),
+        }
+    }
+    line
+}
+
+/// Quotes the bytes, escaping quotes, backslashes and bytes that are not printable ASCII.
+fn quote(bytes: &[u8]) -> String {
+    let mut quoted = String::from('"');
+    for byte in bytes {
+        match byte {
+            b'"' => quoted.push_str("\\\""),
+            b'\\' => quoted.push_str("\\\\"),
+            b'\n' => quoted.push_str("\\n"),
+            b'\r' => quoted.push_str("\\r"),
+            b'\t' => quoted.push_str("\\t"),
+            b' '..=b'~' => quoted.push(*byte as char),
+            _ => quoted.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+pub type SharedMonitor = Arc<Monitor>;
+
+/// Creates a new feed without monitoring connections.
+pub fn new() -> SharedMonitor {
+    Arc::new(Monitor {
+        sender: tokio::sync::broadcast::Sender::new(CAPACITY),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn monitor() -> SharedMonitor {
+        new()
+    }
+
+    #[fixture]
+    fn state() -> crate::state::State {
+        let mut state = crate::state::State::new(0);
+        state.db = 2;
+        state.addr = Some("127.0.0.1:5000".into());
+        state
+    }
+
+    fn make_args(args: &[&str]) -> Vec<crate::resp::RespType> {
+        args.iter()
+            .map(|arg| crate::resp::RespType::BulkString(Some(arg.to_string().into())))
+            .collect()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_feed(monitor: SharedMonitor, state: crate::state::State) {
+        let mut first = monitor.subscribe();
+        let mut second = monitor.subscribe();
+        monitor.feed(&state, "SET", &make_args(&["key", "value"]));
+
+        for receiver in [&mut first, &mut second] {
+            let line = receiver.try_recv().unwrap();
+            assert!(
+                line.ends_with(r#" [2 127.0.0.1:5000] "SET" "key" "value""#),
+                "{line}"
+            );
+        }
+    }
+
+    #[rstest]
+    fn test_feed_without_monitors(monitor: SharedMonitor, state: crate::state::State) {
+        monitor.feed(&state, "GET", &make_args(&["key"]));
+        assert!(monitor.subscribe().try_recv().is_err());
+    }
+
+    #[rstest]
+    #[case::no_args("PING", &[], r#"1700000000.000042 [2 127.0.0.1:5000] "PING""#)]
+    #[case::args(
+        "get",
+        &["key"],
+        r#"1700000000.000042 [2 127.0.0.1:5000] "get" "key""#
+    )]
+    #[case::escaped(
+        "SET",
+        &["a \"key\"", "line\r\n"],
+        r#"1700000000.000042 [2 127.0.0.1:5000] "SET" "a \"key\"" "line\r\n""#
+    )]
+    fn test_format_line(
+        state: crate::state::State,
+        #[case] command: &str,
+        #[case] args: &[&str],
+        #[case] expected: &str,
+    ) {
+        let time = std::time::UNIX_EPOCH + std::time::Duration::from_micros(1_700_000_000_000_042);
+        assert_eq!(
+            expected,
+            format_line(time, &state, command, &make_args(args))
+        );
+    }
+
+    #[rstest]
+    fn test_format_line_unknown_addr() {
+        let line = format_line(
+            std::time::UNIX_EPOCH,
+            &crate::state::State::new(0),
+            "PING",
+            &[],
+        );
+        assert_eq!(r#"0.000000 [0 unknown] "PING""#, line);
+    }
+
+    #[rstest]
+    #[case::printable(b"value", r#""value""#)]
+    #[case::backslash(br"a\b", r#""a\\b""#)]
+    #[case::tab(b"a\tb", r#""a\tb""#)]
+    #[case::binary(b"\x00\xff", r#""\x00\xff""#)]
+    fn test_quote(#[case] bytes: &[u8], #[case] expected: &str) {
+        assert_eq!(expected, quote(bytes));
+    }
+}
@@ -0,0 +1,228 @@
+//! This module contains the glob pattern matching.
+
+/// A token of a glob pattern.
+#[derive(Debug, PartialEq)]
+enum Token {
+    /// `*`, matching any sequence.
+    Any,
+    /// `?`, matching any single character.
+    One,
+    /// `[...]`, matching a single character within the ranges, or outside them if negated.
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+    /// Any other character, matched as is.
+    Literal(char),
+}
+
+impl Token {
+    /// Checks if the token matches the single character, ignoring the case of ASCII letters if
+    /// `nocase`.
+    fn matches(&self, c: char, nocase: bool) -> bool {
+        let fold = |c: char| if nocase { c.to_ascii_lowercase() } else { c };
+        match self {
+            Token::Any | Token::One => true,
+            Token::Class { negated, ranges } => {
+                negated
+                    ^ ranges.iter().any(|(start, end)| {
+                        let (start, end) = (fold(*start), fold(*end));
+                        (start.min(end)..=start.max(end)).contains(&fold(c))
+                    })
+            }
+            Token::Literal(literal) => fold(*literal) == fold(c),
+        }
+    }
+}
+
+/// Parses the glob pattern into its tokens.
+///
+/// A class left open at the end of the pattern is closed there, a reversed range such as `z-a`
+/// is the same as `a-z`, and a trailing backslash is matched as is.
+fn parse(pattern: &str) -> Vec<Token> {
+    let mut chars = pattern.chars().peekable();
+    let mut tokens = vec![];
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '*' => Token::Any,
+            '?' => Token::One,
+            '\\' => Token::Literal(chars.next().unwrap_or('\\')),
+            '[' => {
+                let negated = chars.next_if_eq(&'^').is_some();
+                let mut ranges = vec![];
+                while let Some(c) = chars.next() {
+                    let start = match c {
+                        ']' => break,
+                        '\\' => chars.next().unwrap_or('\\'),
+                        c => c,
+                    };
+                    let mut lookahead = chars.clone();
+                    let range = match (lookahead.next(), lookahead.next()) {
+                        (Some('-'), Some(end)) => {
+                            chars.nth(1);
+                            (start, end)
+                        }
+                        _ => (start, start),
+                    };
+                    ranges.push(range);
+                }
+                Token::Class { negated, ranges }
+            }
+            c => Token::Literal(c),
+        };
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+/// A glob pattern, following the semantics of Redis's pattern matching.
+///
+/// Supports `*` to match any sequence, `?` to match any single character, `[...]` to match a
+/// single character of a class, such as `[abc]`, `[a-z]` or `[^a]`, and `\` to escape the
+/// following character.
+#[derive(Debug, PartialEq)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+    /// Whether the case of ASCII letters is ignored.
+    nocase: bool,
+}
+
+impl Pattern {
+    /// Parses the glob pattern, which is case sensitive.
+    pub fn new<T: AsRef<str>>(pattern: T) -> Self {
+        Self {
+            tokens: parse(pattern.as_ref()),
+            nocase: false,
+        }
+    }
+
+    /// Ignores the case of ASCII letters when matching.
+    pub fn nocase(mut self) -> Self {
+        self.nocase = true;
+        self
+    }
+
+    /// Checks if the value matches the pattern.
+    pub fn matches(&self, value: &str) -> bool {
+        let value = value.chars().collect::<Vec<_>>();
+
+        let (mut p, mut v) = (0, 0);
+        let mut backtrack = None;
+        while v < value.len() {
+            match self.tokens.get(p) {
+                Some(Token::Any) => {
+                    backtrack = Some((p, v));
+                    p += 1;
+                }
+                Some(token) if token.matches(value[v], self.nocase) => {
+                    p += 1;
+                    v += 1;
+                }
+                _ => match backtrack {
+                    Some((star, matched)) => {
+                        p = star + 1;
+                        v = matched + 1;
+                        backtrack = Some((star, matched + 1));
+                    }
+                    None => return false,
+                },
+            }
+        }
+
+        self.tokens[p..].iter().all(|token| *token == Token::Any)
+    }
+}
+
+/// Checks if the value matches the glob pattern, which is case sensitive.
+pub fn glob_match(pattern: &str, value: &str) -> bool {
+    Pattern::new(pattern).matches(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // --- Tests ---
+    #[rstest]
+    #[case::exact("save", "save", true)]
+    #[case::exact_mismatch("save", "maxmemory", false)]
+    #[case::star_all("*", "maxmemory", true)]
+    #[case::star_empty("*", "", true)]
+    #[case::star_suffix("maxmemory*", "maxmemory", true)]
+    #[case::star_suffix_longer("maxmemory*", "maxmemory-policy", true)]
+    #[case::star_prefix("*policy", "maxmemory-policy", true)]
+    #[case::star_middle("max*policy", "maxmemory-policy", true)]
+    #[case::star_middle_mismatch("max*save", "maxmemory-policy", false)]
+    #[case::star_backtrack("*a*e", "maxmemory-policy save", true)]
+    #[case::question("sav?", "save", true)]
+    #[case::question_too_short("sav?", "sav", false)]
+    #[case::empty_pattern("", "save", false)]
+    #[case::class("h[ae]llo", "hello", true)]
+    #[case::class_mismatch("h[ae]llo", "hillo", false)]
+    #[case::class_range("h[a-e]llo", "hcllo", true)]
+    #[case::class_range_reversed("h[e-a]llo", "hcllo", true)]
+    #[case::class_range_mismatch("h[a-e]llo", "hzllo", false)]
+    #[case::class_negated("h[^e]llo", "hallo", true)]
+    #[case::class_negated_mismatch("h[^e]llo", "hello", false)]
+    #[case::class_escaped("[\\]]", "]", true)]
+    #[case::class_dash("[-a]", "-", true)]
+    #[case::class_empty("a[]b", "ab", false)]
+    #[case::class_unterminated("h[ae", "ha", true)]
+    #[case::class_with_star("*[0-9]", "key9", true)]
+    #[case::escaped_star("a\\*", "a*", true)]
+    #[case::escaped_star_mismatch("a\\*", "ab", false)]
+    #[case::escaped_question("a\\?", "a?", true)]
+    #[case::trailing_backslash("a\\", "a\\", true)]
+    fn test_glob_match(#[case] pattern: &str, #[case] value: &str, #[case] expected: bool) {
+        assert_eq!(expected, glob_match(pattern, value));
+    }
+
+    #[rstest]
+    #[case::literal("HELLO", "hello", true)]
+    #[case::literal_mismatch("HELLO", "help", false)]
+    #[case::class("h[AE]llo", "Hello", true)]
+    #[case::range("h[A-E]llo", "hcllo", true)]
+    #[case::range_lowercase("h[a-e]llo", "HCLLO", true)]
+    #[case::range_mixed_case("h[a-E]llo", "hcllo", true)]
+    #[case::negated_class("h[^E]llo", "hello", false)]
+    #[case::escaped("A\\*", "a*", true)]
+    #[case::non_ascii("É", "é", false)]
+    fn test_pattern_nocase(#[case] pattern: &str, #[case] value: &str, #[case] expected: bool) {
+        assert_eq!(expected, Pattern::new(pattern).nocase().matches(value));
+    }
+
+    #[rstest]
+    #[case::literal("HELLO", "hello")]
+    #[case::class("h[AE]llo", "hello")]
+    #[case::range("h[A-E]llo", "hcllo")]
+    fn test_pattern_case_sensitive(#[case] pattern: &str, #[case] value: &str) {
+        assert!(!Pattern::new(pattern).matches(value));
+    }
+
+    #[rstest]
+    fn test_pattern_reused() {
+        let pattern = Pattern::new("key:[0-9]*");
+        assert!(pattern.matches("key:1"));
+        assert!(pattern.matches("key:12"));
+        assert!(!pattern.matches("key:a"));
+        assert!(!pattern.matches("key:"));
+    }
+
+    #[rstest]
+    #[case::stars("a*a*a*a*a*a*a*a*a*a*b", 10_000)]
+    #[case::questions("*?*?*?*?*?*?*?*?*?*?b", 10_000)]
+    fn test_pattern_no_catastrophic_backtracking(#[case] pattern: &str, #[case] length: usize) {
+        let value = "a".repeat(length);
+        let start = std::time::Instant::now();
+        assert!(!Pattern::new(pattern).matches(&value));
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[rstest]
+    fn test_pattern_unicode() {
+        assert!(Pattern::new("caf?").matches("café"));
+        assert!(Pattern::new("[à-ü]").matches("é"));
+    }
+}
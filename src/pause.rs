@@ -0,0 +1,165 @@
+//! This module contains the server-wide pause of client commands.
+use std::sync::Arc;
+
+/// The commands a pause suspends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PauseMode {
+    All,
+    Write,
+}
+
+/// Suspends client commands until a deadline, or until it is lifted early.
+#[derive(Debug, Default)]
+pub struct Pause {
+    /// The mode of the pause and the instant it ends, if commands are paused.
+    paused: std::sync::Mutex<Option<(PauseMode, tokio::time::Instant)>>,
+    /// Wakes the waiting commands when the pause is lifted early.
+    unpaused: tokio::sync::Notify,
+}
+
+impl Pause {
+    /// Pauses the commands of the mode for the duration.
+    ///
+    /// An ongoing pause is only extended: it ends at the later of the two deadlines and pauses
+    /// every command if either pause does.
+    pub fn pause(&self, mode: PauseMode, duration: tokio::time::Duration) {
+        let mut end = tokio::time::Instant::now() + duration;
+        let mut mode = mode;
+        let mut paused = self.paused.lock().unwrap();
+        if let Some((current_mode, current_end)) = *paused {
+            if current_end > tokio::time::Instant::now() {
+                end = end.max(current_end);
+                if current_mode == PauseMode::All {
+                    mode = PauseMode::All;
+                }
+            }
+        }
+        *paused = Some((mode, end));
+    }
+
+    /// Lifts the pause, resuming the waiting commands.
+    pub fn unpause(&self) {
+        *self.paused.lock().unwrap() = None;
+        self.unpaused.notify_waiters();
+    }
+
+    /// Waits until a command can run, where write commands are also held back by a write pause.
+    pub async fn wait(&self, is_write: bool) {
+        loop {
+            // Created before checking the pause, so that an unpause in between is not missed.
+            let unpaused = self.unpaused.notified();
+            let end = match *self.paused.lock().unwrap() {
+                Some((mode, end)) if mode == PauseMode::All || is_write => end,
+                _ => return,
+            };
+            if end <= tokio::time::Instant::now() {
+                return;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep_until(end) => {}
+                _ = unpaused => {}
+            }
+        }
+    }
+}
+
+pub type SharedPause = Arc<Pause>;
+
+/// Creates a new pause, with commands not paused.
+pub fn new() -> SharedPause {
+    Arc::new(Pause::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn pause() -> SharedPause {
+        new()
+    }
+
+    /// Whether a command would wait.
+    async fn is_paused(pause: &Pause, is_write: bool) -> bool {
+        futures_util::FutureExt::now_or_never(pause.wait(is_write)).is_none()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_not_paused(pause: SharedPause) {
+        assert!(!is_paused(&pause, false).await);
+        assert!(!is_paused(&pause, true).await);
+    }
+
+    #[rstest]
+    #[case::all(PauseMode::All, true, true)]
+    #[case::write(PauseMode::Write, false, true)]
+    #[tokio::test]
+    async fn test_pause(
+        pause: SharedPause,
+        #[case] mode: PauseMode,
+        #[case] expected_read: bool,
+        #[case] expected_write: bool,
+    ) {
+        tokio::time::pause();
+        pause.pause(mode, tokio::time::Duration::from_millis(100));
+        assert_eq!(expected_read, is_paused(&pause, false).await);
+        assert_eq!(expected_write, is_paused(&pause, true).await);
+
+        tokio::time::advance(tokio::time::Duration::from_millis(100)).await;
+        assert!(!is_paused(&pause, false).await);
+        assert!(!is_paused(&pause, true).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_pause_waits_until_end(pause: SharedPause) {
+        tokio::time::pause();
+        let start = tokio::time::Instant::now();
+        pause.pause(PauseMode::All, tokio::time::Duration::from_millis(100));
+        pause.wait(false).await;
+        assert!(start.elapsed() >= tokio::time::Duration::from_millis(100));
+    }
+
+    #[rstest]
+    #[case::longer(PauseMode::Write, 200, 200)]
+    #[case::shorter(PauseMode::Write, 50, 100)]
+    #[case::all(PauseMode::All, 50, 100)]
+    #[tokio::test]
+    async fn test_pause_extends(
+        pause: SharedPause,
+        #[case] mode: PauseMode,
+        #[case] duration_ms: u64,
+        #[case] expected_end_ms: u64,
+    ) {
+        tokio::time::pause();
+        pause.pause(PauseMode::All, tokio::time::Duration::from_millis(100));
+        pause.pause(mode, tokio::time::Duration::from_millis(duration_ms));
+        assert!(is_paused(&pause, false).await);
+
+        tokio::time::advance(tokio::time::Duration::from_millis(expected_end_ms - 1)).await;
+        assert!(is_paused(&pause, true).await);
+        tokio::time::advance(tokio::time::Duration::from_millis(1)).await;
+        assert!(!is_paused(&pause, true).await);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_unpause(pause: SharedPause) {
+        tokio::time::pause();
+        pause.pause(PauseMode::All, tokio::time::Duration::from_secs(10));
+        let waiting = tokio::spawn({
+            let pause = pause.clone();
+            async move { pause.wait(true).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiting.is_finished());
+
+        pause.unpause();
+        waiting.await.unwrap();
+        assert!(!is_paused(&pause, true).await);
+    }
+}
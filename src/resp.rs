@@ -1,12 +1,24 @@
 //! This module contains the RESP (Redis Serialization Protocol) data types.
 use anyhow::{Context, Result};
-use bytes::{Buf, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use log::trace;
 
 /// Extracts the string from the message.
 pub fn extract_string(message: &RespType) -> Result<String> {
     match message {
-        RespType::BulkString(Some(s)) | RespType::SimpleString(s) => Ok(s.clone()),
+        RespType::BulkString(Some(s)) => {
+            String::from_utf8(s.to_vec()).context(format!("Cannot unpack: {:?}", message))
+        }
+        RespType::SimpleString(s) => Ok(s.clone()),
+        _ => Err(anyhow::anyhow!("Cannot unpack: {:?}", message)),
+    }
+}
+
+/// Extracts the bytes from the message, which need not be valid UTF-8.
+pub fn extract_bytes(message: &RespType) -> Result<Bytes> {
+    match message {
+        RespType::BulkString(Some(s)) => Ok(s.clone()),
+        RespType::SimpleString(s) => Ok(Bytes::copy_from_slice(s.as_bytes())),
         _ => Err(anyhow::anyhow!("Cannot unpack: {:?}", message)),
     }
 }
@@ -15,7 +27,7 @@ pub fn extract_string(message: &RespType) -> Result<String> {
 pub fn extract_command(message: RespType) -> Result<(String, Vec<RespType>)> {
     match message {
         RespType::Array(vec) => Ok((
-            extract_string(&vec[0])
+            extract_string(vec.first().context("Command is empty.")?)
                 .context("Failed to extract the message from the first argument.")?,
             vec.into_iter().skip(1).collect(),
         )),
@@ -37,6 +49,121 @@ fn read_until_crlf(buffer: &mut BytesMut) -> Option<BytesMut> {
     None
 }
 
+/// Finds the position of the first `\r\n` sequence at or after the start.
+fn find_crlf(buffer: &[u8], start: usize) -> Option<usize> {
+    buffer
+        .get(start..)?
+        .windows(2)
+        .position(|window| window == b"\r\n")
+        .map(|position| start + position)
+}
+
+/// How much of the frame at the start of a buffer has been framed, so that framing resumes there
+/// once more of the frame is buffered instead of starting over.
+#[derive(Debug, Default, PartialEq)]
+pub struct FrameState {
+    /// The end of the last complete element of the frame.
+    end: usize,
+    /// How far the header after the last complete element was searched for its `\r\n`.
+    scanned: usize,
+    /// The number of elements still expected by each aggregate being framed, outermost first.
+    pending: Vec<i64>,
+}
+
+/// Finds the end of the frame at the start of the buffer, without parsing its contents.
+///
+/// Returns `None` if the frame is not fully buffered yet, keeping the progress in the state so that
+/// the next call only frames the newly buffered bytes. The state is reset once the frame ends or
+/// is rejected. Lengths and nesting outside the protocol limits are rejected as soon as their
+/// header is buffered. A malformed length ends the frame at its header, so that the parser reports
+/// the error.
+fn frame_end(
+    buffer: &[u8],
+    limits: &ProtocolLimits,
+    state: &mut FrameState,
+) -> Result<Option<usize>> {
+    let result = resume_frame(buffer, limits, state);
+    if !matches!(result, Ok(None)) {
+        *state = FrameState::default();
+    }
+    result
+}
+
+/// Frames the elements after the last complete element of the state.
+fn resume_frame(
+    buffer: &[u8],
+    limits: &ProtocolLimits,
+    state: &mut FrameState,
+) -> Result<Option<usize>> {
+    let FrameState {
+        end,
+        scanned,
+        pending,
+    } = state;
+    loop {
+        let Some(&first_byte) = buffer.get(*end) else {
+            return Ok(None);
+        };
+        let Some(header_end) = find_crlf(buffer, (*scanned).max(*end)) else {
+            // The last byte may be the `\r` of the `\r\n`, so it is searched again.
+            *scanned = buffer.len() - 1;
+            return Ok(None);
+        };
+        let line_end = header_end + 2;
+        let length = std::str::from_utf8(&buffer[*end + 1..header_end])
+            .ok()
+            .and_then(|length| length.parse::<i64>().ok());
+
+        *end = match first_byte {
+            b'$' | b'=' | b'!' => {
+                let Some(length) = length else {
+                    return Ok(Some(line_end));
+                };
+                let length = usize::try_from(length)
+                    .ok()
+                    .filter(|length| *length <= limits.max_bulk_length)
+                    .ok_or(ProtocolError::InvalidBulkLength)?;
+                let end = line_end + length + 2;
+                if buffer.len() < end {
+                    return Ok(None);
+                }
+                end
+            }
+            b'*' | b'%' | b'~' | b'>' => {
+                let Some(length) = length else {
+                    return Ok(Some(line_end));
+                };
+                if !(0..=limits.max_multibulk_length as i64).contains(&length) {
+                    return Err(ProtocolError::InvalidMultibulkLength.into());
+                }
+                if pending.len() >= limits.max_depth {
+                    return Err(ProtocolError::NestingTooDeep.into());
+                }
+                let elements = if first_byte == b'%' {
+                    length * 2
+                } else {
+                    length
+                };
+                // The header counts as an element of its own, completed just below.
+                pending.push(elements + 1);
+                line_end
+            }
+            _ => line_end,
+        };
+        // Each element completes once, along with every aggregate it was the last element of.
+        while let Some(remaining) = pending.last_mut() {
+            *remaining -= 1;
+            if *remaining > 0 {
+                break;
+            }
+            pending.pop();
+        }
+        if pending.is_empty() {
+            return Ok(Some(*end));
+        }
+    }
+}
+
 /// Parses a byte slice into an integer.
 fn parse_num(buffer: BytesMut) -> Result<i64> {
     trace!("Attempting to parse number from buffer: {:?}.", buffer);
@@ -46,21 +173,118 @@ fn parse_num(buffer: BytesMut) -> Result<i64> {
         .context("Failed to parse the number.")
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// The range of magnitudes formatted without an exponent.
+const PLAIN_DOUBLE_RANGE: std::ops::Range<f64> = 1e-4..1e16;
+
+/// Formats a double the way Redis does, using `inf`, `-inf` and `nan` for the special values.
+/// Very large and very small magnitudes use an exponent instead of writing out every digit, which
+/// is always signed, as in `1e+16` and `1e-5`.
+fn format_double(num: f64) -> String {
+    if num.is_nan() {
+        "nan".into()
+    } else if num.is_finite() && num != 0.0 && !PLAIN_DOUBLE_RANGE.contains(&num.abs()) {
+        let formatted = format!("{num:e}");
+        match formatted.split_once('e') {
+            Some((mantissa, exponent)) if !exponent.starts_with('-') => {
+                format!("{mantissa}e+{exponent}")
+            }
+            _ => formatted,
+        }
+    } else {
+        num.to_string()
+    }
+}
+
+/// The largest bulk string length accepted by default, 512MB.
+const DEFAULT_MAX_BULK_LENGTH: usize = 512 * 1024 * 1024;
+/// The largest array or map length accepted by default.
+const DEFAULT_MAX_MULTIBULK_LENGTH: usize = 1024 * 1024;
+/// The most levels of nested aggregates accepted by default, as requests are flat arrays.
+const DEFAULT_MAX_DEPTH: usize = 1;
+/// The most unparsed bytes buffered for a client by default, 1GB.
+const DEFAULT_MAX_QUERY_BUFFER_LENGTH: usize = 1024 * 1024 * 1024;
+
+/// An error for a message that breaks the protocol, which closes the connection.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ProtocolError {
+    #[error("ERR Protocol error: invalid bulk length")]
+    InvalidBulkLength,
+    #[error("ERR Protocol error: invalid multibulk length")]
+    InvalidMultibulkLength,
+    #[error("ERR Protocol error: query buffer limit reached")]
+    QueryBufferLimit,
+    #[error("ERR Protocol error: aggregates nested too deeply")]
+    NestingTooDeep,
+    /// A message that could not be parsed, with the reason.
+    #[error("ERR Protocol error: {0}")]
+    Malformed(String),
+}
+
+/// The limits on the lengths declared by parsed messages.
+/// Lengths are checked before anything is allocated for them.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ProtocolLimits {
+    /// The largest bulk string length.
+    pub max_bulk_length: usize,
+    /// The largest number of elements in an array or set, or entries in a map.
+    pub max_multibulk_length: usize,
+    /// The most bytes buffered while waiting for the rest of a message.
+    pub max_query_buffer_length: usize,
+    /// The most levels of aggregates nested in each other, where 1 only allows flat aggregates.
+    pub max_depth: usize,
+}
+
+impl Default for ProtocolLimits {
+    fn default() -> Self {
+        Self {
+            max_bulk_length: DEFAULT_MAX_BULK_LENGTH,
+            max_multibulk_length: DEFAULT_MAX_MULTIBULK_LENGTH,
+            max_query_buffer_length: DEFAULT_MAX_QUERY_BUFFER_LENGTH,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl ProtocolLimits {
+    /// Gets the limits for the elements of an aggregate, which have one less level of nesting.
+    fn nested(&self) -> Result<Self> {
+        let max_depth = self
+            .max_depth
+            .checked_sub(1)
+            .ok_or(ProtocolError::NestingTooDeep)?;
+        Ok(Self { max_depth, ..*self })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 /// Represents a RESP (Redis Serialization Protocol) data type.
 pub enum RespType {
     SimpleString(String),
     SimpleError(String),
-    BulkString(Option<String>),
+    /// The bytes of a bulk string, which need not be valid UTF-8.
+    BulkString(Option<Bytes>),
     Array(Vec<RespType>),
     Integer(i64),
     Map(Vec<(RespType, RespType)>),
+    /// An unordered collection of unique elements.
+    Set(Vec<RespType>),
+    /// Out-of-band data sent to the client, such as pub/sub messages.
+    Push(Vec<RespType>),
+    Double(f64),
+    Boolean(bool),
+    /// A string with its three character format, such as `txt` or `mkd`.
+    VerbatimString(String, String),
+    /// An integer beyond the 64-bit range, as its decimal digits.
+    BigNumber(String),
     Null(),
+    /// Several replies written back to back, for commands that reply once per argument.
+    /// This is not a RESP type, so it is never parsed.
+    Replies(Vec<RespType>),
 }
 
 impl RespType {
     /// Parses the buffer for a simple string.
-    fn parse_simple_string(buffer: &mut BytesMut) -> Result<RespType> {
+    fn parse_simple_string(buffer: &mut BytesMut, _: &ProtocolLimits) -> Result<RespType> {
         trace!("Parsing simple string: {:?}.", buffer);
         Ok(RespType::SimpleString(
             String::from_utf8(
@@ -73,7 +297,7 @@ impl RespType {
     }
 
     /// Parses the buffer for a simple error.
-    fn parse_simple_error(buffer: &mut BytesMut) -> Result<RespType> {
+    fn parse_simple_error(buffer: &mut BytesMut, _: &ProtocolLimits) -> Result<RespType> {
         trace!("Parsing simple error: {:?}.", buffer);
         Ok(RespType::SimpleError(
             String::from_utf8(
@@ -86,14 +310,17 @@ impl RespType {
     }
 
     /// Parses a buffer for a bulk string.
-    fn parse_bulk_string(buffer: &mut BytesMut) -> Result<RespType> {
+    fn parse_bulk_string(buffer: &mut BytesMut, limits: &ProtocolLimits) -> Result<RespType> {
         trace!("Parsing bulk string: {:?}", buffer);
         let expected_message_length = parse_num(
             read_until_crlf(buffer)
                 .context(format!("Bulk string missing length segment: {:?}.", buffer))?,
         )
-        .context("Failed to parse bulk string length.")?
-            as usize;
+        .context("Failed to parse bulk string length.")?;
+        let expected_message_length = usize::try_from(expected_message_length)
+            .ok()
+            .filter(|length| *length <= limits.max_bulk_length)
+            .ok_or(ProtocolError::InvalidBulkLength)?;
 
         if buffer.len() < expected_message_length {
             return Err(anyhow::anyhow!(
@@ -103,15 +330,62 @@ impl RespType {
             ));
         }
 
-        let message = String::from_utf8(buffer.split_to(expected_message_length).to_vec())?;
+        let message = buffer.split_to(expected_message_length).freeze();
         if buffer.len() < 2 || buffer.split_to(2).as_ref() != b"\r\n" {
             return Err(anyhow::anyhow!("Expected CRLF."));
         }
         Ok(RespType::BulkString(Some(message)))
     }
 
+    /// Parses a buffer for a blob error, which is kept as a simple error.
+    fn parse_blob_error(buffer: &mut BytesMut, limits: &ProtocolLimits) -> Result<RespType> {
+        trace!("Parsing blob error: {:?}", buffer);
+        let message = match Self::parse_bulk_string(buffer, limits)? {
+            RespType::BulkString(Some(message)) => message,
+            message => unreachable!("Bulk string parsing returned {:?}.", message),
+        };
+        Ok(RespType::SimpleError(
+            String::from_utf8(message.to_vec()).context("Failed to parse blob error.")?,
+        ))
+    }
+
+    /// Parses a buffer for a verbatim string.
+    fn parse_verbatim_string(buffer: &mut BytesMut, limits: &ProtocolLimits) -> Result<RespType> {
+        trace!("Parsing verbatim string: {:?}", buffer);
+        let message = match Self::parse_bulk_string(buffer, limits)? {
+            RespType::BulkString(Some(message)) => message,
+            message => unreachable!("Bulk string parsing returned {:?}.", message),
+        };
+        let message =
+            String::from_utf8(message.to_vec()).context("Failed to parse verbatim string.")?;
+        match message.split_at_checked(3) {
+            Some((format, text)) if text.starts_with(':') => {
+                Ok(RespType::VerbatimString(format.into(), text[1..].into()))
+            }
+            _ => Err(anyhow::anyhow!(
+                "Verbatim string missing format: {:?}.",
+                message
+            )),
+        }
+    }
+
+    /// Parses a buffer for a big number.
+    fn parse_big_number(buffer: &mut BytesMut, _: &ProtocolLimits) -> Result<RespType> {
+        trace!("Parsing big number: {:?}", buffer);
+        let message =
+            read_until_crlf(buffer).context(format!("Big number missing: {:?}.", buffer))?;
+        let digits = message.strip_prefix(b"-").unwrap_or(&message);
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(anyhow::anyhow!("Invalid big number: {:?}.", message));
+        }
+
+        Ok(RespType::BigNumber(
+            String::from_utf8(message.to_vec()).context("Failed to parse big number.")?,
+        ))
+    }
+
     /// Parses a buffer for an integer.
-    fn parse_integer(buffer: &mut BytesMut) -> Result<RespType> {
+    fn parse_integer(buffer: &mut BytesMut, _: &ProtocolLimits) -> Result<RespType> {
         trace!("Parsing integer: {:?}", buffer);
 
         let number =
@@ -122,22 +396,26 @@ impl RespType {
     }
 
     /// Parses a buffer for a map.
-    fn parse_map(buffer: &mut BytesMut) -> Result<RespType> {
+    fn parse_map(buffer: &mut BytesMut, limits: &ProtocolLimits) -> Result<RespType> {
         trace!("Parsing map: {:?}", buffer);
         let map_length = parse_num(
             read_until_crlf(buffer)
                 .context(format!("Map missing length segment: {:?}.", buffer))?,
         )
         .context("Failed to parse map length.")?;
+        if !(0..=limits.max_multibulk_length as i64).contains(&map_length) {
+            return Err(ProtocolError::InvalidMultibulkLength.into());
+        }
+        let limits = &limits.nested()?;
 
         let mut messages = vec![];
         for _ in 0..map_length {
-            let key = RespType::from_bytes(buffer).context(format!(
+            let key = RespType::from_bytes_with_limits(buffer, limits).context(format!(
                 "Message did not match expected length. Expected: {}, got: {}.",
                 map_length,
                 messages.len()
             ))?;
-            let value = RespType::from_bytes(buffer).context(format!(
+            let value = RespType::from_bytes_with_limits(buffer, limits).context(format!(
                 "Message did not match expected length. Expected: {}, got: {}.",
                 map_length,
                 messages.len()
@@ -149,17 +427,21 @@ impl RespType {
     }
 
     /// Parses a buffer for an array.
-    fn parse_array(buffer: &mut BytesMut) -> Result<RespType> {
+    fn parse_array(buffer: &mut BytesMut, limits: &ProtocolLimits) -> Result<RespType> {
         trace!("Parsing array: {:?}", buffer);
         let array_length = parse_num(
             read_until_crlf(buffer)
                 .context(format!("Array missing length segment: {:?}.", buffer))?,
         )
         .context("Failed to parse array length.")?;
+        if !(0..=limits.max_multibulk_length as i64).contains(&array_length) {
+            return Err(ProtocolError::InvalidMultibulkLength.into());
+        }
+        let limits = &limits.nested()?;
 
         let mut messages = vec![];
         for _ in 0..array_length {
-            let message = RespType::from_bytes(buffer).context(format!(
+            let message = RespType::from_bytes_with_limits(buffer, limits).context(format!(
                 "Message did not match expected length. Expected: {}, got: {}.",
                 array_length,
                 messages.len()
@@ -170,8 +452,86 @@ impl RespType {
         Ok(RespType::Array(messages))
     }
 
+    /// Parses a buffer for a set.
+    fn parse_set(buffer: &mut BytesMut, limits: &ProtocolLimits) -> Result<RespType> {
+        trace!("Parsing set: {:?}", buffer);
+        let set_length = parse_num(
+            read_until_crlf(buffer)
+                .context(format!("Set missing length segment: {:?}.", buffer))?,
+        )
+        .context("Failed to parse set length.")?;
+        if !(0..=limits.max_multibulk_length as i64).contains(&set_length) {
+            return Err(ProtocolError::InvalidMultibulkLength.into());
+        }
+        let limits = &limits.nested()?;
+
+        let mut messages = vec![];
+        for _ in 0..set_length {
+            let message = RespType::from_bytes_with_limits(buffer, limits).context(format!(
+                "Message did not match expected length. Expected: {}, got: {}.",
+                set_length,
+                messages.len()
+            ))?;
+            messages.push(message);
+        }
+
+        Ok(RespType::Set(messages))
+    }
+
+    /// Parses a buffer for a push.
+    fn parse_push(buffer: &mut BytesMut, limits: &ProtocolLimits) -> Result<RespType> {
+        trace!("Parsing push: {:?}", buffer);
+        let push_length = parse_num(
+            read_until_crlf(buffer)
+                .context(format!("Push missing length segment: {:?}.", buffer))?,
+        )
+        .context("Failed to parse push length.")?;
+        if !(0..=limits.max_multibulk_length as i64).contains(&push_length) {
+            return Err(ProtocolError::InvalidMultibulkLength.into());
+        }
+        let limits = &limits.nested()?;
+
+        let mut messages = vec![];
+        for _ in 0..push_length {
+            let message = RespType::from_bytes_with_limits(buffer, limits).context(format!(
+                "Message did not match expected length. Expected: {}, got: {}.",
+                push_length,
+                messages.len()
+            ))?;
+            messages.push(message);
+        }
+
+        Ok(RespType::Push(messages))
+    }
+
+    /// Parses a buffer for a double.
+    fn parse_double(buffer: &mut BytesMut, _: &ProtocolLimits) -> Result<RespType> {
+        trace!("Parsing double: {:?}", buffer);
+        let number = String::from_utf8(
+            read_until_crlf(buffer)
+                .context(format!("Double missing: {:?}.", buffer))?
+                .to_vec(),
+        )
+        .context("Failed to parse double.")?
+        .parse::<f64>()
+        .context("Failed to parse double.")?;
+
+        Ok(RespType::Double(number))
+    }
+
+    /// Parses a buffer for a boolean.
+    fn parse_boolean(buffer: &mut BytesMut, _: &ProtocolLimits) -> Result<RespType> {
+        trace!("Parsing boolean: {:?}", buffer);
+        let message = read_until_crlf(buffer).context("Boolean missing CRLF.")?;
+        match message.as_ref() {
+            b"t" => Ok(RespType::Boolean(true)),
+            b"f" => Ok(RespType::Boolean(false)),
+            _ => Err(anyhow::anyhow!("Invalid boolean: {:?}.", message)),
+        }
+    }
+
     /// Parses a buffer for a null.
-    fn parse_null(buffer: &mut BytesMut) -> Result<RespType> {
+    fn parse_null(buffer: &mut BytesMut, _: &ProtocolLimits) -> Result<RespType> {
         trace!("Parsing null: {:?}", buffer);
         let message = read_until_crlf(buffer).context("Null missing CRLF.")?;
         if !message.is_empty() {
@@ -181,57 +541,218 @@ impl RespType {
         Ok(RespType::Null())
     }
 
-    /// Parses a buffer for the message.
+    /// Parses a buffer for an inline command.
+    /// The line is split on whitespace into an array of bulk strings.
+    fn parse_inline(buffer: &mut BytesMut, _: &ProtocolLimits) -> Result<RespType> {
+        trace!("Parsing inline command: {:?}", buffer);
+        let line = String::from_utf8(
+            read_until_crlf(buffer)
+                .context(format!("Inline command missing CRLF: {:?}.", buffer))?
+                .to_vec(),
+        )
+        .context("Failed to parse inline command.")?;
+
+        let tokens = line
+            .split_whitespace()
+            .map(|token| RespType::BulkString(Some(Bytes::copy_from_slice(token.as_bytes()))))
+            .collect::<Vec<_>>();
+        if tokens.is_empty() {
+            return Err(anyhow::anyhow!("Inline command is empty."));
+        }
+
+        Ok(RespType::Array(tokens))
+    }
+
+    /// Parses a buffer for the message, using the default protocol limits but allowing nested
+    /// aggregates, as in replies.
+    #[cfg(test)]
     pub fn from_bytes(buffer: &mut BytesMut) -> Result<Self> {
+        let limits = ProtocolLimits {
+            max_depth: usize::MAX,
+            ..Default::default()
+        };
+        Self::from_bytes_with_limits(buffer, &limits)
+    }
+
+    /// Parses the first message of the buffer once it is fully buffered, rejecting lengths outside
+    /// the protocol limits.
+    ///
+    /// Returns `None` and leaves the buffer untouched if more data is needed, in which case the
+    /// state records how much of the message was framed. The same state must be passed with the
+    /// buffer once more data is appended to it.
+    pub fn try_from_bytes_with_limits(
+        buffer: &mut BytesMut,
+        limits: &ProtocolLimits,
+        state: &mut FrameState,
+    ) -> Result<Option<Self>> {
+        match frame_end(buffer, limits, state)? {
+            Some(end) => Self::from_bytes_with_limits(&mut buffer.split_to(end), limits).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses a buffer for the message, rejecting lengths outside the protocol limits.
+    pub fn from_bytes_with_limits(buffer: &mut BytesMut, limits: &ProtocolLimits) -> Result<Self> {
         trace!("Parsing message: {:?}.", buffer);
         if let Some((&first_byte, _)) = buffer.split_first() {
+            let parser = match first_byte as char {
+                '+' => Self::parse_simple_string,
+                '-' => Self::parse_simple_error,
+                '$' => Self::parse_bulk_string,
+                ':' => Self::parse_integer,
+                '%' => Self::parse_map,
+                '*' => Self::parse_array,
+                '~' => Self::parse_set,
+                '>' => Self::parse_push,
+                ',' => Self::parse_double,
+                '#' => Self::parse_boolean,
+                '!' => Self::parse_blob_error,
+                '=' => Self::parse_verbatim_string,
+                '(' => Self::parse_big_number,
+                '_' => Self::parse_null,
+                _ => return Self::parse_inline(buffer, limits),
+            };
             _ = buffer.split_to(1);
-            match first_byte as char {
-                '+' => Self::parse_simple_string(buffer),
-                '-' => Self::parse_simple_error(buffer),
-                '$' => Self::parse_bulk_string(buffer),
-                ':' => Self::parse_integer(buffer),
-                '%' => Self::parse_map(buffer),
-                '*' => Self::parse_array(buffer),
-                '_' => Self::parse_null(buffer),
-                _ => Err(anyhow::anyhow!("Invalid message type.")),
-            }
+            parser(buffer, limits)
         } else {
             Err(anyhow::anyhow!("Buffer empty."))
         }
     }
 
-    /// Serializes the RESP into a RESP-compliant string.
-    pub fn serialize(&self) -> String {
-        match self {
-            Self::SimpleString(s) => format!("+{s}\r\n"),
-            Self::SimpleError(s) => format!("-{s}\r\n"),
-            Self::BulkString(Some(s)) => format!("${}\r\n{s}\r\n", s.len()),
-            Self::BulkString(None) => "$-1\r\n".into(),
-            Self::Array(array) => {
-                format!(
-                    "*{}\r\n{}",
-                    array.len(),
-                    array
-                        .iter()
-                        .map(|element| element.serialize())
-                        .fold(String::new(), |result, x| result + &x)
-                )
+    /// Appends the serialized RESP to the buffer, using the protocol version if one is given.
+    /// Without a version, each type is written as its own RESP type.
+    /// RESP2 has no null, double, boolean, map, set or push types, so they are written as a null
+    /// bulk string, a bulk string, an integer, a flattened array and arrays respectively.
+    /// Verbatim strings and big numbers are written as bulk strings.
+    fn encode(&self, buf: &mut BytesMut, version: Option<&crate::state::ProtocolVersion>) {
+        use crate::state::ProtocolVersion;
+        use std::fmt::Write;
+
+        match (self, version) {
+            (Self::SimpleString(s), _) => {
+                buf.put_u8(b'+');
+                buf.put_slice(s.as_bytes());
+                buf.put_slice(b"\r\n");
             }
-            Self::Integer(num) => format!(":{num}\r\n"),
-            Self::Map(map) => {
-                format!(
-                    "%{}\r\n{}",
-                    map.len(),
-                    map.iter()
-                        .map(|(key, value)| format!("{}{}", key.serialize(), value.serialize()))
-                        .collect::<Vec<String>>()
-                        .join("")
-                )
+            (Self::SimpleError(s), _) => {
+                buf.put_u8(b'-');
+                buf.put_slice(s.as_bytes());
+                buf.put_slice(b"\r\n");
+            }
+            (Self::BulkString(Some(s)), _) => {
+                let _ = write!(buf, "${}\r\n", s.len());
+                buf.put_slice(s);
+                buf.put_slice(b"\r\n");
+            }
+            (Self::BulkString(None), None | Some(ProtocolVersion::V2))
+            | (Self::Null(), Some(ProtocolVersion::V2)) => buf.put_slice(b"$-1\r\n"),
+            (Self::BulkString(None), Some(ProtocolVersion::V3))
+            | (Self::Null(), None | Some(ProtocolVersion::V3)) => buf.put_slice(b"_\r\n"),
+            (Self::Array(array), _)
+            | (Self::Set(array), Some(ProtocolVersion::V2))
+            | (Self::Push(array), Some(ProtocolVersion::V2)) => {
+                let _ = write!(buf, "*{}\r\n", array.len());
+                for element in array {
+                    element.encode(buf, version);
+                }
+            }
+            (Self::Integer(num), _) => {
+                let _ = write!(buf, ":{num}\r\n");
+            }
+            (Self::Map(map), Some(ProtocolVersion::V2)) => {
+                let _ = write!(buf, "*{}\r\n", map.len() * 2);
+                for (key, value) in map {
+                    key.encode(buf, version);
+                    value.encode(buf, version);
+                }
+            }
+            (Self::Map(map), _) => {
+                let _ = write!(buf, "%{}\r\n", map.len());
+                for (key, value) in map {
+                    key.encode(buf, version);
+                    value.encode(buf, version);
+                }
+            }
+            (Self::Set(set), _) => {
+                let _ = write!(buf, "~{}\r\n", set.len());
+                for element in set {
+                    element.encode(buf, version);
+                }
+            }
+            (Self::Push(push), _) => {
+                let _ = write!(buf, ">{}\r\n", push.len());
+                for element in push {
+                    element.encode(buf, version);
+                }
+            }
+            (Self::Double(num), Some(ProtocolVersion::V2)) => {
+                Self::BulkString(Some(format_double(*num).into())).encode(buf, version)
+            }
+            (Self::Double(num), _) => {
+                let _ = write!(buf, ",{}\r\n", format_double(*num));
+            }
+            (Self::Boolean(value), Some(ProtocolVersion::V2)) => {
+                Self::Integer(*value as i64).encode(buf, version)
+            }
+            (Self::Boolean(value), _) => {
+                buf.put_slice(if *value { b"#t\r\n" } else { b"#f\r\n" });
+            }
+            (Self::VerbatimString(_, text), Some(ProtocolVersion::V2)) => {
+                Self::BulkString(Some(Bytes::copy_from_slice(text.as_bytes()))).encode(buf, version)
+            }
+            (Self::VerbatimString(format, text), _) => {
+                let _ = write!(
+                    buf,
+                    "={}\r\n{format}:{text}\r\n",
+                    format.len() + 1 + text.len()
+                );
+            }
+            (Self::BigNumber(digits), Some(ProtocolVersion::V2)) => {
+                Self::BulkString(Some(Bytes::copy_from_slice(digits.as_bytes())))
+                    .encode(buf, version)
+            }
+            (Self::BigNumber(digits), _) => {
+                let _ = write!(buf, "({digits}\r\n");
+            }
+            (Self::Replies(replies), _) => {
+                for reply in replies {
+                    reply.encode(buf, version);
+                }
             }
-            Self::Null() => "_\r\n".into(),
         }
     }
+
+    /// Appends the RESP-compliant bytes to the buffer.
+    #[cfg(test)]
+    pub fn serialize_into(&self, buf: &mut BytesMut) {
+        self.encode(buf, None);
+    }
+
+    /// Appends the bytes compliant with the given protocol version to the buffer.
+    pub fn serialize_with_version_into(
+        &self,
+        version: &crate::state::ProtocolVersion,
+        buf: &mut BytesMut,
+    ) {
+        self.encode(buf, Some(version));
+    }
+
+    /// Serializes the RESP into RESP-compliant bytes.
+    /// Bulk strings need not be valid UTF-8, so the bytes are not a string.
+    #[cfg(test)]
+    pub fn serialize(&self) -> Bytes {
+        let mut buf = BytesMut::new();
+        self.serialize_into(&mut buf);
+        buf.freeze()
+    }
+
+    /// Serializes the RESP into bytes compliant with the given protocol version.
+    #[cfg(test)]
+    pub fn serialize_with_version(&self, version: &crate::state::ProtocolVersion) -> Bytes {
+        let mut buf = BytesMut::new();
+        self.serialize_with_version_into(version, &mut buf);
+        buf.freeze()
+    }
 }
 
 #[cfg(test)]
@@ -256,11 +777,31 @@ mod tests {
     #[rstest]
     #[case::array(RespType::Array(vec![]))]
     #[case::null(RespType::Null())]
+    #[case::invalid_utf8(RespType::BulkString(Some(Bytes::from_static(b"\xff\xfe"))))]
     fn test_extract_string_fail(#[case] message: RespType) {
         let result = extract_string(&message);
         assert!(result.is_err());
     }
 
+    // --- Extract bytes ---
+    #[rstest]
+    #[case::bulk_string(RespType::BulkString(Some("Test".into())), b"Test")]
+    #[case::bulk_string_binary(
+        RespType::BulkString(Some(Bytes::from_static(b"\xff\x00\xfe"))),
+        b"\xff\x00\xfe"
+    )]
+    #[case::simple_string(RespType::SimpleString("Test".into()), b"Test")]
+    fn test_extract_bytes(#[case] message: RespType, #[case] expected: &[u8]) {
+        assert_eq!(expected, extract_bytes(&message).unwrap());
+    }
+
+    #[rstest]
+    #[case::array(RespType::Array(vec![]))]
+    #[case::null_bulk_string(RespType::BulkString(None))]
+    fn test_extract_bytes_fail(#[case] message: RespType) {
+        assert!(extract_bytes(&message).is_err());
+    }
+
     // --- Extract command ---
     #[rstest]
     #[case::set_command(
@@ -301,6 +842,7 @@ mod tests {
     #[rstest]
     #[case::simple_string(RespType::SimpleString("SET".into()))]
     #[case::bulk_string(RespType::BulkString(Some("SET".into())))]
+    #[case::empty_array(RespType::Array(vec![]))]
     fn test_extract_command_fail(#[case] message: RespType) {
         let result = extract_command(message);
         assert!(result.is_err());
@@ -409,6 +951,10 @@ mod tests {
         b"$13\r\nTest\r\nAnother\r\n",
         Ok(RespType::BulkString(Some("Test\r\nAnother".into())))
     )]
+    #[case::bulk_string_binary(
+        b"$4\r\n\xff\x00\r\n\r\n",
+        Ok(RespType::BulkString(Some(Bytes::from_static(b"\xff\x00\r\n"))))
+    )]
     #[case::bulk_string_mismatch_length(
         b"$7\r\nTest\r\n",
         Err(anyhow::anyhow!("Message did not match the expected length. Expected: 7, got: 6."))
@@ -468,6 +1014,22 @@ mod tests {
         ]))
     )]
     #[case::array_empty(b"*0\r\n", Ok(RespType::Array(vec![])))]
+    // Sets
+    #[case::set(
+        b"~2\r\n+a\r\n:1\r\n",
+        Ok(RespType::Set(vec![RespType::SimpleString("a".into()), RespType::Integer(1)]))
+    )]
+    #[case::set_empty(b"~0\r\n", Ok(RespType::Set(vec![])))]
+    // Pushes
+    #[case::push(
+        b">2\r\n+message\r\n:1\r\n",
+        Ok(RespType::Push(vec![RespType::SimpleString("message".into()), RespType::Integer(1)]))
+    )]
+    #[case::push_empty(b">0\r\n", Ok(RespType::Push(vec![])))]
+    #[case::push_invalid_length(b">a\r\n", Err(anyhow::anyhow!("Failed to parse push length.")))]
+    #[case::push_missing_length(b">2", Err(anyhow::anyhow!("Push missing length segment: b\"2\".")))]
+    #[case::set_invalid_length(b"~a\r\n", Err(anyhow::anyhow!("Failed to parse set length.")))]
+    #[case::set_missing_length(b"~2", Err(anyhow::anyhow!("Set missing length segment: b\"2\".")))]
     #[case::array_too_short(
         b"*3\r\n+Test\r\n+Another\r\n",
         Err(anyhow::anyhow!("Message did not match expected length. Expected: 3, got: 2."))
@@ -477,12 +1039,83 @@ mod tests {
         Err(anyhow::anyhow!("Failed to parse array length."))
     )]
     #[case::array_missing_length(b"*2", Err(anyhow::anyhow!("Array missing length segment: b\"2\".")))]
+    // Doubles
+    #[case::double(b",1.5\r\n", Ok(RespType::Double(1.5)))]
+    #[case::double_integral(b",10\r\n", Ok(RespType::Double(10.0)))]
+    #[case::double_negative(b",-1.5\r\n", Ok(RespType::Double(-1.5)))]
+    #[case::double_exponent(b",1.5e3\r\n", Ok(RespType::Double(1500.0)))]
+    #[case::double_inf(b",inf\r\n", Ok(RespType::Double(f64::INFINITY)))]
+    #[case::double_negative_inf(b",-inf\r\n", Ok(RespType::Double(f64::NEG_INFINITY)))]
+    #[case::double_positive_inf(b",+inf\r\n", Ok(RespType::Double(f64::INFINITY)))]
+    #[case::double_upper_exponent(b",1.5E-3\r\n", Ok(RespType::Double(0.0015)))]
+    #[case::double_zero(b",0\r\n", Ok(RespType::Double(0.0)))]
+    #[case::double_missing_crlf(b",1.5", Err(anyhow::anyhow!("Double missing: b\"1.5\".")))]
+    #[case::double_invalid(b",abc\r\n", Err(anyhow::anyhow!("Failed to parse double.")))]
+    // Booleans
+    #[case::boolean_true(b"#t\r\n", Ok(RespType::Boolean(true)))]
+    #[case::boolean_false(b"#f\r\n", Ok(RespType::Boolean(false)))]
+    #[case::boolean_missing_crlf(b"#t", Err(anyhow::anyhow!("Boolean missing CRLF.")))]
+    #[case::boolean_invalid(b"#x\r\n", Err(anyhow::anyhow!("Invalid boolean: b\"x\".")))]
+    // Blob errors
+    #[case::blob_error(
+        b"!21\r\nSYNTAX invalid syntax\r\n",
+        Ok(RespType::SimpleError("SYNTAX invalid syntax".into()))
+    )]
+    #[case::blob_error_with_crlf(b"!4\r\na\r\nb\r\n", Ok(RespType::SimpleError("a\r\nb".into())))]
+    #[case::blob_error_missing_crlf(b"!3\r\nabc", Err(anyhow::anyhow!("Expected CRLF.")))]
+    // Verbatim strings
+    #[case::verbatim_string(
+        b"=15\r\ntxt:Some string\r\n",
+        Ok(RespType::VerbatimString("txt".into(), "Some string".into()))
+    )]
+    #[case::verbatim_string_empty(b"=4\r\nmkd:\r\n", Ok(RespType::VerbatimString("mkd".into(), "".into())))]
+    #[case::verbatim_string_with_crlf(
+        b"=10\r\ntxt:a\r\nb:c\r\n",
+        Ok(RespType::VerbatimString("txt".into(), "a\r\nb:c".into()))
+    )]
+    #[case::verbatim_string_missing_format(
+        b"=3\r\ntxt\r\n",
+        Err(anyhow::anyhow!("Verbatim string missing format: \"txt\"."))
+    )]
+    #[case::verbatim_string_long_format(
+        b"=6\r\ntext:a\r\n",
+        Err(anyhow::anyhow!("Verbatim string missing format: \"text:a\"."))
+    )]
+    // Big numbers
+    #[case::big_number(
+        b"(3492890328409238509324850943850943825024385\r\n",
+        Ok(RespType::BigNumber("3492890328409238509324850943850943825024385".into()))
+    )]
+    #[case::big_number_negative(b"(-12345678901234567890\r\n", Ok(RespType::BigNumber("-12345678901234567890".into())))]
+    #[case::big_number_missing_crlf(b"(123", Err(anyhow::anyhow!("Big number missing: b\"123\".")))]
+    #[case::big_number_invalid(b"(12a\r\n", Err(anyhow::anyhow!("Invalid big number: b\"12a\".")))]
+    #[case::big_number_sign_only(b"(-\r\n", Err(anyhow::anyhow!("Invalid big number: b\"-\".")))]
     // Null
     #[case::null(b"_\r\n", Ok(RespType::Null()))]
     #[case::null_missing_crlf(b"_", Err(anyhow::anyhow!("Null missing CRLF.")))]
     #[case::null_invalid(b"_abc\r\n", Err(anyhow::anyhow!("Null should not have any value.")))]
-    // Invalid type
-    #[case::invalid(b"123", Err(anyhow::anyhow!("Invalid message type.")))]
+    // Inline commands
+    #[case::inline_ping(
+        b"PING\r\n",
+        Ok(RespType::Array(vec![RespType::BulkString(Some("PING".into()))]))
+    )]
+    #[case::inline_set(
+        b"SET key value\r\n",
+        Ok(RespType::Array(vec![
+            RespType::BulkString(Some("SET".into())),
+            RespType::BulkString(Some("key".into())),
+            RespType::BulkString(Some("value".into())),
+        ]))
+    )]
+    #[case::inline_extra_whitespace(
+        b"  ECHO   hello \r\n",
+        Ok(RespType::Array(vec![
+            RespType::BulkString(Some("ECHO".into())),
+            RespType::BulkString(Some("hello".into())),
+        ]))
+    )]
+    #[case::inline_missing_crlf(b"123", Err(anyhow::anyhow!("Inline command missing CRLF: b\"123\".")))]
+    #[case::inline_empty(b"  \r\n", Err(anyhow::anyhow!("Inline command is empty.")))]
     /// Tests the parser.
     fn test_parse(#[case] bytes: &[u8], #[case] expected: Result<RespType>) {
         let result = RespType::from_bytes(&mut bytes.into());
@@ -497,6 +1130,208 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[case::bulk_string(b"$5\r\nhello\r\n", ProtocolError::InvalidBulkLength)]
+    #[case::verbatim_string(b"=5\r\ntxt:a\r\n", ProtocolError::InvalidBulkLength)]
+    #[case::bulk_string_huge(b"$99999999999\r\n", ProtocolError::InvalidBulkLength)]
+    #[case::bulk_string_negative(b"$-1\r\n", ProtocolError::InvalidBulkLength)]
+    #[case::array(b"*3\r\n", ProtocolError::InvalidMultibulkLength)]
+    #[case::array_huge(b"*4000000000\r\n", ProtocolError::InvalidMultibulkLength)]
+    #[case::array_negative(b"*-1\r\n", ProtocolError::InvalidMultibulkLength)]
+    #[case::map(b"%3\r\n", ProtocolError::InvalidMultibulkLength)]
+    #[case::set(b"~3\r\n", ProtocolError::InvalidMultibulkLength)]
+    #[case::push(b">3\r\n", ProtocolError::InvalidMultibulkLength)]
+    #[case::nested_bulk_string(b"*1\r\n$5\r\nhello\r\n", ProtocolError::InvalidBulkLength)]
+    #[case::nested_array(b"*1\r\n*1\r\n*0\r\n", ProtocolError::NestingTooDeep)]
+    #[case::nested_map(b"%1\r\n:1\r\n*1\r\n~0\r\n", ProtocolError::NestingTooDeep)]
+    #[case::nested_set(b"~1\r\n>1\r\n>0\r\n", ProtocolError::NestingTooDeep)]
+    /// Tests that declared lengths beyond the limits are rejected before reading the contents.
+    fn test_parse_with_limits(#[case] bytes: &[u8], #[case] expected: ProtocolError) {
+        let limits = ProtocolLimits {
+            max_bulk_length: 4,
+            max_multibulk_length: 2,
+            max_depth: 2,
+            ..Default::default()
+        };
+        let result = RespType::from_bytes_with_limits(&mut bytes.into(), &limits);
+        assert_eq!(
+            Some(&expected),
+            result.unwrap_err().downcast_ref::<ProtocolError>()
+        );
+    }
+
+    #[rstest]
+    #[case::simple_string(b"+Test\r\n", RespType::SimpleString("Test".into()))]
+    #[case::bulk_string(b"$4\r\nTest\r\n", RespType::BulkString(Some("Test".into())))]
+    #[case::bulk_string_with_crlf(b"$6\r\nTe\r\nst\r\n", RespType::BulkString(Some("Te\r\nst".into())))]
+    #[case::array(
+        b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n",
+        RespType::Array(vec![
+            RespType::BulkString(Some("GET".into())),
+            RespType::BulkString(Some("key".into())),
+        ])
+    )]
+    #[case::map(
+        b"%1\r\n+Key\r\n:1\r\n",
+        RespType::Map(vec![(RespType::SimpleString("Key".into()), RespType::Integer(1))])
+    )]
+    #[case::set(
+        b"~2\r\n$1\r\na\r\n$1\r\nb\r\n",
+        RespType::Set(vec![
+            RespType::BulkString(Some("a".into())),
+            RespType::BulkString(Some("b".into())),
+        ])
+    )]
+    #[case::blob_error(b"!6\r\nERR\r\nx\r\n", RespType::SimpleError("ERR\r\nx".into()))]
+    #[case::verbatim_string(b"=6\r\ntxt:\r\n\r\n", RespType::VerbatimString("txt".into(), "\r\n".into()))]
+    #[case::big_number(b"(123456789012345678901\r\n", RespType::BigNumber("123456789012345678901".into()))]
+    #[case::push(b">1\r\n$1\r\na\r\n", RespType::Push(vec![RespType::BulkString(Some("a".into()))]))]
+    #[case::inline(b"PING\r\n", RespType::Array(vec![RespType::BulkString(Some("PING".into()))]))]
+    /// Tests that a message is only parsed once every byte of it is buffered, resuming the framing
+    /// as each byte arrives.
+    fn test_try_parse(#[case] bytes: &[u8], #[case] expected: RespType) {
+        let limits = ProtocolLimits::default();
+        let mut state = FrameState::default();
+        for end in 0..bytes.len() {
+            let mut buffer = BytesMut::from(&bytes[..end]);
+            assert!(
+                RespType::try_from_bytes_with_limits(&mut buffer, &limits, &mut state)
+                    .unwrap()
+                    .is_none()
+            );
+            assert_eq!(&bytes[..end], buffer);
+        }
+
+        let mut buffer = BytesMut::from(bytes);
+        buffer.extend_from_slice(b"+Next\r\n");
+        assert_eq!(
+            Some(expected),
+            RespType::try_from_bytes_with_limits(&mut buffer, &limits, &mut state).unwrap()
+        );
+        assert_eq!(b"+Next\r\n".as_slice(), buffer);
+        assert_eq!(FrameState::default(), state);
+    }
+
+    #[rstest]
+    /// Tests that the framing state records the elements framed so far.
+    fn test_try_parse_keeps_state() {
+        let limits = ProtocolLimits::default();
+        let mut state = FrameState::default();
+        let mut buffer = BytesMut::from("*2\r\n:1\r\n:2");
+        assert!(
+            RespType::try_from_bytes_with_limits(&mut buffer, &limits, &mut state)
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            FrameState {
+                end: 8,
+                scanned: 9,
+                pending: vec![1],
+            },
+            state
+        );
+
+        buffer.extend_from_slice(b"\r\n");
+        assert_eq!(
+            Some(RespType::Array(vec![
+                RespType::Integer(1),
+                RespType::Integer(2)
+            ])),
+            RespType::try_from_bytes_with_limits(&mut buffer, &limits, &mut state).unwrap()
+        );
+        assert_eq!(FrameState::default(), state);
+    }
+
+    #[rstest]
+    #[case::bulk_string_length(b"$a\r\n", "Failed to parse bulk string length.")]
+    #[case::array_length(b"*a\r\n", "Failed to parse array length.")]
+    #[case::boolean(b"#x\r\n", "Invalid boolean: b\"x\".")]
+    /// Tests that malformed messages are reported once their header is buffered.
+    fn test_try_parse_errors(#[case] bytes: &[u8], #[case] expected: &str) {
+        let result = RespType::try_from_bytes_with_limits(
+            &mut bytes.into(),
+            &ProtocolLimits::default(),
+            &mut FrameState::default(),
+        );
+        assert_eq!(expected, result.unwrap_err().to_string());
+    }
+
+    #[rstest]
+    #[case::bulk_string(b"$5\r\nhel", ProtocolError::InvalidBulkLength)]
+    #[case::verbatim_string(b"=9\r\ntxt:", ProtocolError::InvalidBulkLength)]
+    #[case::array(b"*3\r\n:1\r\n", ProtocolError::InvalidMultibulkLength)]
+    #[case::nested_array(b"*1\r\n*1\r\n", ProtocolError::NestingTooDeep)]
+    #[case::nested_map(b"*2\r\n:1\r\n%1\r\n", ProtocolError::NestingTooDeep)]
+    /// Tests that lengths beyond the limits are rejected without waiting for the contents.
+    fn test_try_parse_with_limits(#[case] bytes: &[u8], #[case] expected: ProtocolError) {
+        let limits = ProtocolLimits {
+            max_bulk_length: 4,
+            max_multibulk_length: 2,
+            ..Default::default()
+        };
+        let result = RespType::try_from_bytes_with_limits(
+            &mut bytes.into(),
+            &limits,
+            &mut FrameState::default(),
+        );
+        assert_eq!(
+            Some(&expected),
+            result.unwrap_err().downcast_ref::<ProtocolError>()
+        );
+    }
+
+    #[rstest]
+    #[case::flat(1)]
+    #[case::nested(64)]
+    /// Tests that a long run of nested aggregates is rejected rather than overflowing the stack.
+    fn test_try_parse_deeply_nested(#[case] max_depth: usize) {
+        let limits = ProtocolLimits {
+            max_depth,
+            ..Default::default()
+        };
+        let mut buffer = BytesMut::from("*1\r\n".repeat(1_000_000).as_bytes());
+        let result =
+            RespType::try_from_bytes_with_limits(&mut buffer, &limits, &mut FrameState::default());
+        assert_eq!(
+            Some(&ProtocolError::NestingTooDeep),
+            result.unwrap_err().downcast_ref::<ProtocolError>()
+        );
+    }
+
+    #[rstest]
+    #[case::bulk_string(b"$4\r\nTest\r\n", RespType::BulkString(Some("Test".into())))]
+    #[case::array(
+        b"*2\r\n:1\r\n:2\r\n",
+        RespType::Array(vec![RespType::Integer(1), RespType::Integer(2)])
+    )]
+    #[case::nested_array(
+        b"*2\r\n*1\r\n:1\r\n%0\r\n",
+        RespType::Array(vec![RespType::Array(vec![RespType::Integer(1)]), RespType::Map(vec![])])
+    )]
+    /// Tests that declared lengths and nesting within the limits are accepted.
+    fn test_parse_within_limits(#[case] bytes: &[u8], #[case] expected: RespType) {
+        let limits = ProtocolLimits {
+            max_bulk_length: 4,
+            max_multibulk_length: 2,
+            max_depth: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            expected,
+            RespType::from_bytes_with_limits(&mut bytes.into(), &limits).unwrap()
+        );
+        assert_eq!(
+            Some(expected),
+            RespType::try_from_bytes_with_limits(
+                &mut bytes.into(),
+                &limits,
+                &mut FrameState::default()
+            )
+            .unwrap()
+        );
+    }
+
     // --- Serialization ---
     #[rstest]
     // Simple strings
@@ -538,10 +1373,276 @@ mod tests {
         ]),
         "*3\r\n+Test\r\n$0\r\n\r\n:-123\r\n"
     )]
+    #[case::array_nested(
+        RespType::Array(vec![
+            RespType::Array(vec![RespType::Integer(1), RespType::Integer(2)]),
+            RespType::Array(vec![]),
+            RespType::Array(vec![RespType::Array(vec![RespType::BulkString(None)])]),
+        ]),
+        "*3\r\n*2\r\n:1\r\n:2\r\n*0\r\n*1\r\n*1\r\n$-1\r\n"
+    )]
+    // Sets
+    #[case::set_empty(RespType::Set(vec![]), "~0\r\n")]
+    #[case::set_multiple(
+        RespType::Set(vec![RespType::BulkString(Some("a".into())), RespType::Integer(1)]),
+        "~2\r\n$1\r\na\r\n:1\r\n"
+    )]
+    // Pushes
+    #[case::push(
+        RespType::Push(vec![RespType::BulkString(Some("a".into())), RespType::Integer(1)]),
+        ">2\r\n$1\r\na\r\n:1\r\n"
+    )]
+    // Doubles
+    #[case::double(RespType::Double(1.5), ",1.5\r\n")]
+    #[case::double_integral(RespType::Double(10.0), ",10\r\n")]
+    #[case::double_negative(RespType::Double(-1.5), ",-1.5\r\n")]
+    #[case::double_inf(RespType::Double(f64::INFINITY), ",inf\r\n")]
+    #[case::double_negative_inf(RespType::Double(f64::NEG_INFINITY), ",-inf\r\n")]
+    #[case::double_nan(RespType::Double(f64::NAN), ",nan\r\n")]
+    #[case::double_large(RespType::Double(1.5e300), ",1.5e+300\r\n")]
+    #[case::double_plain_limit(RespType::Double(1e16), ",1e+16\r\n")]
+    #[case::double_plain(RespType::Double(1e15), ",1000000000000000\r\n")]
+    #[case::double_small(RespType::Double(-2.5e-7), ",-2.5e-7\r\n")]
+    #[case::double_zero(RespType::Double(0.0), ",0\r\n")]
+    // Booleans
+    #[case::boolean_true(RespType::Boolean(true), "#t\r\n")]
+    #[case::boolean_false(RespType::Boolean(false), "#f\r\n")]
+    // Verbatim strings
+    #[case::verbatim_string(
+        RespType::VerbatimString("txt".into(), "Some string".into()),
+        "=15\r\ntxt:Some string\r\n"
+    )]
+    #[case::verbatim_string_empty(RespType::VerbatimString("mkd".into(), "".into()), "=4\r\nmkd:\r\n")]
+    // Big numbers
+    #[case::big_number(
+        RespType::BigNumber("-3492890328409238509324850943850943825024385".into()),
+        "(-3492890328409238509324850943850943825024385\r\n"
+    )]
     // Null
     #[case::null(RespType::Null(), "_\r\n")]
+    // Replies
+    #[case::replies(
+        RespType::Replies(vec![RespType::Integer(1), RespType::SimpleString("OK".into())]),
+        ":1\r\n+OK\r\n"
+    )]
+    #[case::replies_empty(RespType::Replies(vec![]), "")]
     /// Tests the RESP serialization.
     fn test_serialize(#[case] message: RespType, #[case] expected: String) {
         assert_eq!(expected, message.serialize());
     }
+
+    #[rstest]
+    #[case::simple_string(RespType::SimpleString("OK".into()), "+OK\r\n")]
+    #[case::simple_error(RespType::SimpleError("ERR".into()), "-ERR\r\n")]
+    #[case::bulk_string(RespType::BulkString(Some("Test".into())), "$4\r\nTest\r\n")]
+    #[case::bulk_string_null(RespType::BulkString(None), "$-1\r\n")]
+    #[case::integer(RespType::Integer(-12), ":-12\r\n")]
+    #[case::array(
+        RespType::Array(vec![RespType::Integer(1), RespType::BulkString(None)]),
+        "*2\r\n:1\r\n$-1\r\n"
+    )]
+    #[case::map(
+        RespType::Map(vec![(RespType::SimpleString("Key".into()), RespType::Null())]),
+        "%1\r\n+Key\r\n_\r\n"
+    )]
+    #[case::double(RespType::Double(1.5), ",1.5\r\n")]
+    #[case::boolean(RespType::Boolean(true), "#t\r\n")]
+    #[case::null(RespType::Null(), "_\r\n")]
+    #[case::replies(
+        RespType::Replies(vec![RespType::Integer(1), RespType::Integer(2)]),
+        ":1\r\n:2\r\n"
+    )]
+    /// Tests that serializing into a buffer appends the same bytes as the string serialization.
+    fn test_serialize_into(#[case] message: RespType, #[case] expected: &str) {
+        let mut buf = BytesMut::from("prefix");
+        message.serialize_into(&mut buf);
+        assert_eq!([b"prefix".as_slice(), &message.serialize()].concat(), buf);
+        assert_eq!(format!("prefix{expected}"), buf);
+    }
+
+    #[rstest]
+    #[case::null_v2(RespType::Null(), crate::state::ProtocolVersion::V2, "$-1\r\n")]
+    #[case::null_v3(RespType::Null(), crate::state::ProtocolVersion::V3, "_\r\n")]
+    #[case::double_v2(
+        RespType::Double(1.5),
+        crate::state::ProtocolVersion::V2,
+        "$3\r\n1.5\r\n"
+    )]
+    #[case::double_large_v2(
+        RespType::Double(1e20),
+        crate::state::ProtocolVersion::V2,
+        "$5\r\n1e+20\r\n"
+    )]
+    #[case::boolean_v2(RespType::Boolean(true), crate::state::ProtocolVersion::V2, ":1\r\n")]
+    #[case::boolean_false_v2(RespType::Boolean(false), crate::state::ProtocolVersion::V2, ":0\r\n")]
+    #[case::boolean_v3(RespType::Boolean(false), crate::state::ProtocolVersion::V3, "#f\r\n")]
+    #[case::push_v2(
+        RespType::Push(vec![RespType::Integer(1), RespType::Null()]),
+        crate::state::ProtocolVersion::V2,
+        "*2\r\n:1\r\n$-1\r\n"
+    )]
+    #[case::push_v3(
+        RespType::Push(vec![RespType::Integer(1), RespType::Null()]),
+        crate::state::ProtocolVersion::V3,
+        ">2\r\n:1\r\n_\r\n"
+    )]
+    #[case::verbatim_string_v2(
+        RespType::VerbatimString("txt".into(), "a\r\nb".into()),
+        crate::state::ProtocolVersion::V2,
+        "$4\r\na\r\nb\r\n"
+    )]
+    #[case::verbatim_string_v3(
+        RespType::VerbatimString("txt".into(), "a\r\nb".into()),
+        crate::state::ProtocolVersion::V3,
+        "=8\r\ntxt:a\r\nb\r\n"
+    )]
+    #[case::big_number_v2(
+        RespType::BigNumber("123456789012345678901".into()),
+        crate::state::ProtocolVersion::V2,
+        "$21\r\n123456789012345678901\r\n"
+    )]
+    #[case::big_number_v3(
+        RespType::BigNumber("123456789012345678901".into()),
+        crate::state::ProtocolVersion::V3,
+        "(123456789012345678901\r\n"
+    )]
+    #[case::map_v2(
+        RespType::Map(vec![(RespType::Integer(1), RespType::Boolean(false))]),
+        crate::state::ProtocolVersion::V2,
+        "*2\r\n:1\r\n:0\r\n"
+    )]
+    #[case::map_v3(
+        RespType::Map(vec![(RespType::Integer(1), RespType::Boolean(false))]),
+        crate::state::ProtocolVersion::V3,
+        "%1\r\n:1\r\n#f\r\n"
+    )]
+    #[case::set_v2(
+        RespType::Set(vec![RespType::Integer(1), RespType::Null()]),
+        crate::state::ProtocolVersion::V2,
+        "*2\r\n:1\r\n$-1\r\n"
+    )]
+    #[case::set_v3(
+        RespType::Set(vec![RespType::Integer(1), RespType::Null()]),
+        crate::state::ProtocolVersion::V3,
+        "~2\r\n:1\r\n_\r\n"
+    )]
+    /// Tests that serializing into a buffer with a version appends the same bytes as the string
+    /// serialization.
+    fn test_serialize_with_version_into(
+        #[case] message: RespType,
+        #[case] version: crate::state::ProtocolVersion,
+        #[case] expected: &str,
+    ) {
+        let mut buf = BytesMut::from("prefix");
+        message.serialize_with_version_into(&version, &mut buf);
+        assert_eq!(
+            [
+                b"prefix".as_slice(),
+                &message.serialize_with_version(&version)
+            ]
+            .concat(),
+            buf
+        );
+        assert_eq!(format!("prefix{expected}"), buf);
+    }
+
+    #[rstest]
+    #[case::double(RespType::Double(1.5))]
+    #[case::double_negative(RespType::Double(-0.25))]
+    #[case::double_inf(RespType::Double(f64::INFINITY))]
+    #[case::double_negative_inf(RespType::Double(f64::NEG_INFINITY))]
+    #[case::double_large(RespType::Double(1.7976931348623157e308))]
+    #[case::double_small(RespType::Double(5e-324))]
+    #[case::double_precise(RespType::Double(0.1 + 0.2))]
+    #[case::boolean_true(RespType::Boolean(true))]
+    #[case::boolean_false(RespType::Boolean(false))]
+    #[case::map(RespType::Map(vec![(RespType::Double(1.5), RespType::Boolean(true))]))]
+    #[case::set(RespType::Set(vec![RespType::BulkString(Some("a".into())), RespType::Integer(1)]))]
+    #[case::verbatim_string(RespType::VerbatimString("txt".into(), "a:b\r\nc".into()))]
+    #[case::big_number(RespType::BigNumber("-98765432109876543210".into()))]
+    #[case::push(RespType::Push(vec![RespType::BulkString(Some("a".into())), RespType::Integer(1)]))]
+    #[case::array_nested(RespType::Array(vec![
+        RespType::BulkString(Some("key".into())),
+        RespType::Array(vec![RespType::Integer(1), RespType::Array(vec![])]),
+    ]))]
+    #[case::bulk_string_binary(RespType::BulkString(Some(Bytes::from_static(b"\xff\x00\xfe"))))]
+    /// Tests that serializing then parsing returns the original message.
+    fn test_round_trip(#[case] message: RespType) {
+        let result = RespType::from_bytes(&mut BytesMut::from(&message.serialize()[..])).unwrap();
+        assert_eq!(message, result);
+    }
+
+    #[rstest]
+    fn test_round_trip_binary() {
+        let message = RespType::BulkString(Some(Bytes::from_static(b"\xff\x00\r\n\xfe")));
+        let mut buf = BytesMut::new();
+        message.serialize_into(&mut buf);
+        assert_eq!(b"$5\r\n\xff\x00\r\n\xfe\r\n".as_slice(), buf);
+        assert_eq!(message, RespType::from_bytes(&mut buf).unwrap());
+    }
+
+    #[rstest]
+    fn test_round_trip_nan() {
+        let message = RespType::Double(f64::NAN);
+        match RespType::from_bytes(&mut BytesMut::from(&message.serialize()[..])).unwrap() {
+            RespType::Double(num) => assert!(num.is_nan()),
+            result => panic!("Expected a double, got {:?}.", result),
+        }
+    }
+
+    #[rstest]
+    #[case::null_v2(RespType::Null(), crate::state::ProtocolVersion::V2, "$-1\r\n")]
+    #[case::null_v3(RespType::Null(), crate::state::ProtocolVersion::V3, "_\r\n")]
+    #[case::bulk_string_null_v2(
+        RespType::BulkString(None),
+        crate::state::ProtocolVersion::V2,
+        "$-1\r\n"
+    )]
+    #[case::bulk_string_null_v3(
+        RespType::BulkString(None),
+        crate::state::ProtocolVersion::V3,
+        "_\r\n"
+    )]
+    #[case::bulk_string_v2(RespType::BulkString(Some("Test".into())), crate::state::ProtocolVersion::V2, "$4\r\nTest\r\n")]
+    #[case::array_with_null_v2(
+        RespType::Array(vec![RespType::Null(), RespType::Integer(1)]),
+        crate::state::ProtocolVersion::V2,
+        "*2\r\n$-1\r\n:1\r\n"
+    )]
+    #[case::array_with_null_v3(
+        RespType::Array(vec![RespType::BulkString(None), RespType::Integer(1)]),
+        crate::state::ProtocolVersion::V3,
+        "*2\r\n_\r\n:1\r\n"
+    )]
+    #[case::double_v2(
+        RespType::Double(1.5),
+        crate::state::ProtocolVersion::V2,
+        "$3\r\n1.5\r\n"
+    )]
+    #[case::double_v3(RespType::Double(1.5), crate::state::ProtocolVersion::V3, ",1.5\r\n")]
+    #[case::boolean_v2(RespType::Boolean(true), crate::state::ProtocolVersion::V2, ":1\r\n")]
+    #[case::boolean_v3(RespType::Boolean(false), crate::state::ProtocolVersion::V3, "#f\r\n")]
+    #[case::map_v2(
+        RespType::Map(vec![(RespType::SimpleString("Key".into()), RespType::Null())]),
+        crate::state::ProtocolVersion::V2,
+        "*2\r\n+Key\r\n$-1\r\n"
+    )]
+    #[case::map_v3(
+        RespType::Map(vec![(RespType::SimpleString("Key".into()), RespType::Null())]),
+        crate::state::ProtocolVersion::V3,
+        "%1\r\n+Key\r\n_\r\n"
+    )]
+    #[case::replies_v2(
+        RespType::Replies(vec![RespType::Null(), RespType::Boolean(true)]),
+        crate::state::ProtocolVersion::V2,
+        "$-1\r\n:1\r\n"
+    )]
+    /// Tests the protocol version aware RESP serialization.
+    fn test_serialize_with_version(
+        #[case] message: RespType,
+        #[case] version: crate::state::ProtocolVersion,
+        #[case] expected: String,
+    ) {
+        assert_eq!(expected, message.serialize_with_version(&version));
+    }
 }
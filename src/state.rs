@@ -1,8 +1,17 @@
 //! This module contains the state for each session.
 use anyhow::Result;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The id to assign to the next client. Ids start at 1, as in Redis.
+static NEXT_CLIENT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Gets a unique id for a new client.
+pub fn next_client_id() -> u64 {
+    NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed)
+}
 
 /// The protocol version to use for the current connection.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum ProtocolVersion {
     V2,
     V3,
@@ -29,18 +38,55 @@ impl ProtocolVersion {
 #[derive(Debug, PartialEq)]
 pub struct State {
     pub protocol_version: ProtocolVersion,
-    pub client_id: usize,
+    pub client_id: u64,
+    pub name: Option<String>,
+    /// The index of the selected database.
+    pub db: usize,
+    /// The channels the connection is subscribed to.
+    pub channels: std::collections::BTreeSet<String>,
+    /// Whether the connection should be closed once the current reply is written.
+    pub close_after_reply: bool,
+    /// Whether the connection can run commands other than those authenticating it.
+    pub authenticated: bool,
+    /// The name of the user the connection is authenticated as.
+    pub user: String,
+    /// The lowercase name of the last command the connection ran.
+    pub last_command: Option<String>,
+    /// The address of the client's end of the connection, if known.
+    pub addr: Option<String>,
+    /// Whether the connection streams the commands run by every connection, after MONITOR.
+    pub monitoring: bool,
 }
 
 impl State {
     /// Creates a new state.
-    pub fn new(client_id: usize) -> Self {
+    pub fn new(client_id: u64) -> Self {
         Self {
             protocol_version: ProtocolVersion::V2,
             client_id,
+            name: None,
+            db: 0,
+            channels: std::collections::BTreeSet::new(),
+            close_after_reply: false,
+            // The default user has no password, so connections start authenticated.
+            authenticated: true,
+            user: crate::acl::DEFAULT_USER.into(),
+            last_command: None,
+            addr: None,
+            monitoring: false,
         }
     }
 
+    /// Whether the connection is in subscribe mode, having subscribed to at least one channel.
+    pub fn is_subscribed(&self) -> bool {
+        !self.channels.is_empty()
+    }
+
+    /// Sets the connection name, where an empty name clears it.
+    pub fn set_name(&mut self, name: String) {
+        self.name = if name.is_empty() { None } else { Some(name) };
+    }
+
     /// Updates the protocol version from a string.
     pub fn update_version_from_string<T: AsRef<str>>(&mut self, version: T) -> Result<()> {
         let version = ProtocolVersion::from_string(version)?;
@@ -55,6 +101,14 @@ mod tests {
     use rstest::rstest;
 
     // --- Tests ---
+    #[rstest]
+    fn test_next_client_id() {
+        let first = next_client_id();
+        let second = next_client_id();
+        assert!(first > 0);
+        assert!(second > first);
+    }
+
     mod protocol_version {
         use super::*;
         #[rstest]
@@ -97,16 +151,25 @@ mod tests {
                 State::new(0),
                 State {
                     protocol_version: ProtocolVersion::V2,
-                    client_id: 0
+                    client_id: 0,
+                    name: None,
+                    db: 0,
+                    channels: std::collections::BTreeSet::new(),
+                    close_after_reply: false,
+                    authenticated: true,
+                    user: "default".into(),
+                    last_command: None,
+                    addr: None,
+                    monitoring: false
                 }
             );
         }
 
         #[rstest]
-        #[case::v2_str("2", State{ protocol_version: ProtocolVersion::V2, client_id: 0 })]
-        #[case::v3_str("3", State{ protocol_version: ProtocolVersion::V3, client_id: 0 })]
-        #[case::v2_string("2".to_string(), State{ protocol_version: ProtocolVersion::V2, client_id: 0 })]
-        #[case::v3_string("3".to_string(), State{ protocol_version: ProtocolVersion::V3, client_id: 0 })]
+        #[case::v2_str("2", State{ protocol_version: ProtocolVersion::V2, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false })]
+        #[case::v3_str("3", State{ protocol_version: ProtocolVersion::V3, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false })]
+        #[case::v2_string("2".to_string(), State{ protocol_version: ProtocolVersion::V2, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false })]
+        #[case::v3_string("3".to_string(), State{ protocol_version: ProtocolVersion::V3, client_id: 0, name: None, db: 0, channels: std::collections::BTreeSet::new(), close_after_reply: false, authenticated: true, user: "default".into(), last_command: None, addr: None, monitoring: false })]
         fn test_update_protocol_version_from_string<T: AsRef<str>>(
             #[case] input: T,
             #[case] expected: State,
@@ -117,6 +180,24 @@ mod tests {
             assert_eq!(expected, state);
         }
 
+        #[rstest]
+        #[case::name("name", Some("name"))]
+        #[case::empty("", None)]
+        fn test_set_name(#[case] name: &str, #[case] expected: Option<&str>) {
+            let mut state = State::new(0);
+            state.name = Some("old".into());
+            state.set_name(name.into());
+            assert_eq!(expected.map(String::from), state.name);
+        }
+
+        #[rstest]
+        fn test_is_subscribed() {
+            let mut state = State::new(0);
+            assert!(!state.is_subscribed());
+            state.channels.insert("channel".into());
+            assert!(state.is_subscribed());
+        }
+
         #[rstest]
         #[case::invalid_str("3a", "Invalid protocol version: 3a")]
         #[case::invalid_string("3a".to_string(), "Invalid protocol version: 3a")]
@@ -0,0 +1,208 @@
+//! This module contains the server statistics.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// The statistics of a command, as listed by INFO commandstats.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CommandStats {
+    pub calls: u64,
+    /// The total time spent running the command, in microseconds.
+    pub usec: u64,
+    /// The number of calls that replied with an error.
+    pub failed_calls: u64,
+}
+
+/// The counters behind a command's statistics.
+#[derive(Debug, Default)]
+struct CommandCounters {
+    calls: AtomicU64,
+    usec: AtomicU64,
+    failed_calls: AtomicU64,
+}
+
+impl CommandCounters {
+    /// Records a call that ran for the duration.
+    fn record(&self, duration: std::time::Duration, failed: bool) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.usec
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        if failed {
+            self.failed_calls.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Gets the current statistics.
+    fn load(&self) -> CommandStats {
+        CommandStats {
+            calls: self.calls.load(Ordering::Relaxed),
+            usec: self.usec.load(Ordering::Relaxed),
+            failed_calls: self.failed_calls.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The server statistics. Counters are atomics, with a write lock only taken on the first call of a command.
+#[derive(Debug, Default)]
+pub struct Stats {
+    total_connections_received: AtomicU64,
+    total_commands_processed: AtomicU64,
+    /// The statistics per lowercase command name.
+    commands: std::sync::RwLock<HashMap<String, CommandCounters>>,
+}
+
+impl Stats {
+    /// Records an accepted connection.
+    pub fn record_connection(&self) {
+        self.total_connections_received
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a call to the command that ran for the duration, and whether it replied with an
+    /// error.
+    pub fn record_command(&self, name: &str, duration: std::time::Duration, failed: bool) {
+        self.total_commands_processed
+            .fetch_add(1, Ordering::Relaxed);
+
+        let name = name.to_lowercase();
+        if let Some(counters) = self.commands.read().unwrap().get(&name) {
+            counters.record(duration, failed);
+            return;
+        }
+        self.commands
+            .write()
+            .unwrap()
+            .entry(name)
+            .or_default()
+            .record(duration, failed);
+    }
+
+    /// Resets every statistic, as done by CONFIG RESETSTAT.
+    pub fn reset(&self) {
+        let mut commands = self.commands.write().unwrap();
+        commands.clear();
+        self.total_connections_received.store(0, Ordering::Relaxed);
+        self.total_commands_processed.store(0, Ordering::Relaxed);
+    }
+
+    /// Gets the number of accepted connections.
+    pub fn total_connections_received(&self) -> u64 {
+        self.total_connections_received.load(Ordering::Relaxed)
+    }
+
+    /// Gets the number of commands processed.
+    pub fn total_commands_processed(&self) -> u64 {
+        self.total_commands_processed.load(Ordering::Relaxed)
+    }
+
+    /// Gets the statistics of every called command, sorted by the command name.
+    pub fn all_command_stats(&self) -> Vec<(String, CommandStats)> {
+        let mut stats = self
+            .commands
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, counters)| (name.clone(), counters.load()))
+            .collect::<Vec<_>>();
+        stats.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        stats
+    }
+}
+
+pub type SharedStats = Arc<Stats>;
+
+/// Creates new shared statistics with every counter at zero.
+pub fn new() -> SharedStats {
+    Arc::new(Stats::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn stats() -> SharedStats {
+        new()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_new(stats: SharedStats) {
+        assert_eq!(0, stats.total_connections_received());
+        assert_eq!(0, stats.total_commands_processed());
+        assert_eq!(
+            Vec::<(String, CommandStats)>::new(),
+            stats.all_command_stats()
+        );
+    }
+
+    #[rstest]
+    fn test_record_connection(stats: SharedStats) {
+        stats.record_connection();
+        stats.record_connection();
+        assert_eq!(2, stats.total_connections_received());
+    }
+
+    #[rstest]
+    fn test_record_command(stats: SharedStats) {
+        stats.record_command("GET", std::time::Duration::from_micros(10), false);
+        stats.record_command("get", std::time::Duration::from_micros(5), true);
+        stats.record_command("SET", std::time::Duration::from_millis(1), false);
+        assert_eq!(3, stats.total_commands_processed());
+        assert_eq!(
+            vec![
+                (
+                    "get".to_string(),
+                    CommandStats {
+                        calls: 2,
+                        usec: 15,
+                        failed_calls: 1
+                    }
+                ),
+                (
+                    "set".to_string(),
+                    CommandStats {
+                        calls: 1,
+                        usec: 1000,
+                        failed_calls: 0
+                    }
+                ),
+            ],
+            stats.all_command_stats()
+        );
+    }
+
+    #[rstest]
+    fn test_reset(stats: SharedStats) {
+        stats.record_connection();
+        stats.record_command("get", std::time::Duration::from_micros(10), false);
+        stats.reset();
+        assert_eq!(0, stats.total_connections_received());
+        assert_eq!(0, stats.total_commands_processed());
+        assert_eq!(
+            Vec::<(String, CommandStats)>::new(),
+            stats.all_command_stats()
+        );
+    }
+
+    #[rstest]
+    fn test_record_command_concurrently(stats: SharedStats) {
+        let threads = (0..4)
+            .map(|_| {
+                let stats = stats.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        stats.record_command("get", std::time::Duration::ZERO, false);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(400, stats.total_commands_processed());
+        assert_eq!(400, stats.all_command_stats()[0].1.calls);
+    }
+}
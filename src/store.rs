@@ -1,30 +1,125 @@
 //! This module contains the Redis store.
-use std::collections::HashMap;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::RwLock;
+
+// --- Access tracking ---
+/// A logical clock incremented on every key access.
+static ACCESS_CLOCK: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug)]
+/// When an entry was last accessed.
+///
+/// The access is recorded both as a logical time, used to order accesses, and as an instant, used
+/// to report how long the entry has been idle. Both can be updated while only holding a read guard
+/// on the store.
+pub struct LastAccess {
+    clock: AtomicU64,
+    time: std::sync::Mutex<tokio::time::Instant>,
+}
+
+impl LastAccess {
+    /// Creates an access time marked as the most recent access.
+    pub fn now() -> Self {
+        Self {
+            clock: AtomicU64::new(ACCESS_CLOCK.fetch_add(1, Ordering::Relaxed) + 1),
+            time: std::sync::Mutex::new(tokio::time::Instant::now()),
+        }
+    }
+
+    /// Marks the access time as the most recent access.
+    pub fn touch(&self) {
+        let clock = ACCESS_CLOCK.fetch_add(1, Ordering::Relaxed) + 1;
+        self.clock.store(clock, Ordering::Relaxed);
+        *self.time.lock().expect("Access time lock is poisoned.") = tokio::time::Instant::now();
+    }
+
+    /// Gets the logical access time.
+    pub fn get(&self) -> u64 {
+        self.clock.load(Ordering::Relaxed)
+    }
+
+    /// Gets the time elapsed since the last access.
+    pub fn idle_time(&self) -> tokio::time::Duration {
+        let time = *self.time.lock().expect("Access time lock is poisoned.");
+        tokio::time::Instant::now().saturating_duration_since(time)
+    }
+}
+
+impl Clone for LastAccess {
+    fn clone(&self) -> Self {
+        Self {
+            clock: AtomicU64::new(self.get()),
+            time: std::sync::Mutex::new(*self.time.lock().expect("Access time lock is poisoned.")),
+        }
+    }
+}
 
 // --- Store entry ---
 #[derive(PartialEq, Debug, Clone)]
 /// An entry value.
 pub enum EntryValue {
+    Hash(HashMap<String, String>),
     List(Vec<String>),
-    String(String),
+    Set(HashSet<String>),
+    /// The bytes of a string, which need not be valid UTF-8.
+    String(bytes::Bytes),
 }
 
-#[derive(PartialEq, Debug, Clone)]
+impl EntryValue {
+    /// Gets the name of the value's type.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            EntryValue::Hash(_) => "hash",
+            EntryValue::List(_) => "list",
+            EntryValue::Set(_) => "set",
+            EntryValue::String(_) => "string",
+        }
+    }
+
+    /// Gets the effort of dropping the value, which is its number of elements, or 1 for a string.
+    pub fn free_effort(&self) -> usize {
+        match self {
+            EntryValue::Hash(hash) => hash.len(),
+            EntryValue::List(list) => list.len(),
+            EntryValue::Set(set) => set.len(),
+            EntryValue::String(_) => 1,
+        }
+    }
+}
+
+/// The number of keys sampled to pick each key evicted, as Redis's default `maxmemory-samples`.
+const EVICTION_SAMPLES: usize = 5;
+
+/// The number of keys sampled by RANDOMKEY before falling back to collecting the keys that have
+/// not expired.
+const RANDOM_KEY_SAMPLES: usize = 100;
+
+#[derive(Debug, Clone)]
 /// An entry in the Redis store.
 pub struct Entry {
     pub value: EntryValue,
     pub deletion_time: Option<tokio::time::Instant>,
+    pub last_access: LastAccess,
+}
+
+impl PartialEq for Entry {
+    /// Entries are equal if their values and deletion times are, regardless of their accesses.
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.deletion_time == other.deletion_time
+    }
 }
 
 impl Entry {
     /// Creates a new Redis entry for a string.
-    pub fn new_string<T: Into<String>>(value: T) -> Self {
+    pub fn new_string<T: Into<bytes::Bytes>>(value: T) -> Self {
         let value = EntryValue::String(value.into());
         Self {
             value,
             deletion_time: None,
+            last_access: LastAccess::now(),
         }
     }
 
@@ -34,9 +129,59 @@ impl Entry {
         Self {
             value,
             deletion_time: None,
+            last_access: LastAccess::now(),
         }
     }
 
+    /// Creates a new Redis entry for a hash.
+    pub fn new_hash() -> Self {
+        let value = EntryValue::Hash(HashMap::new());
+        Self {
+            value,
+            deletion_time: None,
+            last_access: LastAccess::now(),
+        }
+    }
+
+    /// Creates a new Redis entry for a set.
+    pub fn new_set() -> Self {
+        let value = EntryValue::Set(HashSet::new());
+        Self {
+            value,
+            deletion_time: None,
+            last_access: LastAccess::now(),
+        }
+    }
+
+    /// Estimates the number of bytes used by the entry and its key.
+    pub fn approximate_size(&self, key: &str) -> u64 {
+        let value_size = match &self.value {
+            EntryValue::Hash(hash) => hash
+                .iter()
+                .map(|(field, value)| field.len() + value.len())
+                .sum(),
+            EntryValue::List(list) => list.iter().map(|element| element.len()).sum(),
+            EntryValue::Set(set) => set.iter().map(|member| member.len()).sum(),
+            EntryValue::String(value) => value.len(),
+        };
+        (key.len() + value_size) as u64
+    }
+
+    /// Estimates the number of bytes of memory used by the entry and its key, as reported by
+    /// MEMORY USAGE. On top of the approximate size, this counts the entry itself and the header
+    /// of every string it holds.
+    pub fn memory_usage(&self, key: &str) -> u64 {
+        let strings = match &self.value {
+            EntryValue::Hash(hash) => 2 * hash.len(),
+            EntryValue::List(list) => list.len(),
+            EntryValue::Set(set) => set.len(),
+            EntryValue::String(_) => 0,
+        };
+        // The key is a string too.
+        let overhead = std::mem::size_of::<Entry>() + (strings + 1) * std::mem::size_of::<String>();
+        self.approximate_size(key) + overhead as u64
+    }
+
     /// Adds a deletion timer to the entry.
     pub fn with_deletion<T: Into<u64>>(mut self, delete_timer_duration_ms: T) -> Self {
         let delete_timer_duration_ms = delete_timer_duration_ms.into();
@@ -45,66 +190,577 @@ impl Entry {
         self.deletion_time = Some(deletion_time);
         self
     }
+
+    /// Gets the Unix time in milliseconds at which the entry will be deleted.
+    ///
+    /// The deletion time is a monotonic instant, so the wall-clock time is derived by adding the
+    /// time remaining until the deletion to the current system time.
+    pub fn deletion_unix_time_ms(&self) -> Option<i64> {
+        let deletion_time = self.deletion_time?;
+        let remaining = deletion_time.saturating_duration_since(tokio::time::Instant::now());
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Some((now + remaining).as_millis() as i64)
+    }
+}
+
+// --- Eviction policy ---
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+/// How keys are evicted once the store exceeds its memory budget.
+pub enum EvictionPolicy {
+    #[default]
+    NoEviction,
+    AllkeysLru,
+}
+
+impl EvictionPolicy {
+    pub fn from_string<T: AsRef<str>>(policy: T) -> Result<Self> {
+        match policy.as_ref().to_lowercase().as_str() {
+            "noeviction" => Ok(EvictionPolicy::NoEviction),
+            "allkeys-lru" => Ok(EvictionPolicy::AllkeysLru),
+            x => Err(anyhow::anyhow!("Invalid eviction policy: {}", x)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EvictionPolicy::NoEviction => "noeviction",
+            EvictionPolicy::AllkeysLru => "allkeys-lru",
+        }
+    }
+}
+
+// --- Memory budget ---
+#[derive(Debug, Default)]
+/// The memory budget shared by the logical databases, along with the memory they use.
+pub struct MemoryBudget {
+    /// The approximate number of bytes used by the entries of every database, expired or not.
+    used_memory: AtomicU64,
+    /// The approximate memory budget in bytes, 0 for no limit.
+    maxmemory: AtomicU64,
+    eviction_policy: std::sync::Mutex<EvictionPolicy>,
+}
+
+impl MemoryBudget {
+    /// Sets the memory budget and how keys are evicted when it is exceeded.
+    pub fn set(&self, maxmemory: u64, eviction_policy: EvictionPolicy) {
+        self.maxmemory.store(maxmemory, Ordering::Relaxed);
+        *self
+            .eviction_policy
+            .lock()
+            .expect("Eviction policy lock is poisoned.") = eviction_policy;
+    }
+
+    /// Gets how keys are evicted when the budget is exceeded.
+    pub fn eviction_policy(&self) -> EvictionPolicy {
+        *self
+            .eviction_policy
+            .lock()
+            .expect("Eviction policy lock is poisoned.")
+    }
+
+    /// Gets the approximate number of bytes used by the entries of every database.
+    pub fn used_memory(&self) -> u64 {
+        self.used_memory.load(Ordering::Relaxed)
+    }
+
+    /// Whether the databases use more memory than the budget.
+    pub fn is_exceeded(&self) -> bool {
+        let maxmemory = self.maxmemory.load(Ordering::Relaxed);
+        maxmemory > 0 && self.used_memory() > maxmemory
+    }
+}
+
+impl PartialEq for MemoryBudget {
+    fn eq(&self, other: &Self) -> bool {
+        self.used_memory() == other.used_memory()
+            && self.maxmemory.load(Ordering::Relaxed) == other.maxmemory.load(Ordering::Relaxed)
+            && self.eviction_policy() == other.eviction_policy()
+    }
 }
 
 // --- Redis store ---
 #[derive(Debug, PartialEq)]
 /// The Redis store.
 pub struct Store {
-    store: HashMap<String, Entry>,
+    /// The entries, indexed so that random keys can be sampled.
+    store: indexmap::IndexMap<String, Entry>,
+    /// The keys ordered by their position in the order of SCAN.
+    scan_index: std::collections::BTreeSet<(u64, String)>,
+    /// The keys with a deletion time, indexed so that they can be sampled by active expiration.
+    expires: indexmap::IndexSet<String>,
+    /// The approximate number of bytes used by the entries, expired or not.
+    used_memory: u64,
+    /// The budget shared with the other databases, which counts the memory used by this store too.
+    budget: Arc<MemoryBudget>,
 }
 
 impl Store {
+    /// Creates a store with a memory budget of its own.
+    #[cfg(test)]
     pub fn new() -> Self {
+        Self::with_budget(Arc::default())
+    }
+
+    /// Creates a store counting its memory against the budget.
+    fn with_budget(budget: Arc<MemoryBudget>) -> Self {
         Self {
-            store: HashMap::new(),
+            store: indexmap::IndexMap::new(),
+            scan_index: std::collections::BTreeSet::new(),
+            expires: indexmap::IndexSet::new(),
+            used_memory: 0,
+            budget,
+        }
+    }
+
+    /// Removes every entry, keeping the eviction settings.
+    pub fn clear(&mut self) {
+        self.store.clear();
+        self.scan_index.clear();
+        self.expires.clear();
+        self.release(self.used_memory);
+    }
+
+    /// Sets the memory budget and how keys are evicted when it is exceeded, for every database
+    /// sharing the store's budget.
+    pub fn set_eviction(&self, maxmemory: u64, eviction_policy: EvictionPolicy) {
+        self.budget.set(maxmemory, eviction_policy);
+    }
+
+    /// Counts the bytes taken by new entries or values, in the store and in its budget.
+    fn take(&mut self, bytes: u64) {
+        self.used_memory += bytes;
+        self.budget.used_memory.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Counts the bytes freed by removed entries or values, in the store and in its budget.
+    fn release(&mut self, bytes: u64) {
+        let bytes = bytes.min(self.used_memory);
+        self.used_memory -= bytes;
+        self.budget.used_memory.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// Gets the approximate number of bytes used by the entries, including the expired entries
+    /// that have not been removed yet.
+    ///
+    /// The usage is kept up to date as entries are inserted and removed, and as their values are
+    /// changed in place, which is reported with `track_resize`.
+    #[cfg(test)]
+    pub fn used_memory(&self) -> u64 {
+        self.used_memory
+    }
+
+    /// Estimates the number of bytes used by the entries by walking them, to check the usage kept
+    /// up to date against.
+    #[cfg(test)]
+    pub fn recount_memory(&self) -> u64 {
+        self.store
+            .iter()
+            .map(|(key, entry)| entry.approximate_size(key))
+            .sum()
+    }
+
+    /// Records that values were changed in place through a mutable reference, freeing and taking
+    /// the numbers of bytes counted by `Entry::approximate_size`.
+    pub fn track_resize(&mut self, freed: usize, taken: usize) {
+        self.take(taken as u64);
+        self.release(freed as u64);
+    }
+
+    /// Whether the databases sharing the store's budget use more memory than it.
+    pub fn is_over_budget(&self) -> bool {
+        self.budget.is_exceeded()
+    }
+
+    /// Samples up to the given number of entries, whether they have expired or not.
+    /// Every entry is returned when there are no more than that, otherwise they are picked at
+    /// random, possibly more than once.
+    fn sample_keys(&self, samples: usize) -> impl Iterator<Item = (&String, &Entry)> {
+        sample_indices(self.store.len(), samples)
+            .into_iter()
+            .filter_map(|index| self.store.get_index(index))
+    }
+
+    /// Samples up to the given number of keys with a deletion time, removing the ones that have
+    /// expired. Returns the number of keys sampled and the number removed.
+    pub fn remove_expired_sample(&mut self, samples: usize) -> (usize, usize) {
+        let keys = sample_indices(self.expires.len(), samples)
+            .into_iter()
+            .filter_map(|index| self.expires.get_index(index).cloned())
+            .collect::<Vec<_>>();
+        let now = tokio::time::Instant::now();
+        let mut expired = 0;
+        for key in keys.iter() {
+            let deletion_time = self.store.get(key).and_then(|entry| entry.deletion_time);
+            if deletion_time.is_some_and(|deletion_time| deletion_time <= now) {
+                self.remove_entry(key);
+                expired += 1;
+            }
         }
+        (keys.len(), expired)
     }
 
     /// Removes an entry from the store if it has expired.
     fn remove_if_expired<T: std::borrow::Borrow<str> + ?Sized>(&mut self, key: &T) {
         let key = key.borrow();
-        match self.store.entry(key.to_string()) {
-            std::collections::hash_map::Entry::Occupied(entry) => {
-                if let Some(deletion_time) = entry.get().deletion_time {
-                    if deletion_time <= tokio::time::Instant::now() {
-                        entry.remove_entry();
-                    }
-                }
-            }
-            _ => (),
+        let expired = match self.store.get(key).and_then(|entry| entry.deletion_time) {
+            Some(deletion_time) => deletion_time <= tokio::time::Instant::now(),
+            None => false,
+        };
+        if expired {
+            self.remove_entry(key);
         }
     }
 
-    /// Gets the given key's entry and removes the entry if it has expired.
-    pub fn entry(&mut self, key: String) -> std::collections::hash_map::Entry<'_, String, Entry> {
+    /// Removes an entry along with its position in the order of SCAN, whether it has expired or
+    /// not.
+    fn remove_entry(&mut self, key: &str) -> Option<Entry> {
+        let entry = self.store.swap_remove(key)?;
+        self.scan_index
+            .remove(&(scan_position(key), key.to_string()));
+        self.expires.swap_remove(key);
+        self.release(entry.approximate_size(key));
+        Some(entry)
+    }
+
+    /// Removes every entry that has expired, by sampling every key with a deletion time.
+    #[cfg(test)]
+    pub fn remove_expired(&mut self) {
+        self.remove_expired_sample(self.expires.len());
+    }
+
+    /// Gets a mutable reference to the key's entry, inserting the value if the key does not exist
+    /// or has expired.
+    pub fn get_or_insert(&mut self, key: String, value: Entry) -> &mut Entry {
         self.remove_if_expired(&key);
-        self.store.entry(key)
+        if !self.store.contains_key(&key) {
+            self.take(value.approximate_size(&key));
+            self.scan_index.insert((scan_position(&key), key.clone()));
+            if value.deletion_time.is_some() {
+                self.expires.insert(key.clone());
+            }
+        }
+        match self.store.entry(key) {
+            indexmap::map::Entry::Occupied(entry) => {
+                entry.get().last_access.touch();
+                entry.into_mut()
+            }
+            indexmap::map::Entry::Vacant(entry) => entry.insert(value),
+        }
     }
 
     /// Inserts a key-value pair irrespective of the key already existing.
     pub fn insert(&mut self, key: String, value: Entry) -> Option<Entry> {
         self.remove_if_expired(&key);
-        self.store.insert(key, value)
+        value.last_access.touch();
+        self.take(value.approximate_size(&key));
+        if value.deletion_time.is_some() {
+            self.expires.insert(key.clone());
+        } else {
+            self.expires.swap_remove(&key);
+        }
+        let position = (scan_position(&key), key.clone());
+        let previous = self.store.insert(key, value);
+        match &previous {
+            Some(previous) => self.release(previous.approximate_size(&position.1)),
+            None => {
+                self.scan_index.insert(position);
+            }
+        }
+        previous
+    }
+
+    /// Gets a mutable reference to the key's entry, removing the entry if it has expired.
+    ///
+    /// Unlike `get`, this needs a write guard, so the entry's value can be read and changed without
+    /// locking the store again. The deletion time is changed with `set_expiry` and `clear_expiry`
+    /// instead, so that the keys with a deletion time stay indexed.
+    pub fn get_entry(&mut self, key: &str) -> Option<&mut Entry> {
+        self.remove_if_expired(key);
+        let entry = self.store.get_mut(key)?;
+        entry.last_access.touch();
+        Some(entry)
+    }
+
+    /// Sets the instant at which the key expires.
+    /// Returns whether the key exists, where an expired key does not.
+    pub fn set_expiry(&mut self, key: &str, deletion_time: tokio::time::Instant) -> bool {
+        match self.get_entry(key) {
+            Some(entry) => {
+                entry.deletion_time = Some(deletion_time);
+                self.expires.insert(key.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the key's expiry so that it persists.
+    /// Returns whether the key existed and had an expiry.
+    pub fn clear_expiry(&mut self, key: &str) -> bool {
+        let cleared = self
+            .get_entry(key)
+            .and_then(|entry| entry.deletion_time.take())
+            .is_some();
+        self.expires.swap_remove(key);
+        cleared
+    }
+
+    /// Removes a key, returning its entry if it had not expired.
+    pub fn remove(&mut self, key: &str) -> Option<Entry> {
+        self.remove_if_expired(key);
+        self.remove_entry(key)
     }
 
-    /// Returns a reference to the value corresponding to the key.
-    pub fn get<T>(&mut self, key: &T) -> Option<&Entry>
+    /// Returns an iterator over the key-value pairs that have not expired.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Entry)> {
+        let now = tokio::time::Instant::now();
+        self.store
+            .iter()
+            .filter(move |(_, entry)| match entry.deletion_time {
+                Some(deletion_time) => deletion_time > now,
+                None => true,
+            })
+    }
+
+    /// Returns the number of keys that have not expired.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns the keys that have not expired among the next `count` keys from the cursor onwards,
+    /// along with the cursor to continue from, which is 0 once the iteration is complete.
+    ///
+    /// Keys are ordered by the hash of their name rather than by insertion, so every key present
+    /// for the whole iteration is returned exactly once, however the store changes between calls.
+    /// Keys sharing a hash are returned together, which may exceed `count`. Each call takes
+    /// O(`count` log N) time, as the keys are kept in that order.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&String, &Entry)>) {
+        let mut batch = vec![];
+        let mut last = None;
+        let keys = self.scan_index.range((cursor, String::new())..);
+        for (visited, (position, key)) in keys.enumerate() {
+            if visited >= count.max(1) && last != Some(*position) {
+                let next_cursor = last.map_or(cursor, |last: u64| last + 1);
+                return (next_cursor, batch);
+            }
+            last = Some(*position);
+            if let Some(entry) = self.peek(key) {
+                batch.push((key, entry));
+            }
+        }
+        (0, batch)
+    }
+
+    /// Returns a uniformly random key that has not expired.
+    ///
+    /// Keys are sampled by their index in the whole store, rejecting the expired ones, so the keys
+    /// are not collected unless too many of the samples have expired.
+    pub fn random_key(&self) -> Option<String> {
+        for _ in 0..RANDOM_KEY_SAMPLES {
+            if self.store.is_empty() {
+                return None;
+            }
+            let (key, entry) = self
+                .store
+                .get_index(rand::random_range(0..self.store.len()))?;
+            let expired = match entry.deletion_time {
+                Some(deletion_time) => deletion_time <= tokio::time::Instant::now(),
+                None => false,
+            };
+            if !expired {
+                return Some(key.clone());
+            }
+        }
+
+        let keys = self.iter().map(|(key, _)| key).collect::<Vec<_>>();
+        if keys.is_empty() {
+            return None;
+        }
+        Some(keys[rand::random_range(0..keys.len())].clone())
+    }
+
+    /// Returns a reference to the value corresponding to the key if it has not expired, without
+    /// marking it as accessed.
+    pub fn peek<T>(&self, key: &T) -> Option<&Entry>
     where
         T: std::hash::Hash + Eq + ?Sized,
-        T: std::borrow::Borrow<str>,
         String: std::borrow::Borrow<T>,
     {
-        self.remove_if_expired(key);
-        self.store.get(key)
+        self.store
+            .get(key)
+            .filter(|entry| match entry.deletion_time {
+                Some(deletion_time) => deletion_time > tokio::time::Instant::now(),
+                None => true,
+            })
+    }
+
+    /// Returns a reference to the value corresponding to the key if it has not expired.
+    ///
+    /// This does not remove an expired entry, so that it can be called with only a read guard.
+    /// Expired entries are instead removed by the mutating methods.
+    pub fn get<T>(&self, key: &T) -> Option<&Entry>
+    where
+        T: std::hash::Hash + Eq + ?Sized,
+        String: std::borrow::Borrow<T>,
+    {
+        let entry = self.peek(key)?;
+        entry.last_access.touch();
+        Some(entry)
     }
 }
 
-pub type SharedStore = Arc<Mutex<Box<Store>>>;
+/// A store shared between connections.
+/// Read-only commands take a read guard so that they can run concurrently.
+pub type SharedStore = Arc<RwLock<Box<Store>>>;
 
-/// Creates a new Redis store.
+/// Creates a new Redis store, with a memory budget of its own.
+#[cfg(test)]
 pub fn new() -> SharedStore {
-    Arc::new(Mutex::new(Box::new(Store::new())))
+    Arc::new(RwLock::new(Box::new(Store::new())))
+}
+
+/// The logical databases, indexed by their number.
+pub type SharedDatabases = Arc<Vec<SharedStore>>;
+
+/// Creates the logical databases, each with a new Redis store, sharing a memory budget.
+pub fn new_databases(count: usize) -> SharedDatabases {
+    let budget = Arc::new(MemoryBudget::default());
+    Arc::new(
+        (0..count)
+            .map(|_| Arc::new(RwLock::new(Box::new(Store::with_budget(budget.clone())))))
+            .collect(),
+    )
+}
+
+/// Frees memory according to the eviction policy if the databases are over their shared memory
+/// budget. Errors if they are over budget and no keys can be evicted.
+///
+/// Each victim is the least recently used of a few keys sampled from every database, as with
+/// Redis's approximated LRU, so that evicting a key does not walk the whole keyspace. The
+/// databases are locked one at a time.
+pub async fn free_memory(databases: &SharedDatabases) -> Result<()> {
+    let oom = || anyhow::anyhow!("OOM command not allowed when used memory > 'maxmemory'.");
+    let Some(first) = databases.first() else {
+        return Ok(());
+    };
+    let budget = first.read().await.budget.clone();
+    while budget.is_exceeded() {
+        if budget.eviction_policy() == EvictionPolicy::NoEviction {
+            return Err(oom());
+        }
+        // The least recent access among the samples, with the database and key accessed.
+        let mut victim: Option<(u64, usize, String)> = None;
+        for (index, store) in databases.iter().enumerate() {
+            let store = store.read().await;
+            for (key, entry) in store.sample_keys(EVICTION_SAMPLES) {
+                let access = entry.last_access.get();
+                if victim
+                    .as_ref()
+                    .is_none_or(|(oldest, _, _)| access < *oldest)
+                {
+                    victim = Some((access, index, key.clone()));
+                }
+            }
+        }
+        let (_, index, key) = victim.ok_or_else(oom)?;
+        log::debug!("Evicting {key} from database {index}.");
+        databases[index].write().await.remove_entry(&key);
+    }
+    Ok(())
+}
+
+// --- Active expiration ---
+/// The interval between active expiration cycles.
+const ACTIVE_EXPIRE_INTERVAL_MS: u64 = 100;
+
+/// The number of keys with a deletion time sampled from a database in each round of a cycle, as
+/// Redis's `ACTIVE_EXPIRE_CYCLE_KEYS_PER_LOOP`.
+const ACTIVE_EXPIRE_SAMPLES: usize = 20;
+
+/// The percentage of sampled keys that must have expired for a database to be sampled again in
+/// the same cycle.
+const ACTIVE_EXPIRE_STALE_PERCENT: usize = 10;
+
+/// The time a cycle may spend removing expired keys, a quarter of the interval between cycles as
+/// in Redis.
+const ACTIVE_EXPIRE_TIME_LIMIT_MS: u64 = ACTIVE_EXPIRE_INTERVAL_MS / 4;
+
+/// Whether expired entries are actively removed in the background.
+/// When disabled, expired entries are only removed when they are next written.
+pub type SharedActiveExpire = Arc<AtomicBool>;
+
+/// Creates the active expiration flag, enabled by default.
+pub fn new_active_expire() -> SharedActiveExpire {
+    Arc::new(AtomicBool::new(true))
+}
+
+/// Removes expired entries from every database if active expiration is enabled.
+///
+/// As in Redis, each database has a few of its keys with a deletion time sampled, and is sampled
+/// again while many of the samples had expired, until the cycle runs out of time. The write guard
+/// is released between samples, so that clients are not blocked for a whole cycle.
+pub async fn active_expire_cycle(databases: &SharedDatabases, active_expire: &SharedActiveExpire) {
+    if !active_expire.load(Ordering::Relaxed) {
+        return;
+    }
+    let start = tokio::time::Instant::now();
+    let time_limit = tokio::time::Duration::from_millis(ACTIVE_EXPIRE_TIME_LIMIT_MS);
+    for store in databases.iter() {
+        loop {
+            let (sampled, expired) = store
+                .write()
+                .await
+                .remove_expired_sample(ACTIVE_EXPIRE_SAMPLES);
+            if start.elapsed() >= time_limit {
+                return;
+            }
+            if sampled == 0 || expired * 100 <= sampled * ACTIVE_EXPIRE_STALE_PERCENT {
+                break;
+            }
+        }
+    }
+}
+
+/// Spawns the task that periodically runs the active expiration cycle.
+/// The duration of each cycle is recorded by the latency monitor as an `expire-cycle` event.
+pub fn spawn_active_expire(
+    databases: SharedDatabases,
+    active_expire: SharedActiveExpire,
+    latency: crate::latency::SharedLatency,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(
+            ACTIVE_EXPIRE_INTERVAL_MS,
+        ));
+        loop {
+            interval.tick().await;
+            let start = tokio::time::Instant::now();
+            active_expire_cycle(&databases, &active_expire).await;
+            latency.record("expire-cycle", start.elapsed());
+        }
+    })
+}
+
+/// Picks up to the given number of indices below the length, every index when there are no more
+/// than that, otherwise at random, possibly more than once.
+fn sample_indices(len: usize, samples: usize) -> Vec<usize> {
+    if len <= samples {
+        (0..len).collect()
+    } else {
+        (0..samples).map(|_| rand::random_range(0..len)).collect()
+    }
+}
+
+/// Gets the position of the key in the order of SCAN, which is stable for the lifetime of the
+/// process.
+fn scan_position(key: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::hash::DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
 }
 
 #[cfg(test)]
@@ -136,6 +792,7 @@ mod tests {
         let expected = Entry {
             value: EntryValue::String(value.into()),
             deletion_time: None,
+            last_access: LastAccess::now(),
         };
         assert_eq!(expected, Entry::new_string(value));
     }
@@ -145,10 +802,31 @@ mod tests {
         let expected = Entry {
             value: EntryValue::List(vec![]),
             deletion_time: None,
+            last_access: LastAccess::now(),
         };
         assert_eq!(expected, Entry::new_list());
     }
 
+    #[rstest]
+    fn test_entry_hash() {
+        let expected = Entry {
+            value: EntryValue::Hash(HashMap::new()),
+            deletion_time: None,
+            last_access: LastAccess::now(),
+        };
+        assert_eq!(expected, Entry::new_hash());
+    }
+
+    #[rstest]
+    fn test_entry_set() {
+        let expected = Entry {
+            value: EntryValue::Set(HashSet::new()),
+            deletion_time: None,
+            last_access: LastAccess::now(),
+        };
+        assert_eq!(expected, Entry::new_set());
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_entry_with_deletion() {
@@ -160,19 +838,229 @@ mod tests {
             deletion_time: Some(
                 tokio::time::Instant::now() + tokio::time::Duration::from_millis(duration),
             ),
+            last_access: LastAccess::now(),
         };
         assert_eq!(expected, Entry::new_string(value).with_deletion(duration));
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_entry_deletion_unix_time_ms() {
+        tokio::time::pause();
+        let duration: u64 = 10_000;
+        let unix_time_ms = || {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as i64
+        };
+        let before = unix_time_ms();
+        let deletion_time = Entry::new_string("value")
+            .with_deletion(duration)
+            .deletion_unix_time_ms()
+            .unwrap();
+        let after = unix_time_ms();
+        assert!((before + duration as i64..=after + duration as i64).contains(&deletion_time));
+    }
+
+    #[rstest]
+    fn test_entry_deletion_unix_time_ms_without_deletion() {
+        assert_eq!(None, Entry::new_string("value").deletion_unix_time_ms());
+    }
+
+    #[rstest]
+    #[case::string(EntryValue::String("value".into()), "string")]
+    #[case::list(EntryValue::List(vec![]), "list")]
+    #[case::hash(EntryValue::Hash(HashMap::new()), "hash")]
+    #[case::set(EntryValue::Set(HashSet::new()), "set")]
+    fn test_entry_value_type_name(#[case] value: EntryValue, #[case] expected: &str) {
+        assert_eq!(expected, value.type_name());
+    }
+
+    #[rstest]
+    #[case::string(EntryValue::String("value".into()), 1)]
+    #[case::list(EntryValue::List(vec!["a".into(), "b".into()]), 2)]
+    #[case::hash(EntryValue::Hash(HashMap::from([("a".into(), "b".into())])), 1)]
+    #[case::set(EntryValue::Set(HashSet::new()), 0)]
+    fn test_entry_value_free_effort(#[case] value: EntryValue, #[case] expected: usize) {
+        assert_eq!(expected, value.free_effort());
+    }
+
+    #[rstest]
+    #[case::string(Entry::new_string("value"), 8)]
+    #[case::empty_list(Entry::new_list(), 3)]
+    #[case::list(Entry { value: EntryValue::List(vec!["a".into(), "bc".into()]), deletion_time: None, last_access: LastAccess::now() }, 6)]
+    #[case::hash(Entry { value: EntryValue::Hash(HashMap::from([("a".into(), "bc".into())])), deletion_time: None, last_access: LastAccess::now() }, 6)]
+    #[case::set(Entry { value: EntryValue::Set(HashSet::from(["a".into(), "bc".into()])), deletion_time: None, last_access: LastAccess::now() }, 6)]
+    fn test_entry_approximate_size(#[case] entry: Entry, #[case] expected: u64) {
+        assert_eq!(expected, entry.approximate_size("key"));
+    }
+
+    #[rstest]
+    #[case::string(Entry::new_string("value"), 0)]
+    #[case::empty_list(Entry::new_list(), 0)]
+    #[case::list(Entry { value: EntryValue::List(vec!["a".into(), "bc".into()]), deletion_time: None, last_access: LastAccess::now() }, 2)]
+    #[case::hash(Entry { value: EntryValue::Hash(HashMap::from([("a".into(), "bc".into())])), deletion_time: None, last_access: LastAccess::now() }, 2)]
+    #[case::set(Entry { value: EntryValue::Set(HashSet::from(["a".into(), "bc".into()])), deletion_time: None, last_access: LastAccess::now() }, 2)]
+    fn test_entry_memory_usage(#[case] entry: Entry, #[case] strings: usize) {
+        let overhead = std::mem::size_of::<Entry>() + (strings + 1) * std::mem::size_of::<String>();
+        assert_eq!(
+            entry.approximate_size("key") + overhead as u64,
+            entry.memory_usage("key")
+        );
+    }
+
+    // ---- Eviction policy ----
+    #[rstest]
+    #[case::noeviction("noeviction", EvictionPolicy::NoEviction)]
+    #[case::allkeys_lru("allkeys-lru", EvictionPolicy::AllkeysLru)]
+    #[case::upper("ALLKEYS-LRU", EvictionPolicy::AllkeysLru)]
+    fn test_eviction_policy_from_string(#[case] policy: &str, #[case] expected: EvictionPolicy) {
+        assert_eq!(expected, EvictionPolicy::from_string(policy).unwrap());
+    }
+
+    #[rstest]
+    fn test_eviction_policy_from_invalid_string() {
+        assert_eq!(
+            "Invalid eviction policy: invalid",
+            EvictionPolicy::from_string("invalid")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[rstest]
+    #[case::noeviction(EvictionPolicy::NoEviction, "noeviction")]
+    #[case::allkeys_lru(EvictionPolicy::AllkeysLru, "allkeys-lru")]
+    fn test_eviction_policy_as_str(#[case] policy: EvictionPolicy, #[case] expected: &str) {
+        assert_eq!(expected, policy.as_str());
+    }
+
     // ---- Store ----
     #[rstest]
     fn test_store_new() {
         let expected = Store {
-            store: std::collections::HashMap::new(),
+            store: indexmap::IndexMap::new(),
+            scan_index: std::collections::BTreeSet::new(),
+            expires: indexmap::IndexSet::new(),
+            used_memory: 0,
+            budget: Arc::new(MemoryBudget::default()),
         };
         assert_eq!(expected, Store::new());
     }
 
+    #[rstest]
+    fn test_store_remove(mut store: Store, key: String, value: Entry) {
+        store.insert(key.clone(), value.clone());
+        assert_eq!(Some(value), store.remove(&key));
+        assert!(store.get(&key).is_none());
+        assert!(store.remove(&key).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_remove_expired(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert(key.clone(), value.with_deletion(duration));
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert!(store.remove(&key).is_none());
+    }
+
+    #[rstest]
+    fn test_store_get_entry(mut store: Store, key: String, value: Entry) {
+        store.insert(key.clone(), value.clone());
+        let before = store.store[&key].last_access.get();
+        let entry = store.get_entry(&key).unwrap();
+        assert_eq!(value, *entry);
+        entry.value = EntryValue::String("new value".into());
+        assert_eq!(
+            Some(&Entry::new_string("new value")),
+            store.peek(key.as_str())
+        );
+        assert!(store.store[&key].last_access.get() > before);
+    }
+
+    #[rstest]
+    fn test_store_get_entry_vacant(mut store: Store, key: String) {
+        assert!(store.get_entry(&key).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_get_entry_expired(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert(key.clone(), value.with_deletion(duration));
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert!(store.get_entry(&key).is_none());
+        assert!(!store.store.contains_key(&key));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_set_expiry(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = tokio::time::Duration::from_millis(10);
+        store.insert(key.clone(), value);
+        assert!(store.set_expiry(&key, tokio::time::Instant::now() + duration));
+
+        tokio::time::advance(duration - tokio::time::Duration::from_millis(1)).await;
+        assert!(store.peek(key.as_str()).is_some());
+        tokio::time::advance(tokio::time::Duration::from_millis(1)).await;
+        assert!(store.peek(key.as_str()).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_set_expiry_expired(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert(key.clone(), value.with_deletion(duration));
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        let deletion_time = tokio::time::Instant::now() + tokio::time::Duration::from_secs(1);
+        assert!(!store.set_expiry(&key, deletion_time));
+        assert!(!store.set_expiry("missing", deletion_time));
+        assert!(store.peek(key.as_str()).is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_clear_expiry(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert(key.clone(), value.clone().with_deletion(duration));
+        assert!(store.clear_expiry(&key));
+        assert!(!store.clear_expiry(&key));
+        assert!(!store.clear_expiry("missing"));
+
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert_eq!(Some(&value), store.peek(key.as_str()));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_clear_expiry_expired(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert(key.clone(), value.with_deletion(duration));
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert!(!store.clear_expiry(&key));
+        assert!(store.peek(key.as_str()).is_none());
+    }
+
+    #[rstest]
+    fn test_store_clear(mut store: Store, key: String, value: Entry) {
+        store.set_eviction(100, EvictionPolicy::AllkeysLru);
+        store.insert(key.clone(), value);
+        store.clear();
+        assert!(store.get(&key).is_none());
+        assert_eq!(0, store.len());
+        assert_eq!(0, store.budget.used_memory());
+        assert_eq!(100, store.budget.maxmemory.load(Ordering::Relaxed));
+        assert_eq!(EvictionPolicy::AllkeysLru, store.budget.eviction_policy());
+    }
+
     #[rstest]
     fn test_store_insert(mut store: Store, key: String, value: Entry) {
         store.insert(key.clone(), value.clone());
@@ -207,44 +1095,37 @@ mod tests {
     }
 
     #[rstest]
-    fn test_store_entry_occupied(mut store: Store, key: String, value: Entry) {
-        store.store.insert(key.clone(), value.clone());
-        match store.entry(key) {
-            std::collections::hash_map::Entry::Occupied(entry) => {
-                assert_eq!(value, *entry.get());
-            }
-            _ => panic!("Entry should be occupied."),
-        }
+    fn test_store_get_or_insert_occupied(mut store: Store, key: String, value: Entry) {
+        store.insert(key.clone(), value.clone());
+        let entry = store.get_or_insert(key, Entry::new_list());
+        assert_eq!(value, *entry);
     }
 
     #[rstest]
-    fn test_store_entry_vacant(mut store: Store, key: String) {
-        match store.entry(key) {
-            std::collections::hash_map::Entry::Vacant(_) => {}
-            _ => panic!("Entry should be vacant."),
-        }
+    fn test_store_get_or_insert_vacant(mut store: Store, key: String, value: Entry) {
+        let entry = store.get_or_insert(key.clone(), value.clone());
+        assert_eq!(value, *entry);
+        assert_eq!(Some(&value), store.peek(&key));
+        assert_eq!(1, store.scan_index.len());
     }
 
     #[rstest]
     #[tokio::test]
-    async fn test_store_entry_with_deletion(mut store: Store, key: String, mut value: Entry) {
+    async fn test_store_get_or_insert_with_deletion(mut store: Store, key: String, value: Entry) {
         tokio::time::pause();
-        let duration = 10;
-
-        value = value.with_deletion(duration);
-        store.store.insert(key.clone(), value.clone());
-        match store.entry(key.clone()) {
-            std::collections::hash_map::Entry::Occupied(entry) => {
-                assert_eq!(value, *entry.get());
-            }
-            _ => panic!("Entry should be occupied."),
-        }
+        let duration = 10u64;
+        store.insert(key.clone(), value.clone().with_deletion(duration));
+        assert_eq!(
+            value.clone().with_deletion(duration),
+            *store.get_or_insert(key.clone(), Entry::new_list())
+        );
 
         tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
-        match store.entry(key) {
-            std::collections::hash_map::Entry::Vacant(_) => {}
-            _ => panic!("Entry should be vacant."),
-        }
+        assert_eq!(
+            Entry::new_list(),
+            *store.get_or_insert(key, Entry::new_list())
+        );
+        assert_eq!(1, store.scan_index.len());
     }
 
     #[rstest]
@@ -259,7 +1140,7 @@ mod tests {
     }
 
     #[rstest]
-    fn test_store_get_vacant(mut store: Store, key: String) {
+    fn test_store_get_vacant(store: Store, key: String) {
         match store.get(&key) {
             None => {}
             _ => panic!("Entry should not exist."),
@@ -288,12 +1169,588 @@ mod tests {
         }
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_get_keeps_expired(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert(key.clone(), value.with_deletion(duration));
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert!(store.get(&key).is_none());
+        assert!(store.store.contains_key(&key));
+    }
+
+    #[rstest]
+    fn test_store_get_updates_last_access(mut store: Store, key: String, value: Entry) {
+        store.insert(key.clone(), value);
+        let before = store.store[&key].last_access.get();
+        store.get(&key);
+        assert!(store.store[&key].last_access.get() > before);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_peek_keeps_last_access(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        store.insert(key.clone(), value);
+        let before = store.store[&key].last_access.get();
+        tokio::time::advance(tokio::time::Duration::from_secs(1)).await;
+        assert!(store.peek(&key).is_some());
+        assert_eq!(before, store.store[&key].last_access.get());
+        assert_eq!(
+            tokio::time::Duration::from_secs(1),
+            store.store[&key].last_access.idle_time()
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_remove_expired_entries(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert("a".into(), value.clone());
+        store.insert("b".into(), value.with_deletion(duration));
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        store.remove_expired();
+        let mut keys = store.store.keys().cloned().collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(vec!["a"], keys);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_expires_index(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        let expires = |store: &Store| {
+            let mut keys = store.expires.iter().cloned().collect::<Vec<_>>();
+            keys.sort_unstable();
+            keys
+        };
+        store.insert("a".into(), value.clone().with_deletion(10u64));
+        store.insert("b".into(), value.clone());
+        store.get_or_insert("c".into(), value.clone().with_deletion(10u64));
+        assert_eq!(vec!["a", "c"], expires(&store));
+
+        store.set_expiry("b", tokio::time::Instant::now());
+        store.clear_expiry("a");
+        store.insert("c".into(), value);
+        assert_eq!(vec!["b"], expires(&store));
+
+        store.remove("b");
+        assert!(expires(&store).is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_remove_expired_sample(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert("a".into(), value.clone());
+        store.insert("b".into(), value.clone().with_deletion(duration));
+        store.insert("c".into(), value.with_deletion(duration * 2));
+        assert_eq!((2, 0), store.remove_expired_sample(5));
+
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert_eq!((2, 1), store.remove_expired_sample(5));
+        assert!(store.store.contains_key("a"));
+        assert!(!store.store.contains_key("b"));
+        assert!(store.store.contains_key("c"));
+        assert_eq!(1, store.expires.len());
+        assert_eq!(store.recount_memory(), store.used_memory());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_remove_expired_sample_bounded(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        for i in 0..100 {
+            store.insert(format!("key:{i:02}"), value.clone().with_deletion(10u64));
+        }
+        tokio::time::advance(tokio::time::Duration::from_millis(10)).await;
+        let (sampled, expired) = store.remove_expired_sample(5);
+        assert_eq!(5, sampled);
+        assert!((1..=5).contains(&expired));
+        assert_eq!(100 - expired, store.store.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_len(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        assert_eq!(0, store.len());
+
+        store.insert("a".into(), value.clone());
+        store.insert("b".into(), value.clone().with_deletion(duration));
+        assert_eq!(2, store.len());
+
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert_eq!(1, store.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_iter(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert("a".into(), value.clone());
+        store.insert("b".into(), value.clone().with_deletion(duration));
+
+        let mut keys = store.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(vec!["a", "b"], keys);
+
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        let keys = store.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        assert_eq!(vec!["a"], keys);
+    }
+
+    #[rstest]
+    #[case::single_batch(100, 1)]
+    #[case::exact_batches(5, 4)]
+    #[case::partial_batch(3, 7)]
+    fn test_store_scan(
+        mut store: Store,
+        value: Entry,
+        #[case] count: usize,
+        #[case] expected_calls: usize,
+    ) {
+        for i in 0..20 {
+            store.insert(format!("key:{i}"), value.clone());
+        }
+
+        let mut cursor = 0;
+        let mut keys = vec![];
+        let mut calls = 0;
+        loop {
+            let (next_cursor, batch) = store.scan(cursor, count);
+            assert!(batch.len() <= count);
+            keys.extend(batch.into_iter().map(|(key, _)| key.clone()));
+            calls += 1;
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+        keys.sort_unstable();
+        let mut expected = (0..20).map(|i| format!("key:{i}")).collect::<Vec<_>>();
+        expected.sort_unstable();
+        assert_eq!(expected, keys);
+        assert_eq!(expected_calls, calls);
+    }
+
+    #[rstest]
+    fn test_store_scan_concurrent_writes(mut store: Store, value: Entry) {
+        for i in 0..50 {
+            store.insert(format!("stable:{i}"), value.clone());
+            store.insert(format!("removed:{i}"), value.clone());
+        }
+
+        let mut cursor = 0;
+        let mut keys = vec![];
+        let mut round = 0;
+        loop {
+            let (next_cursor, batch) = store.scan(cursor, 7);
+            keys.extend(batch.into_iter().map(|(key, _)| key.clone()));
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+            for i in 0..10 {
+                store.remove(&format!("removed:{}", round * 10 + i));
+                store.insert(format!("added:{round}:{i}"), value.clone());
+            }
+            round += 1;
+        }
+
+        for i in 0..50 {
+            let key = format!("stable:{i}");
+            assert_eq!(1, keys.iter().filter(|scanned| **scanned == key).count());
+        }
+        let mut unique = keys.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), keys.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_scan_index(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert("a".into(), value.clone());
+        store.insert("a".into(), value.clone());
+        store.get_or_insert("b".into(), value.clone());
+        store.insert("c".into(), value.clone().with_deletion(duration));
+        store.insert("d".into(), value.clone());
+        store.remove("d");
+        assert_eq!(3, store.scan_index.len());
+
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        store.remove_expired();
+        let mut keys = store
+            .scan_index
+            .iter()
+            .map(|(position, key)| {
+                assert_eq!(scan_position(key), *position);
+                key.as_str()
+            })
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(vec!["a", "b"], keys);
+
+        store.clear();
+        assert!(store.scan_index.is_empty());
+    }
+
+    #[rstest]
+    fn test_store_scan_empty(store: Store) {
+        assert_eq!((0, vec![]), store.scan(0, 10));
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_scan_expired(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert(key, value.with_deletion(duration));
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert_eq!((0, vec![]), store.scan(0, 10));
+    }
+
+    #[rstest]
+    fn test_store_random_key(mut store: Store, value: Entry) {
+        let keys = ["a", "b", "c"];
+        for key in keys {
+            store.insert(key.into(), value.clone());
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..100 {
+            let key = store.random_key().expect("Store should not be empty.");
+            assert!(keys.contains(&key.as_str()));
+            seen.insert(key);
+        }
+        assert_eq!(keys.len(), seen.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_random_key_mostly_expired(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        for i in 0..1000 {
+            store.insert(
+                format!("expired:{i}"),
+                value.clone().with_deletion(duration),
+            );
+        }
+        store.insert("key".into(), value);
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        for _ in 0..10 {
+            assert_eq!(Some("key".into()), store.random_key());
+        }
+    }
+
+    #[rstest]
+    fn test_store_random_key_empty(store: Store) {
+        assert!(store.random_key().is_none());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_random_key_expired(mut store: Store, key: String, value: Entry) {
+        tokio::time::pause();
+        let duration = 10u64;
+        store.insert(key, value.with_deletion(duration));
+        tokio::time::advance(tokio::time::Duration::from_millis(duration)).await;
+        assert!(store.random_key().is_none());
+    }
+
+    #[rstest]
+    fn test_store_used_memory(mut store: Store, value: Entry) {
+        assert_eq!(0, store.used_memory());
+        store.insert("a".into(), value.clone());
+        store.insert("bb".into(), value);
+        assert_eq!(13, store.used_memory());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_store_used_memory_tracked(mut store: Store, value: Entry) {
+        tokio::time::pause();
+        store.insert("a".into(), value.clone());
+        store.insert("a".into(), Entry::new_string("longer value"));
+        assert_eq!(13, store.used_memory());
+
+        if let EntryValue::List(list) =
+            &mut store.get_or_insert("b".into(), Entry::new_list()).value
+        {
+            list.push("abc".into());
+        }
+        store.track_resize(0, 3);
+        assert_eq!(13 + 4, store.used_memory());
+
+        if let Some(EntryValue::List(list)) = store.get_entry("b").map(|entry| &mut entry.value) {
+            list[0] = "a".into();
+        }
+        store.track_resize(3, 1);
+        assert_eq!(13 + 2, store.used_memory());
+
+        store.remove("b");
+        assert_eq!(13, store.used_memory());
+
+        store.insert("c".into(), value.clone().with_deletion(10u64));
+        assert_eq!(13 + 6, store.used_memory());
+        tokio::time::advance(tokio::time::Duration::from_millis(10)).await;
+        store.remove_expired();
+        assert_eq!(13, store.used_memory());
+
+        store.insert("d".into(), value);
+        store.clear();
+        assert_eq!(0, store.used_memory());
+    }
+
+    #[rstest]
+    #[case::unlimited(0, false)]
+    #[case::within(6, false)]
+    #[case::over(5, true)]
+    fn test_store_is_over_budget(
+        mut store: Store,
+        value: Entry,
+        #[case] maxmemory: u64,
+        #[case] expected: bool,
+    ) {
+        store.set_eviction(maxmemory, EvictionPolicy::NoEviction);
+        store.insert("a".into(), value);
+        assert_eq!(expected, store.is_over_budget());
+    }
+
     // ---- Shared store ----
     #[rstest]
     #[tokio::test]
     async fn test_shared_store() {
         let shared_store = new();
-        let store = shared_store.try_lock().expect("Should acquire lock");
-        assert_eq!(HashMap::new(), store.store);
+        let store = shared_store.try_write().expect("Should acquire lock");
+        assert!(store.store.is_empty());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_shared_store_concurrent_reads() {
+        let shared_store = new();
+        let _first = shared_store.try_read().expect("Should acquire read lock");
+        let _second = shared_store
+            .try_read()
+            .expect("Should acquire a second read lock");
+        assert!(shared_store.try_write().is_err());
+    }
+
+    // ---- Memory budget ----
+    #[rstest]
+    #[tokio::test]
+    async fn test_databases_share_budget(value: Entry) {
+        let databases = new_databases(2);
+        databases[0]
+            .read()
+            .await
+            .set_eviction(10, EvictionPolicy::NoEviction);
+        databases[0].write().await.insert("a".into(), value.clone());
+        assert!(!databases[1].read().await.is_over_budget());
+
+        databases[1].write().await.insert("b".into(), value);
+        for store in databases.iter() {
+            assert!(store.read().await.is_over_budget());
+        }
+        assert_eq!(12, databases[0].read().await.budget.used_memory());
+
+        databases[1].write().await.clear();
+        assert_eq!(6, databases[0].read().await.budget.used_memory());
+    }
+
+    #[rstest]
+    #[case::noeviction(EvictionPolicy::NoEviction)]
+    #[case::allkeys_lru(EvictionPolicy::AllkeysLru)]
+    #[tokio::test]
+    async fn test_free_memory_unlimited(value: Entry, #[case] policy: EvictionPolicy) {
+        let databases = new_databases(1);
+        {
+            let mut store = databases[0].write().await;
+            store.set_eviction(0, policy);
+            store.insert("a".into(), value);
+        }
+        assert!(free_memory(&databases).await.is_ok());
+        assert_eq!(1, databases[0].read().await.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_free_memory_noeviction(value: Entry) {
+        let databases = new_databases(1);
+        databases[0]
+            .read()
+            .await
+            .set_eviction(10, EvictionPolicy::NoEviction);
+        databases[0].write().await.insert("a".into(), value.clone());
+        assert!(free_memory(&databases).await.is_ok());
+
+        databases[0].write().await.insert("b".into(), value);
+        assert_eq!(
+            "OOM command not allowed when used memory > 'maxmemory'.",
+            free_memory(&databases).await.unwrap_err().to_string()
+        );
+        assert_eq!(2, databases[0].read().await.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_free_memory_allkeys_lru(value: Entry) {
+        let databases = new_databases(1);
+        {
+            let mut store = databases[0].write().await;
+            store.set_eviction(14, EvictionPolicy::AllkeysLru);
+            store.insert("a".into(), value.clone());
+            store.insert("b".into(), value.clone());
+            store.insert("c".into(), value);
+            store.get("a");
+        }
+
+        assert!(free_memory(&databases).await.is_ok());
+        let store = databases[0].read().await;
+        assert!(store.used_memory() <= 14);
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+        assert!(store.get("c").is_some());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_free_memory_allkeys_lru_sampled(value: Entry) {
+        let databases = new_databases(1);
+        {
+            let mut store = databases[0].write().await;
+            for i in 0..1000 {
+                store.insert(format!("key:{i:03}"), value.clone());
+            }
+            store.get("key:000");
+            store.set_eviction(store.used_memory() / 2, EvictionPolicy::AllkeysLru);
+        }
+
+        assert!(free_memory(&databases).await.is_ok());
+        let store = databases[0].read().await;
+        assert!(!store.is_over_budget());
+        assert!(store.len() < 1000);
+        assert!(store.get("key:000").is_some());
+        assert_eq!(store.len(), store.scan_index.len());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_free_memory_across_databases(value: Entry) {
+        let databases = new_databases(2);
+        databases[0]
+            .read()
+            .await
+            .set_eviction(14, EvictionPolicy::AllkeysLru);
+        databases[0].write().await.insert("a".into(), value.clone());
+        databases[1].write().await.insert("b".into(), value.clone());
+        databases[0].write().await.insert("c".into(), value);
+        databases[0].read().await.get("a");
+
+        assert!(free_memory(&databases).await.is_ok());
+        assert_eq!(2, databases[0].read().await.len());
+        assert_eq!(0, databases[1].read().await.len());
+    }
+
+    // ---- Active expiration ----
+    #[rstest]
+    #[tokio::test]
+    async fn test_active_expire_enabled(key: String, value: Entry) {
+        tokio::time::pause();
+        let databases = new_databases(2);
+        let task = spawn_active_expire(
+            databases.clone(),
+            new_active_expire(),
+            crate::latency::new(),
+        );
+        for store in databases.iter() {
+            store
+                .write()
+                .await
+                .insert(key.clone(), value.clone().with_deletion(10u64));
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(
+            ACTIVE_EXPIRE_INTERVAL_MS * 2,
+        ))
+        .await;
+        for store in databases.iter() {
+            assert!(!store.read().await.store.contains_key(&key));
+        }
+        task.abort();
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_active_expire_cycle_repeats_while_stale(value: Entry) {
+        tokio::time::pause();
+        let databases = new_databases(2);
+        for store in databases.iter() {
+            let mut store = store.write().await;
+            for i in 0..1000 {
+                store.insert(format!("key:{i:03}"), value.clone().with_deletion(10u64));
+            }
+            store.insert("persistent".into(), value.clone());
+        }
+        tokio::time::advance(tokio::time::Duration::from_millis(10)).await;
+
+        active_expire_cycle(&databases, &new_active_expire()).await;
+        for store in databases.iter() {
+            let store = store.read().await;
+            assert_eq!(vec!["persistent"], store.store.keys().collect::<Vec<_>>());
+            assert_eq!(store.recount_memory(), store.used_memory());
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_active_expire_cycle_stops_when_fresh(value: Entry) {
+        tokio::time::pause();
+        let databases = new_databases(1);
+        {
+            let mut store = databases[0].write().await;
+            for i in 0..1000 {
+                store.insert(format!("key:{i:03}"), value.clone().with_deletion(10u64));
+            }
+            store.insert("expired".into(), value.with_deletion(0u64));
+        }
+
+        active_expire_cycle(&databases, &new_active_expire()).await;
+        assert!(databases[0].read().await.store.len() >= 1000);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_active_expire_disabled(key: String, value: Entry) {
+        tokio::time::pause();
+        let databases = new_databases(1);
+        let active_expire = new_active_expire();
+        active_expire.store(false, Ordering::Relaxed);
+        let task = spawn_active_expire(databases.clone(), active_expire, crate::latency::new());
+        databases[0]
+            .write()
+            .await
+            .insert(key.clone(), value.with_deletion(10u64));
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(
+            ACTIVE_EXPIRE_INTERVAL_MS * 5,
+        ))
+        .await;
+        let mut store = databases[0].write().await;
+        assert!(store.store.contains_key(&key));
+        assert!(store.get(&key).is_none());
+        assert!(store.remove(&key).is_none());
+        assert!(!store.store.contains_key(&key));
+        task.abort();
     }
 }
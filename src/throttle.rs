@@ -0,0 +1,91 @@
+//! This module contains the throttling of the connections accepted from each IP address.
+use std::collections::HashMap;
+
+/// The length of the windows in which connections are counted.
+const WINDOW: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Counts the connections from each IP address in windows of a second, so that the addresses
+/// opening too many connections at once can be refused.
+#[derive(Debug)]
+pub struct Throttle {
+    /// The start of the current window.
+    window_start: std::time::Instant,
+    /// The number of connections from each address in the current window.
+    connections: HashMap<std::net::IpAddr, u64>,
+}
+
+impl Throttle {
+    /// Creates a throttle without any connection counted.
+    pub fn new() -> Self {
+        Self {
+            window_start: std::time::Instant::now(),
+            connections: HashMap::new(),
+        }
+    }
+
+    /// Counts a connection from the address at the instant, returning whether it is within the
+    /// limit of connections per second, where 0 is no limit.
+    /// Refused connections are counted too, so an address must slow down to be accepted again.
+    pub fn allow(&mut self, ip: std::net::IpAddr, limit: u64, now: std::time::Instant) -> bool {
+        if now.duration_since(self.window_start) >= WINDOW {
+            self.window_start = now;
+            self.connections.clear();
+        }
+        if limit == 0 {
+            return true;
+        }
+        let connections = self.connections.entry(ip.to_canonical()).or_default();
+        *connections += 1;
+        *connections <= limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    // --- Fixtures ---
+    #[fixture]
+    fn throttle() -> Throttle {
+        Throttle::new()
+    }
+
+    fn ip(address: &str) -> std::net::IpAddr {
+        address.parse().unwrap()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    fn test_allow(mut throttle: Throttle) {
+        let now = throttle.window_start;
+        assert!(throttle.allow(ip("10.0.0.1"), 2, now));
+        assert!(throttle.allow(ip("10.0.0.1"), 2, now));
+        assert!(!throttle.allow(ip("10.0.0.1"), 2, now));
+        assert!(throttle.allow(ip("10.0.0.2"), 2, now));
+    }
+
+    #[rstest]
+    fn test_allow_mapped_address(mut throttle: Throttle) {
+        let now = throttle.window_start;
+        assert!(throttle.allow(ip("10.0.0.1"), 1, now));
+        assert!(!throttle.allow(ip("::ffff:10.0.0.1"), 1, now));
+    }
+
+    #[rstest]
+    fn test_allow_next_window(mut throttle: Throttle) {
+        let now = throttle.window_start;
+        assert!(throttle.allow(ip("10.0.0.1"), 1, now));
+        assert!(!throttle.allow(ip("10.0.0.1"), 1, now + WINDOW / 2));
+        assert!(throttle.allow(ip("10.0.0.1"), 1, now + WINDOW));
+    }
+
+    #[rstest]
+    fn test_allow_unlimited(mut throttle: Throttle) {
+        let now = throttle.window_start;
+        for _ in 0..100 {
+            assert!(throttle.allow(ip("10.0.0.1"), 0, now));
+        }
+        assert!(throttle.connections.is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+//! This module contains the TLS setup of the connections accepted on `tls-port`.
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+
+/// Creates the acceptor of TLS connections, presenting the certificate chain of the certificate
+/// file and signing with the private key of the key file, both PEM encoded.
+/// Clients are not asked for a certificate.
+pub fn new_acceptor(cert_file: &str, key_file: &str) -> Result<tokio_rustls::TlsAcceptor> {
+    let certificates = rustls::pki_types::CertificateDer::pem_file_iter(cert_file)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("Failed to load the certificate file '{cert_file}'"))?;
+    if certificates.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No certificate found in the certificate file '{cert_file}'"
+        ));
+    }
+    let key = rustls::pki_types::PrivateKeyDer::from_pem_file(key_file)
+        .with_context(|| format!("Failed to load the key file '{key_file}'"))?;
+
+    let config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .context("Failed to set the TLS protocol versions")?
+    .with_no_client_auth()
+    .with_single_cert(certificates, key)
+    .context("Failed to use the certificate and key")?;
+    Ok(tokio_rustls::TlsAcceptor::from(Arc::new(config)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// The PEM files of a self-signed certificate for `localhost`, removed when dropped.
+    struct CertificateFiles {
+        certificate: rcgen::Certificate,
+        cert_file: std::path::PathBuf,
+        key_file: std::path::PathBuf,
+    }
+
+    impl Drop for CertificateFiles {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.cert_file);
+            let _ = std::fs::remove_file(&self.key_file);
+        }
+    }
+
+    // --- Fixtures ---
+    #[fixture]
+    fn files(#[default("default")] name: &str) -> CertificateFiles {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let path = |kind: &str| {
+            std::env::temp_dir().join(format!(
+                "redis-rs-tls-{}-{name}-{kind}.pem",
+                std::process::id()
+            ))
+        };
+        let files = CertificateFiles {
+            cert_file: path("cert"),
+            key_file: path("key"),
+            certificate: certified.cert,
+        };
+        std::fs::write(&files.cert_file, files.certificate.pem()).unwrap();
+        std::fs::write(&files.key_file, certified.signing_key.serialize_pem()).unwrap();
+        files
+    }
+
+    fn path_str(path: &std::path::Path) -> &str {
+        path.to_str().unwrap()
+    }
+
+    // --- Tests ---
+    #[rstest]
+    #[tokio::test]
+    async fn test_new_acceptor(#[with("handshake")] files: CertificateFiles) {
+        let acceptor = new_acceptor(path_str(&files.cert_file), path_str(&files.key_file)).unwrap();
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(files.certificate.der().clone()).unwrap();
+        let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+
+        let (client, server) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut stream = acceptor.accept(server).await.unwrap();
+            let mut buffer = [0; 4];
+            stream.read_exact(&mut buffer).await.unwrap();
+            stream.write_all(&buffer).await.unwrap();
+            stream.flush().await.unwrap();
+        });
+        let mut client = connector
+            .connect("localhost".try_into().unwrap(), client)
+            .await
+            .unwrap();
+        client.write_all(b"PING").await.unwrap();
+        let mut buffer = [0; 4];
+        client.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(b"PING", &buffer);
+        server.await.unwrap();
+    }
+
+    // --- Errors ---
+    #[rstest]
+    fn test_new_acceptor_missing_cert_file(#[with("missing-cert")] files: CertificateFiles) {
+        let err = new_acceptor("missing.pem", path_str(&files.key_file))
+            .err()
+            .unwrap();
+        assert_eq!(
+            "Failed to load the certificate file 'missing.pem'",
+            err.to_string()
+        );
+    }
+
+    #[rstest]
+    fn test_new_acceptor_missing_key_file(#[with("missing-key")] files: CertificateFiles) {
+        let err = new_acceptor(path_str(&files.cert_file), "missing.pem")
+            .err()
+            .unwrap();
+        assert_eq!("Failed to load the key file 'missing.pem'", err.to_string());
+    }
+
+    #[rstest]
+    fn test_new_acceptor_no_certificate(#[with("no-certificate")] files: CertificateFiles) {
+        let key_file = path_str(&files.key_file);
+        let err = new_acceptor(key_file, key_file).err().unwrap();
+        assert_eq!(
+            format!("No certificate found in the certificate file '{key_file}'"),
+            err.to_string()
+        );
+    }
+}